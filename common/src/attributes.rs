@@ -7,19 +7,126 @@ use crate::constants::{
 
 pub struct Attributes {
     pub wasm: bool,
+    pub panic_message: bool,
     pub memory_size: u64,
     pub stack_size: u64,
+    pub heap_size: Option<u64>,
+    pub allocator: Allocator,
     pub max_input_size: u64,
     pub max_output_size: u64,
+    pub io_commitment: Option<IoCommitment>,
+}
+
+/// Hash function used to commit to the program inputs and outputs when
+/// `#[provable(io_commitment = "...")]` is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IoCommitment {
+    Keccak256,
+    Sha256,
+}
+
+impl IoCommitment {
+    fn parse(value: &str) -> Self {
+        match value {
+            "keccak" | "keccak256" => IoCommitment::Keccak256,
+            "sha256" => IoCommitment::Sha256,
+            _ => panic!("invalid io_commitment, expected \"keccak\" or \"sha256\""),
+        }
+    }
+}
+
+/// Global allocator installed in the guest, selected via `#[provable(allocator = "...")]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Allocator {
+    /// Never frees memory; the cheapest option for short-lived guests.
+    #[default]
+    Bump,
+    /// Reuses freed blocks and panics if the heap is exhausted.
+    FreeList,
+}
+
+impl Allocator {
+    fn parse(value: &str) -> Self {
+        match value {
+            "bump" => Allocator::Bump,
+            "free_list" => Allocator::FreeList,
+            _ => panic!("invalid allocator, expected \"bump\" or \"free_list\""),
+        }
+    }
+}
+
+/// Identifies a provable function in a guest built with a dispatch table, where the
+/// selector precedes the function's arguments in the input region (FNV-1a of the name).
+pub fn function_selector(func_name: &str) -> u32 {
+    func_name.bytes().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
+/// Attributes specific to `#[jolt::test]`.
+pub struct TestAttributes {
+    /// Also prove and verify the execution (using a mock commitment scheme).
+    pub prove: bool,
+    pub max_cycles: Option<u64>,
+}
+
+/// Separates the `#[jolt::test]`-specific attributes from those shared with
+/// `#[jolt::provable]`.
+pub fn split_test_attributes(attr: Vec<NestedMeta>) -> (TestAttributes, Vec<NestedMeta>) {
+    let mut test_attributes = TestAttributes {
+        prove: false,
+        max_cycles: None,
+    };
+    let mut rest = Vec::new();
+
+    for attr in attr {
+        match &attr {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("prove") => {
+                test_attributes.prove = true;
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))
+                if path.is_ident("max_cycles") =>
+            {
+                let value = match lit {
+                    Lit::Int(lit) => lit.base10_parse().unwrap(),
+                    _ => panic!("expected integer literal"),
+                };
+                test_attributes.max_cycles = Some(value);
+            }
+            _ => rest.push(attr),
+        }
+    }
+
+    (test_attributes, rest)
 }
 
 pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
     let mut attributes = HashMap::<_, u64>::new();
     let mut wasm = false;
+    let mut panic_message = false;
+    let mut io_commitment = None;
+    let mut allocator = Allocator::default();
 
     for attr in attr {
         match attr {
             NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
+                if path.is_ident("io_commitment") {
+                    let value = match lit {
+                        Lit::Str(lit) => lit.value(),
+                        _ => panic!("expected string literal"),
+                    };
+                    io_commitment = Some(IoCommitment::parse(&value));
+                    continue;
+                }
+                if path.is_ident("allocator") {
+                    let value = match lit {
+                        Lit::Str(lit) => lit.value(),
+                        _ => panic!("expected string literal"),
+                    };
+                    allocator = Allocator::parse(&value);
+                    continue;
+                }
+
                 let value: u64 = match lit {
                     Lit::Int(lit) => lit.base10_parse().unwrap(),
                     _ => panic!("expected integer literal"),
@@ -28,6 +135,7 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
                 match ident.to_string().as_str() {
                     "memory_size" => attributes.insert("memory_size", value),
                     "stack_size" => attributes.insert("stack_size", value),
+                    "heap_size" => attributes.insert("heap_size", value),
                     "max_input_size" => attributes.insert("max_input_size", value),
                     "max_output_size" => attributes.insert("max_output_size", value),
                     _ => panic!("invalid attribute"),
@@ -36,6 +144,9 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
             NestedMeta::Meta(Meta::Path(path)) if path.is_ident("wasm") => {
                 wasm = true;
             }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("panic_message") => {
+                panic_message = true;
+            }
             _ => panic!("expected integer literal"),
         }
     }
@@ -44,6 +155,7 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
         .get("memory_size")
         .unwrap_or(&DEFAULT_MEMORY_SIZE);
     let stack_size = *attributes.get("stack_size").unwrap_or(&DEFAULT_STACK_SIZE);
+    let heap_size = attributes.get("heap_size").copied();
     let max_input_size = *attributes
         .get("max_input_size")
         .unwrap_or(&DEFAULT_MAX_INPUT_SIZE);
@@ -53,9 +165,13 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
 
     Attributes {
         wasm,
+        panic_message,
         memory_size,
         stack_size,
+        heap_size,
+        allocator,
         max_input_size,
         max_output_size,
+        io_commitment,
     }
 }
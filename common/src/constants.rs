@@ -11,6 +11,9 @@ pub const DEFAULT_MEMORY_SIZE: u64 = 10 * 1024 * 1024;
 pub const DEFAULT_STACK_SIZE: u64 = 4096;
 pub const DEFAULT_MAX_INPUT_SIZE: u64 = 4096;
 pub const DEFAULT_MAX_OUTPUT_SIZE: u64 = 4096;
+/// Symbol of the guest buffer whose contents the tracer returns to the host as
+/// `JoltDevice::private_outputs`.
+pub const PRIVATE_OUTPUT_SYMBOL: &str = "JOLT_PRIVATE_OUTPUT";
 
 pub const fn virtual_register_index(index: u64) -> u64 {
     index + VIRTUAL_REGISTER_COUNT
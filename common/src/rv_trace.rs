@@ -588,6 +588,10 @@ pub struct JoltDevice {
     pub outputs: Vec<u8>,
     pub panic: bool,
     pub memory_layout: MemoryLayout,
+    /// Bytes the guest hands back to the host outside of the public statement: the final
+    /// contents of its `JOLT_PRIVATE_OUTPUT` buffer, if it declares one. They are not
+    /// proven, so the host must check them against a digest in `outputs`.
+    pub private_outputs: Vec<u8>,
 }
 
 impl JoltDevice {
@@ -597,6 +601,7 @@ impl JoltDevice {
             outputs: Vec::new(),
             panic: false,
             memory_layout: MemoryLayout::new(max_input_size, max_output_size),
+            private_outputs: Vec::new(),
         }
     }
 
@@ -647,6 +652,18 @@ impl JoltDevice {
         self.inputs.len() + self.outputs.len()
     }
 
+    /// Returns the portion of the device that is part of the public statement.
+    /// Private outputs are omitted, as are the inputs if the memory layout marks
+    /// them as private.
+    pub fn public_io(&self) -> Self {
+        let mut public_io = self.clone();
+        public_io.private_outputs.clear();
+        if self.memory_layout.private_inputs {
+            public_io.inputs.clear();
+        }
+        public_io
+    }
+
     pub fn is_input(&self, address: u64) -> bool {
         address >= self.memory_layout.input_start && address < self.memory_layout.input_end
     }
@@ -684,6 +701,10 @@ pub struct MemoryLayout {
     pub output_end: u64,
     pub panic: u64,
     pub termination: u64,
+    /// If set, the input region is not part of the public statement: the prover
+    /// commits to the inputs instead, and the guest is expected to publish a
+    /// digest of them in its outputs (see `#[provable(io_commitment = ...)]`).
+    pub private_inputs: bool,
 }
 
 impl MemoryLayout {
@@ -716,6 +737,7 @@ impl MemoryLayout {
             output_end,
             panic,
             termination,
+            private_inputs: false,
         }
     }
 }
@@ -215,9 +215,9 @@ pub fn main() {
     let (prove_alloc, verify_alloc) = guest::build_alloc();
     let (prove_model, verify_model) = guest::build_load_model();
 
-    let (model_output, model_proof) = prove_model(model_input);
+    let (model_output, model_proof) = prove_model(model_input).unwrap();
 
-    let (output, proof) = prove_alloc(41);
+    let (output, proof) = prove_alloc(41).unwrap();
     let is_valid = verify_alloc(proof);
 
     println!("output: {:?}", output);
@@ -1,5 +1,7 @@
 #![cfg_attr(feature = "guest", no_std)]
 
+use serde::{Deserialize, Serialize};
+
 #[jolt::provable]
 fn collatz_convergence_range(start: u128, end: u128) -> u128 {
     let mut max_num_steps = 0;
@@ -26,3 +28,57 @@ fn collatz_convergence(n: u128) -> u128 {
     }
     return num_steps;
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum CollatzError {
+    /// The sequence starting at 0 never reaches 1.
+    Zero,
+    /// The sequence exceeds `u128::MAX` before reaching 1.
+    Overflow,
+}
+
+#[jolt::provable]
+fn collatz_convergence_checked(n: u128) -> Result<u128, CollatzError> {
+    if n == 0 {
+        return Err(CollatzError::Zero);
+    }
+    let mut n = n;
+    let mut num_steps = 0;
+    while n != 1 {
+        if n % 2 == 0 {
+            n /= 2;
+        } else {
+            n = n
+                .checked_mul(3)
+                .and_then(|n| n.checked_add(1))
+                .ok_or(CollatzError::Overflow)?;
+        }
+        num_steps += 1;
+    }
+    Ok(num_steps)
+}
+
+#[jolt::provable(panic_message)]
+fn collatz_convergence_bounded(n: u128, max_steps: u128) -> u128 {
+    let mut n = n;
+    let mut num_steps = 0;
+    while n != 1 {
+        assert!(
+            num_steps < max_steps,
+            "did not converge within {} steps",
+            max_steps
+        );
+        if n % 2 == 0 {
+            n /= 2;
+        } else {
+            n += (n << 1) + 1;
+        }
+        num_steps += 1;
+    }
+    num_steps
+}
+
+#[jolt::test(prove)]
+fn collatz_convergence_of_27() -> u128 {
+    collatz_convergence(27)
+}
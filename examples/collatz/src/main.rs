@@ -1,8 +1,8 @@
-pub fn main() {
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Prove/verify convergence for a single number:
     let (prove_collatz_single, verify_collatz_single) = guest::build_collatz_convergence();
 
-    let (output, proof) = prove_collatz_single(19);
+    let (output, proof) = prove_collatz_single(19)?;
     let is_valid = verify_collatz_single(proof);
 
     println!("output: {}", output);
@@ -14,9 +14,63 @@ pub fn main() {
 
     // https://www.reddit.com/r/compsci/comments/gk9x6g/collatz_conjecture_news_recently_i_managed_to/
     let start: u128 = 1 << 68;
-    let (output, proof) = prove_collatz_convergence(start, start + 100);
+    let (output, proof) = prove_collatz_convergence(start, start + 100)?;
     let is_valid = verify_collatz_convergence(proof);
 
     println!("output: {}", output);
     println!("valid: {}", is_valid);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use guest::CollatzError;
+    use jolt_sdk::{GuestError, JoltHyperKZGProof, Serializable};
+
+    #[test]
+    fn checked_convergence_round_trip() {
+        let (prove_checked, verify_checked) = guest::build_collatz_convergence_checked();
+
+        let result: Result<(u128, JoltHyperKZGProof), GuestError<CollatzError>> = prove_checked(27);
+        let (num_steps, proof) = result.unwrap();
+        assert_eq!(num_steps, 111);
+        assert!(verify_checked(proof));
+
+        // The guest's error is proven just like its output
+        let Err(GuestError::Err { error, proof }) = prove_checked(0) else {
+            panic!("expected the guest to return Err");
+        };
+        assert_eq!(error, CollatzError::Zero);
+        assert!(verify_checked(proof));
+    }
+
+    #[test]
+    fn bounded_convergence_panic_message() {
+        let (program, preprocessing) = guest::preprocess_collatz_convergence_bounded();
+        let verifier_preprocessing = jolt_sdk::JoltVerifierPreprocessing::from(&preprocessing);
+
+        let Err(GuestError::Panic { panic, proof }) =
+            guest::prove_collatz_convergence_bounded(program, preprocessing, 27, 100)
+        else {
+            panic!("expected the guest to panic");
+        };
+        assert_eq!(
+            panic.message.as_deref(),
+            Some("did not converge within 100 steps")
+        );
+        // The proof attests to the panic, and is not a proof of normal execution
+        let proof_bytes = proof.serialize_to_bytes().unwrap();
+        assert!(jolt_sdk::verify_with_panic_status(
+            verifier_preprocessing.clone(),
+            proof,
+            true
+        ));
+        let proof = JoltHyperKZGProof::deserialize_from_bytes(&proof_bytes).unwrap();
+        assert!(!jolt_sdk::verify_with_panic_status(
+            verifier_preprocessing,
+            proof,
+            false
+        ));
+    }
 }
@@ -1,13 +1,15 @@
-pub fn main() {
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (prove_fib, verify_fib) = guest::build_fib();
     let program_summary = guest::analyze_fib(10);
     program_summary
         .write_to_file("fib_10.txt".into())
         .expect("should write");
 
-    let (output, proof) = prove_fib(50);
+    let (output, proof) = prove_fib(50)?;
     let is_valid = verify_fib(proof);
 
     println!("output: {}", output);
     println!("valid: {}", is_valid);
+
+    Ok(())
 }
@@ -1,7 +1,7 @@
-pub fn main() {
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (prove, verify) = guest::build_memory_ops();
 
-    let (output, proof) = prove();
+    let (output, proof) = prove()?;
     let is_valid = verify(proof);
 
     println!(
@@ -9,4 +9,6 @@ pub fn main() {
         output.0, output.1, output.2, output.3
     );
     println!("valid: {}", is_valid);
+
+    Ok(())
 }
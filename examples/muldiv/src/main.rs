@@ -1,9 +1,11 @@
-pub fn main() {
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (prove, verify) = guest::build_muldiv();
 
-    let (output, proof) = prove(12031293, 17, 92);
+    let (output, proof) = prove(12031293, 17, 92)?;
     let is_valid = verify(proof);
 
     println!("output: {}", output);
     println!("valid: {}", is_valid);
+
+    Ok(())
 }
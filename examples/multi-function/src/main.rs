@@ -1,16 +1,39 @@
-pub fn main() {
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (prove_add, verify_add) = guest::build_add();
     let (prove_mul, verify_mul) = guest::build_mul();
 
-    let (output, proof) = prove_add(5, 10);
+    let (output, proof) = prove_add(5, 10)?;
     let is_valid = verify_add(proof);
 
     println!("add output: {}", output);
     println!("add valid: {}", is_valid);
 
-    let (output, proof) = prove_mul(5, 10);
+    let (output, proof) = prove_mul(5, 10)?;
     let is_valid = verify_mul(proof);
 
     println!("mul output: {}", output);
     println!("mul valid: {}", is_valid);
+
+    dispatch()
+}
+
+pub fn dispatch() -> Result<(), Box<dyn std::error::Error>> {
+    // Both functions are built into a single ELF, sharing one preprocessing.
+    let (program, preprocessing) = guest::preprocess_dispatch();
+    let verifier_preprocessing = jolt_sdk::JoltVerifierPreprocessing::from(&preprocessing);
+
+    let (output, proof) = guest::prove_add(program.clone(), preprocessing.clone(), 5, 10)?;
+    let is_valid =
+        jolt_sdk::verify_dispatch(verifier_preprocessing.clone(), proof, guest::ADD_SELECTOR);
+
+    println!("dispatched add output: {}", output);
+    println!("dispatched add valid: {}", is_valid);
+
+    let (output, proof) = guest::prove_mul(program, preprocessing, 5, 10)?;
+    let is_valid = jolt_sdk::verify_dispatch(verifier_preprocessing, proof, guest::MUL_SELECTOR);
+
+    println!("dispatched mul output: {}", output);
+    println!("dispatched mul valid: {}", is_valid);
+
+    Ok(())
 }
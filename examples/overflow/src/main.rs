@@ -1,13 +1,13 @@
 use std::any::Any;
 use std::panic;
 
-pub fn main() {
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (prove_overflow_stack, _) = guest::build_overflow_stack();
 
     let res = panic::catch_unwind(|| {
         // trying to allocate 1024 elems array and sum it up
         // with stack_size=1024, should panic
-        let (_, _) = prove_overflow_stack();
+        let (_, _) = prove_overflow_stack().unwrap();
     });
     handle_result(res);
 
@@ -15,7 +15,7 @@ pub fn main() {
     let (prove_overflow_heap, _) = guest::build_overflow_heap();
 
     let res = panic::catch_unwind(|| {
-        let (_, _) = prove_overflow_heap();
+        let (_, _) = prove_overflow_heap().unwrap();
     });
     handle_result(res);
 
@@ -24,11 +24,13 @@ pub fn main() {
     let (prove_allocate_stack_with_increased_size, verfiy_allocate_stack_with_increased_size) =
         guest::build_allocate_stack_with_increased_size();
 
-    let (output, proof) = prove_allocate_stack_with_increased_size();
+    let (output, proof) = prove_allocate_stack_with_increased_size()?;
     let is_valid = verfiy_allocate_stack_with_increased_size(proof);
 
     println!("output: {}", output);
     println!("valid: {}", is_valid);
+
+    Ok(())
 }
 
 fn handle_result(res: Result<(), Box<dyn Any + Send>>) {
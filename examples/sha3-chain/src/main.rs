@@ -1,11 +1,13 @@
-pub fn main() {
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (prove_sha3_chain, verify_sha3_chain) = guest::build_sha3_chain();
 
     let input = [5u8; 32];
     let iters = 100;
-    let (output, proof) = prove_sha3_chain(input, iters);
+    let (output, proof) = prove_sha3_chain(input, iters)?;
     let is_valid = verify_sha3_chain(proof);
 
     println!("output: {}", hex::encode(output));
     println!("valid: {}", is_valid);
+
+    Ok(())
 }
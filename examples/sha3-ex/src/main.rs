@@ -1,10 +1,12 @@
-pub fn main() {
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (prove_sha3, verify_sha3) = guest::build_sha3();
 
     let input: &[u8] = &[5u8; 32];
-    let (output, proof) = prove_sha3(input);
+    let (output, proof) = prove_sha3(input)?;
     let is_valid = verify_sha3(proof);
 
     println!("output: {}", hex::encode(output));
     println!("valid: {}", is_valid);
+
+    Ok(())
 }
@@ -216,7 +216,7 @@ pub fn main() {
     // guest::load_model(model_input);
     let (test_prove, test_verify) = guest::build_load_model();
     println!("BUILT MODEL"); 
-    let (test_output, test_proof) = test_prove(model_input);
+    let (test_output, test_proof) = test_prove(model_input).unwrap();
     println!("PROVED MODEL");
     let test_is_valid = test_verify(test_proof);
 
@@ -225,7 +225,7 @@ pub fn main() {
 
     // let (prove, verify) = guest::build_int_to_string();
     //
-    // let (output, proof) = prove(81);
+    // let (output, proof) = prove(81).unwrap();
     // let is_valid = verify(proof);
     //
     // println!("int to string output: {:?}", output);
@@ -233,7 +233,7 @@ pub fn main() {
     //
     // let (prove, verify) = guest::build_string_concat();
     //
-    // let (output, proof) = prove(20);
+    // let (output, proof) = prove(20).unwrap();
     // let is_valid = verify(proof);
     //
     // println!("string concat output: {:?}", output);
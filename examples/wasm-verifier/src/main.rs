@@ -0,0 +1,26 @@
+use jolt_sdk::{JoltHyperKZGProof, JoltHyperKZGVerifierPreprocessing, Serializable};
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (program, preprocessing) = guest::preprocess_fib();
+
+    // `jolt build-wasm` writes this key for the wasm `verify_fib` to load.
+    let verifier_key = JoltHyperKZGVerifierPreprocessing::from(&preprocessing)
+        .serialize_to_bytes()
+        .expect("failed to serialize verifier key");
+
+    let (output, proof) = guest::prove_fib(program, preprocessing, 50)?;
+    let proof_bytes = proof.serialize_to_bytes().unwrap();
+
+    // Verify the same way `verify_fib` does in wasm.
+    let verifier_preprocessing =
+        JoltHyperKZGVerifierPreprocessing::deserialize_from_bytes(&verifier_key)
+            .expect("failed to load verifier key");
+    let proof = JoltHyperKZGProof::deserialize_from_bytes(&proof_bytes).unwrap();
+    let is_valid = jolt_sdk::verify_with_panic_status(verifier_preprocessing, proof, false);
+
+    println!("verifier key size: {} bytes", verifier_key.len());
+    println!("output: {}", output);
+    println!("valid: {}", is_valid);
+
+    Ok(())
+}
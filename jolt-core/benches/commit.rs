@@ -1,8 +1,10 @@
-use ark_bn254::{Bn254, Fr};
+use ark_bn254::{Bn254, Fr, G1Projective};
 use criterion::Criterion;
 use jolt_core::field::JoltField;
+use jolt_core::msm::{FixedBaseMSMTable, SmallScalar};
 use jolt_core::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
 use jolt_core::poly::commitment::hyperkzg::HyperKZG;
+use jolt_core::poly::commitment::hyrax::HyraxScheme;
 use jolt_core::poly::commitment::kzg::CommitMode;
 use jolt_core::poly::commitment::zeromorph::Zeromorph;
 use jolt_core::utils::transcript::{KeccakTranscript, Transcript};
@@ -94,6 +96,86 @@ fn benchmark_commit<PCS, F, ProofTranscript>(
     );
 }
 
+// Compares `batch_commit` with the fixed-base MSM tables of `PCS::with_msm_tables` against
+// plain Pippenger, on polynomials of Jolt's trace lengths with coefficients drawn by `sample`
+fn benchmark_msm_table<PCS, ProofTranscript>(
+    c: &mut Criterion,
+    name: &str,
+    log_sizes: &[usize],
+    num_polys: usize,
+    sample: impl Fn(&mut ChaCha20Rng, usize) -> Fr,
+) where
+    PCS: CommitmentScheme<ProofTranscript, Field = Fr>,
+    ProofTranscript: Transcript,
+{
+    let mut rng = ChaCha20Rng::seed_from_u64(111111u64);
+    for &log_size in log_sizes {
+        let polys: Vec<Vec<Fr>> = (0..num_polys)
+            .map(|_| {
+                (0..1 << log_size)
+                    .map(|_| sample(&mut rng, log_size))
+                    .collect()
+            })
+            .collect();
+        let polys: Vec<&[Fr]> = polys.iter().map(|poly| poly.as_slice()).collect();
+        let plain_setup = PCS::setup(&[CommitShape::new(1 << log_size, BatchType::Big)]);
+        let setup = PCS::with_msm_tables(
+            plain_setup.clone(),
+            FixedBaseMSMTable::<G1Projective>::DEFAULT_NUM_WINDOWS,
+        );
+
+        let mut group = c.benchmark_group(format!("{name} Commit: 2^{log_size}"));
+        group.sample_size(10);
+        group.bench_function("Pippenger", |b| {
+            b.iter(|| PCS::batch_commit(&polys, &plain_setup, BatchType::Big));
+        });
+        group.bench_function("Fixed-base table", |b| {
+            b.iter(|| PCS::batch_commit(&polys, &setup, BatchType::Big));
+        });
+        group.finish();
+    }
+}
+
+// Compares `batch_commit` on field elements against `batch_commit_small` on the same
+// evaluations given as small integers or bits
+fn benchmark_commit_small<PCS, S, ProofTranscript>(
+    c: &mut Criterion,
+    name: &str,
+    log_size: usize,
+    num_polys: usize,
+    sample: impl Fn(&mut ChaCha20Rng) -> S,
+) where
+    PCS: CommitmentScheme<ProofTranscript, Field = Fr>,
+    S: SmallScalar,
+    ProofTranscript: Transcript,
+{
+    let mut rng = ChaCha20Rng::seed_from_u64(111111u64);
+    let small_polys: Vec<Vec<S>> = (0..num_polys)
+        .map(|_| (0..1 << log_size).map(|_| sample(&mut rng)).collect())
+        .collect();
+    let field_polys: Vec<Vec<Fr>> = small_polys
+        .iter()
+        .map(|poly| {
+            poly.iter()
+                .map(|eval| Fr::from_u64(eval.to_u64()).unwrap())
+                .collect()
+        })
+        .collect();
+    let small_polys: Vec<&[S]> = small_polys.iter().map(|poly| poly.as_slice()).collect();
+    let field_polys: Vec<&[Fr]> = field_polys.iter().map(|poly| poly.as_slice()).collect();
+    let setup = PCS::setup(&[CommitShape::new(1 << log_size, BatchType::Big)]);
+
+    let mut group = c.benchmark_group(format!("{name} Commit: 2^{log_size}"));
+    group.sample_size(10);
+    group.bench_function("Field elements", |b| {
+        b.iter(|| PCS::batch_commit(&field_polys, &setup, BatchType::Big));
+    });
+    group.bench_function(std::any::type_name::<S>(), |b| {
+        b.iter(|| PCS::batch_commit_small(&small_polys, &setup, BatchType::Big));
+    });
+    group.finish();
+}
+
 fn main() {
     let mut criterion = Criterion::default()
         .configure_from_args()
@@ -126,5 +208,70 @@ fn main() {
         BatchType::Big,
     );
 
+    // Most of Jolt's witness polynomials are sparse or have small coefficients: e.g. memory
+    // values fit in 32 bits, timestamps are below the trace length and circuit flags are
+    // mostly zero. Only a few, like the grand product layers, have random coefficients.
+    let log_sizes = [16, 18, 20];
+    let witnesses: [(&str, fn(&mut ChaCha20Rng, usize) -> Fr); 4] = [
+        ("random", |rng, _| Fr::random(rng)),
+        ("u32 values", |rng, _| {
+            Fr::from_u64(rng.next_u32() as u64).unwrap()
+        }),
+        ("timestamps", |rng, log_size| {
+            Fr::from_u64(rng.next_u64() % (1 << log_size)).unwrap()
+        }),
+        ("flags", |rng, _| {
+            Fr::from_u64((rng.next_u32() % 16 == 0) as u64).unwrap()
+        }),
+    ];
+    for (witness, sample) in witnesses {
+        benchmark_msm_table::<HyperKZG<Bn254, KeccakTranscript>, KeccakTranscript>(
+            &mut criterion,
+            &format!("HyperKZG {witness}"),
+            &log_sizes,
+            4,
+            sample,
+        );
+        benchmark_msm_table::<Zeromorph<Bn254, KeccakTranscript>, KeccakTranscript>(
+            &mut criterion,
+            &format!("Zeromorph {witness}"),
+            &log_sizes,
+            4,
+            sample,
+        );
+        benchmark_msm_table::<HyraxScheme<G1Projective, KeccakTranscript>, KeccakTranscript>(
+            &mut criterion,
+            &format!("Hyrax {witness}"),
+            &log_sizes,
+            4,
+            sample,
+        );
+    }
+
+    // Circuit flags are mostly zero, chunk indices are below 2^16 and timestamps below the
+    // trace length
+    let log_size = 20;
+    benchmark_commit_small::<HyperKZG<Bn254, KeccakTranscript>, _, KeccakTranscript>(
+        &mut criterion,
+        "HyperKZG flags",
+        log_size,
+        16,
+        |rng| rng.next_u32() % 16 == 0,
+    );
+    benchmark_commit_small::<HyperKZG<Bn254, KeccakTranscript>, _, KeccakTranscript>(
+        &mut criterion,
+        "HyperKZG chunks",
+        log_size,
+        4,
+        |rng| rng.next_u32() as u16,
+    );
+    benchmark_commit_small::<HyperKZG<Bn254, KeccakTranscript>, _, KeccakTranscript>(
+        &mut criterion,
+        "HyperKZG timestamps",
+        log_size,
+        4,
+        |rng| rng.next_u32() % (1 << log_size),
+    );
+
     criterion.final_summary();
 }
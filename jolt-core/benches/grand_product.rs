@@ -102,7 +102,7 @@ fn benchmark_prove<PCS, F, G, ProofTranscript>(
                 // Prove the grand product
                 let mut transcript = ProofTranscript::new(b"test_transcript");
                 let mut prover_accumulator: ProverOpeningAccumulator<F, ProofTranscript> =
-                    ProverOpeningAccumulator::new();
+                    ProverOpeningAccumulator::new::<PCS>();
                 let _proof: BatchedGrandProductProof<PCS, ProofTranscript> = grand_product
                     .prove_grand_product(
                         Some(&mut prover_accumulator),
@@ -134,12 +134,13 @@ fn benchmark_verify<PCS, F, G, ProofTranscript>(
     let mut transcript = ProofTranscript::new(b"test_transcript");
     let mut grand_product = G::construct_with_config(leaves, grand_products_config);
     let mut prover_accumulator: ProverOpeningAccumulator<F, ProofTranscript> =
-        ProverOpeningAccumulator::new();
+        ProverOpeningAccumulator::new::<PCS>();
     let (proof, r_prover) = grand_product.prove_grand_product(
         Some(&mut prover_accumulator),
         &mut transcript,
         Some(&setup),
     );
+    let verifier_setup = PCS::verifier_setup(&setup);
 
     c.bench_function(
         &format!(
@@ -157,8 +158,9 @@ fn benchmark_verify<PCS, F, G, ProofTranscript>(
                     &known_products,
                     Some(&mut verifier_accumulator),
                     &mut transcript,
-                    Some(&setup),
-                );
+                    Some(&verifier_setup),
+                )
+                .unwrap();
 
                 assert_eq!(r_prover, r_verifier);
             });
@@ -1,10 +1,12 @@
 use crate::field::JoltField;
 use crate::host;
+use crate::jolt::vm::prover_config::ProverConfig;
 use crate::jolt::vm::rv32i_vm::{RV32IJoltVM, C, M};
 use crate::jolt::vm::Jolt;
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 use crate::poly::commitment::hyperkzg::HyperKZG;
 use crate::poly::commitment::hyrax::HyraxScheme;
+use crate::poly::commitment::ligero::Ligero;
 use crate::poly::commitment::zeromorph::Zeromorph;
 use crate::utils::transcript::{KeccakTranscript, Transcript};
 use ark_bn254::{Bn254, Fr, G1Projective};
@@ -15,6 +17,7 @@ pub enum PCSType {
     Hyrax,
     Zeromorph,
     HyperKZG,
+    Ligero,
 }
 
 #[derive(Debug, Copy, Clone, clap::ValueEnum)]
@@ -71,6 +74,17 @@ pub fn benchmarks(
             }
             _ => panic!("BenchType does not have a mapping"),
         },
+        PCSType::Ligero => match bench_type {
+            BenchType::Sha2 => sha2::<Fr, Ligero<Fr, KeccakTranscript>, KeccakTranscript>(),
+            BenchType::Sha3 => sha3::<Fr, Ligero<Fr, KeccakTranscript>, KeccakTranscript>(),
+            BenchType::Sha2Chain => {
+                sha2chain::<Fr, Ligero<Fr, KeccakTranscript>, KeccakTranscript>()
+            }
+            BenchType::Fibonacci => {
+                fibonacci::<Fr, Ligero<Fr, KeccakTranscript>, KeccakTranscript>()
+            }
+            _ => panic!("BenchType does not have a mapping"),
+        },
         _ => panic!("PCS Type does not have a mapping"),
     }
 }
@@ -131,7 +145,7 @@ where
         let (io_device, trace) = program.trace();
 
         let preprocessing: crate::jolt::vm::JoltPreprocessing<C, F, PCS, ProofTranscript> =
-            RV32IJoltVM::preprocess(
+            RV32IJoltVM::insecure_preprocess(
                 bytecode.clone(),
                 io_device.memory_layout.clone(),
                 memory_init,
@@ -145,7 +159,9 @@ where
                 io_device,
                 trace,
                 preprocessing.clone(),
-            );
+                &ProverConfig::default(),
+            )
+            .unwrap();
 
         println!("Proof sizing:");
         serialize_and_print_size("jolt_commitments", &jolt_commitments);
@@ -161,8 +177,12 @@ where
             &jolt_proof.instruction_lookups,
         );
 
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, None);
+        let verification_result = RV32IJoltVM::verify(
+            crate::jolt::vm::JoltVerifierPreprocessing::from(&preprocessing),
+            jolt_proof,
+            jolt_commitments,
+            None,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -194,7 +214,7 @@ where
         let (io_device, trace) = program.trace();
 
         let preprocessing: crate::jolt::vm::JoltPreprocessing<C, F, PCS, ProofTranscript> =
-            RV32IJoltVM::preprocess(
+            RV32IJoltVM::insecure_preprocess(
                 bytecode.clone(),
                 io_device.memory_layout.clone(),
                 memory_init,
@@ -208,9 +228,15 @@ where
                 io_device,
                 trace,
                 preprocessing.clone(),
-            );
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, None);
+                &ProverConfig::default(),
+            )
+            .unwrap();
+        let verification_result = RV32IJoltVM::verify(
+            crate::jolt::vm::JoltVerifierPreprocessing::from(&preprocessing),
+            jolt_proof,
+            jolt_commitments,
+            None,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -12,11 +12,21 @@ impl BiniusConstructable for BinaryField128b {
     fn new(n: u64) -> Self {
         Self::new(n as u128)
     }
+
+    fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self.val()).ok()
+    }
 }
 
+// POLYVAL elements are stored in a basis in which the bits 0b1 are not the multiplicative
+// identity, so integers are embedded through the tower basis instead.
 impl BiniusConstructable for BinaryField128bPolyval {
     fn new(n: u64) -> Self {
-        Self::new(n as u128)
+        BinaryField128b::new(n as u128).into()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        BinaryField128b::from(*self).to_u64()
     }
 }
 
@@ -26,8 +36,11 @@ impl BiniusSpecific for BinaryField128bPolyval {}
 /// Trait for BiniusField functionality specific to each impl.
 pub trait BiniusSpecific: binius_field::TowerField + BiniusConstructable + bytemuck::Pod {}
 
+/// Embeds integers into the field as the tower-basis element with the same bits, which makes
+/// `from_u64` additive over XOR.
 pub trait BiniusConstructable {
     fn new(n: u64) -> Self;
+    fn to_u64(&self) -> Option<u64>;
 }
 
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
@@ -106,8 +119,8 @@ impl<'b, F: BiniusSpecific> Div<&'b BiniusField<F>> for BiniusField<F> {
 }
 
 impl<F: BiniusSpecific> Hash for BiniusField<F> {
-    fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {
-        todo!()
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        bytemuck::bytes_of(&self.0).hash(state);
     }
 }
 
@@ -124,10 +137,21 @@ impl<F: BiniusSpecific> JoltField for BiniusField<F> {
     }
 
     fn from_i64(val: i64) -> Self {
-        if val > 0 {
-            <Self as JoltField>::from_u64(val as u64).unwrap()
+        // Every element is its own negation in characteristic 2
+        <Self as JoltField>::from_u64(val.unsigned_abs()).unwrap()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+
+    #[inline(always)]
+    fn step_linear_eval(eval: Self, m: Self, k: u64) -> Self {
+        // from_u64(k + 1) - from_u64(k) = from_u64(k ^ (k + 1)), which is 1 for even k
+        if k % 2 == 0 {
+            eval + m
         } else {
-            <Self as JoltField>::from_u64(-val as u64).unwrap()
+            eval + <Self as JoltField>::from_u64(k ^ (k + 1)).unwrap() * m
         }
     }
 
@@ -142,8 +166,8 @@ impl<F: BiniusSpecific> JoltField for BiniusField<F> {
     fn from_bytes(bytes: &[u8]) -> Self {
         assert_eq!(bytes.len(), Self::NUM_BYTES);
 
-        let field_element = bytemuck::try_from_bytes::<F>(bytes).unwrap();
-        Self(field_element.to_owned())
+        // `bytes` need not be aligned to `F`
+        Self(bytemuck::pod_read_unaligned::<F>(bytes))
     }
 }
 
@@ -269,22 +293,29 @@ impl<F: BiniusSpecific> CanonicalSerialize for BiniusField<F> {
 impl<F: BiniusSpecific> CanonicalDeserialize for BiniusField<F> {
     // Required method
     fn deserialize_with_mode<R: std::io::prelude::Read>(
-        _reader: R,
+        mut reader: R,
         _compress: ark_serialize::Compress,
         _validate: ark_serialize::Validate,
     ) -> Result<Self, ark_serialize::SerializationError> {
-        todo!()
+        let mut bytes = vec![0u8; <Self as JoltField>::NUM_BYTES];
+        reader.read_exact(&mut bytes)?;
+        Ok(<Self as JoltField>::from_bytes(&bytes))
     }
 }
 
 impl<F: BiniusSpecific> ark_serialize::Valid for BiniusField<F> {
     fn check(&self) -> Result<(), ark_serialize::SerializationError> {
-        todo!()
+        // Every bit pattern is a field element
+        Ok(())
     }
 }
 
 impl<F: BiniusSpecific> std::fmt::Display for BiniusField<F> {
-    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x")?;
+        for byte in bytemuck::bytes_of(&self.0).iter().rev() {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
     }
 }
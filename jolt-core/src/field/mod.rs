@@ -50,6 +50,16 @@ pub trait JoltField:
     fn to_u64(&self) -> Option<u64> {
         unimplemented!("conversion to u64 not implemented");
     }
+    /// Given the evaluation `eval` of a linear polynomial with slope `m` at the point
+    /// `from_u64(k)`, returns its evaluation at `from_u64(k + 1)`. Sumcheck provers use this
+    /// to extrapolate round polynomials past 0 and 1.
+    ///
+    /// This is `eval + m` whenever `from_u64(k + 1) = from_u64(k) + 1`, which fails in binary
+    /// fields: there `from_u64` embeds integers as bit vectors, so `1 + 1 = 0`.
+    #[inline(always)]
+    fn step_linear_eval(eval: Self, m: Self, _k: u64) -> Self {
+        eval + m
+    }
 }
 
 pub trait OptimizedMul<Rhs, Output>: Sized + Mul<Rhs, Output = Output> {
@@ -48,8 +48,11 @@ pub struct Program {
     input: Vec<u8>,
     memory_size: u64,
     stack_size: u64,
+    heap_size: Option<u64>,
     max_input_size: u64,
     max_output_size: u64,
+    private_inputs: bool,
+    dispatch: bool,
     std: bool,
     pub elf: Option<PathBuf>,
 }
@@ -62,8 +65,11 @@ impl Program {
             input: Vec::new(),
             memory_size: DEFAULT_MEMORY_SIZE,
             stack_size: DEFAULT_STACK_SIZE,
+            heap_size: None,
             max_input_size: DEFAULT_MAX_INPUT_SIZE,
             max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
+            private_inputs: false,
+            dispatch: false,
             std: false,
             elf: None,
         }
@@ -90,6 +96,11 @@ impl Program {
         self.stack_size = len;
     }
 
+    /// Bounds the guest heap, which otherwise extends to the end of guest memory.
+    pub fn set_heap_size(&mut self, len: u64) {
+        self.heap_size = Some(len);
+    }
+
     pub fn set_max_input_size(&mut self, size: u64) {
         self.max_input_size = size;
     }
@@ -98,6 +109,22 @@ impl Program {
         self.max_output_size = size;
     }
 
+    /// Marks the program inputs as private, i.e. excluded from the public statement.
+    /// The guest is responsible for committing to them in its outputs.
+    pub fn set_private_inputs(&mut self, private_inputs: bool) {
+        self.private_inputs = private_inputs;
+    }
+
+    /// Builds every provable function of the guest into a single ELF, which selects
+    /// the function to run based on a selector preceding the inputs.
+    pub fn set_dispatch(&mut self, dispatch: bool) {
+        self.dispatch = dispatch;
+    }
+
+    pub fn is_dispatch(&self) -> bool {
+        self.dispatch
+    }
+
     #[tracing::instrument(skip_all, name = "Program::build")]
     pub fn build(&mut self) {
         if self.elf.is_none() {
@@ -133,15 +160,21 @@ impl Program {
                 envs.push(("RUSTUP_TOOLCHAIN", toolchain.to_string()));
             }
 
-            if let Some(func) = &self.func {
+            if self.dispatch {
+                envs.push(("JOLT_DISPATCH", "1".to_string()));
+            } else if let Some(func) = &self.func {
                 envs.push(("JOLT_FUNC_NAME", func.to_string()));
             }
 
-            let target = format!(
-                "/tmp/jolt-guest-target-{}-{}",
-                self.guest,
-                self.func.as_ref().unwrap_or(&"".to_string())
-            );
+            let target = if self.dispatch {
+                format!("/tmp/jolt-guest-target-{}-dispatch", self.guest)
+            } else {
+                format!(
+                    "/tmp/jolt-guest-target-{}-{}",
+                    self.guest,
+                    self.func.as_ref().unwrap_or(&"".to_string())
+                )
+            };
 
             let output = Command::new("cargo")
                 .envs(envs)
@@ -170,14 +203,19 @@ impl Program {
         }
     }
 
-    pub fn decode(&mut self) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
+    /// Builds the guest, if needed, and returns the contents of its ELF.
+    pub fn elf_contents(&mut self) -> Vec<u8> {
         self.build();
         let elf = self.elf.as_ref().unwrap();
         let mut elf_file =
             File::open(elf).unwrap_or_else(|_| panic!("could not open elf file: {:?}", elf));
         let mut elf_contents = Vec::new();
         elf_file.read_to_end(&mut elf_contents).unwrap();
-        tracer::decode(&elf_contents)
+        elf_contents
+    }
+
+    pub fn decode(&mut self) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
+        tracer::decode(&self.elf_contents())
     }
 
     // TODO(moodlezoup): Make this generic over InstructionSet
@@ -185,8 +223,9 @@ impl Program {
     pub fn trace(&mut self) -> (JoltDevice, Vec<JoltTraceStep<RV32I>>) {
         self.build();
         let elf = self.elf.clone().unwrap();
-        let (raw_trace, io_device) =
+        let (raw_trace, mut io_device) =
             tracer::trace(&elf, &self.input, self.max_input_size, self.max_output_size);
+        io_device.memory_layout.private_inputs = self.private_inputs;
 
         let trace: Vec<_> = raw_trace
             .into_par_iter()
@@ -249,9 +288,14 @@ impl Program {
             fs::create_dir_all(parent).expect("could not create linker file");
         }
 
+        let heap_end = match self.heap_size {
+            Some(heap_size) => format!("_HEAP_PTR + {heap_size}"),
+            None => "ORIGIN(program) + LENGTH(program)".to_string(),
+        };
         let linker_script = LINKER_SCRIPT_TEMPLATE
             .replace("{MEMORY_SIZE}", &self.memory_size.to_string())
-            .replace("{STACK_SIZE}", &self.stack_size.to_string());
+            .replace("{STACK_SIZE}", &self.stack_size.to_string())
+            .replace("{HEAP_END}", &heap_end);
 
         let mut file = File::create(linker_path).expect("could not create linker file");
         file.write_all(linker_script.as_bytes())
@@ -281,6 +325,12 @@ SECTIONS {
     *(.data)
   } > program
 
+  .jolt_dispatch : {
+    __jolt_dispatch_start = .;
+    KEEP(*(.jolt_dispatch))
+    __jolt_dispatch_end = .;
+  } > program
+
   .bss : {
     *(.bss)
   } > program
@@ -290,5 +340,7 @@ SECTIONS {
   _STACK_PTR = .;
   . = ALIGN(8);
   _HEAP_PTR = .;
+  _HEAP_END = {HEAP_END};
+  ASSERT(_HEAP_END <= ORIGIN(program) + LENGTH(program), "heap_size exceeds memory_size")
 }
 "#;
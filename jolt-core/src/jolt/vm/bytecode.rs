@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 #[cfg(test)]
 use std::collections::HashSet;
+use std::marker::PhantomData;
 use tracer::RV32IM;
 
 use crate::field::JoltField;
@@ -15,12 +16,15 @@ use crate::lasso::memory_checking::{
 };
 use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
 use crate::poly::eq_poly::EqPolynomial;
+use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
+use crate::utils::errors::MemoryCheckingError;
+use crate::utils::math::Math;
 use common::constants::{BYTES_PER_INSTRUCTION, RAM_START_ADDRESS};
 use common::rv_trace::ELFInstruction;
 
 use rayon::prelude::*;
 
-use super::{JoltPolynomials, JoltTraceStep};
+use super::{CompactEvals, JoltPolynomials, JoltTraceStep};
 use crate::utils::transcript::Transcript;
 use crate::{
     lasso::memory_checking::{MemoryCheckingProof, MemoryCheckingProver, MemoryCheckingVerifier},
@@ -43,7 +47,8 @@ pub struct BytecodeStuff<T: CanonicalSerialize + CanonicalDeserialize> {
     /// Final timestamps for offline memory-checking
     pub(crate) t_final: T,
     a_init_final: VerifierComputedOpening<T>,
-    v_init_final: VerifierComputedOpening<[T; 6]>,
+    /// Opened by the prover against the commitments in the verifier preprocessing.
+    pub(crate) v_init_final: VerifierComputedOpening<[T; 6]>,
 }
 
 /// Note –– F: JoltField bound is not enforced.
@@ -217,7 +222,7 @@ pub fn random_bytecode_trace(
     trace
 }
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BytecodePreprocessing<F: JoltField> {
     /// Size of the (padded) bytecode.
     code_size: usize,
@@ -264,6 +269,27 @@ impl<F: JoltField> BytecodePreprocessing<F> {
         let code_size = bytecode.len().next_power_of_two();
         bytecode.resize(code_size, BytecodeRow::no_op(0));
 
+        let v_init_final = Self::v_init_final(bytecode.iter().map(|instruction| {
+            [
+                instruction.address as u64,
+                instruction.bitflags,
+                instruction.rd,
+                instruction.rs1,
+                instruction.rs2,
+                instruction.imm as u64,
+            ]
+        }));
+
+        Self {
+            v_init_final,
+            code_size,
+            virtual_address_map,
+        }
+    }
+
+    /// Builds the six `v_init_final` polynomials from (address, bitflags, rd, rs1, rs2, imm)
+    /// rows, where `imm` holds the two's complement bits of the signed immediate.
+    fn v_init_final(rows: impl Iterator<Item = [u64; 6]>) -> [DensePolynomial<F>; 6] {
         let mut address = vec![];
         let mut bitflags = vec![];
         let mut rd = vec![];
@@ -271,28 +297,75 @@ impl<F: JoltField> BytecodePreprocessing<F> {
         let mut rs2 = vec![];
         let mut imm = vec![];
 
-        for instruction in bytecode {
-            address.push(F::from_u64(instruction.address as u64).unwrap());
-            bitflags.push(F::from_u64(instruction.bitflags).unwrap());
-            rd.push(F::from_u64(instruction.rd).unwrap());
-            rs1.push(F::from_u64(instruction.rs1).unwrap());
-            rs2.push(F::from_u64(instruction.rs2).unwrap());
-            imm.push(F::from_i64(instruction.imm));
+        for row in rows {
+            address.push(F::from_u64(row[0]).unwrap());
+            bitflags.push(F::from_u64(row[1]).unwrap());
+            rd.push(F::from_u64(row[2]).unwrap());
+            rs1.push(F::from_u64(row[3]).unwrap());
+            rs2.push(F::from_u64(row[4]).unwrap());
+            imm.push(F::from_i64(row[5] as i64));
         }
 
-        let v_init_final = [
+        [
             DensePolynomial::new(address),
             DensePolynomial::new(bitflags),
             DensePolynomial::new(rd),
             DensePolynomial::new(rs1),
             DensePolynomial::new(rs2),
             DensePolynomial::new(imm),
-        ];
+        ]
+    }
 
-        Self {
-            v_init_final,
-            code_size,
-            virtual_address_map,
+    /// Strips the preprocessing down to what the verifier needs: the bytecode size and
+    /// commitments to the `v_init_final` polynomials, whose openings are proven alongside
+    /// the rest of the bytecode memory-checking openings.
+    pub fn to_verifier_preprocessing<PCS, ProofTranscript>(
+        &self,
+        setup: &PCS::Setup,
+    ) -> BytecodeVerifierPreprocessing<PCS, ProofTranscript>
+    where
+        PCS: CommitmentScheme<ProofTranscript, Field = F>,
+        ProofTranscript: Transcript,
+    {
+        BytecodeVerifierPreprocessing {
+            code_size: self.code_size,
+            v_init_final: self
+                .v_init_final
+                .each_ref()
+                .map(|poly| PCS::commit(poly, setup)),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Succinct form of [`BytecodePreprocessing`] shipped to verifiers. Its size is independent
+/// of the length of the program.
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BytecodeVerifierPreprocessing<PCS, ProofTranscript>
+where
+    PCS: CommitmentScheme<ProofTranscript>,
+    ProofTranscript: Transcript,
+{
+    /// Size of the (padded) bytecode.
+    code_size: usize,
+    /// Commitments to the `v_init_final` polynomials of the bytecode.
+    pub(crate) v_init_final: [PCS::Commitment; 6],
+    _marker: PhantomData<ProofTranscript>,
+}
+
+impl<PCS, ProofTranscript> BytecodeVerifierPreprocessing<PCS, ProofTranscript>
+where
+    PCS: CommitmentScheme<ProofTranscript>,
+    ProofTranscript: Transcript,
+{
+    /// The [`BytecodePreprocessing`] used by the verifier. It only records the bytecode size:
+    /// the `v_init_final` polynomials are opened by the prover instead, and the virtual
+    /// address map is only used for witness generation.
+    pub fn to_preprocessing(&self) -> BytecodePreprocessing<PCS::Field> {
+        BytecodePreprocessing {
+            code_size: self.code_size,
+            v_init_final: Default::default(),
+            virtual_address_map: BTreeMap::new(),
         }
     }
 }
@@ -307,12 +380,12 @@ where
     pub fn generate_witness<InstructionSet: JoltInstructionSet>(
         preprocessing: &BytecodePreprocessing<F>,
         trace: &mut Vec<JoltTraceStep<InstructionSet>>,
-    ) -> BytecodePolynomials<F> {
+    ) -> (BytecodePolynomials<F>, BytecodeStuff<CompactEvals>) {
         let num_ops = trace.len();
 
         let mut a_read_write_usize: Vec<usize> = vec![0; num_ops];
-        let mut read_cts: Vec<usize> = vec![0; num_ops];
-        let mut final_cts: Vec<usize> = vec![0; preprocessing.code_size];
+        let mut read_cts: Vec<u32> = vec![0; num_ops];
+        let mut final_cts: Vec<u32> = vec![0; preprocessing.code_size];
 
         for (step_index, step) in trace.iter_mut().enumerate() {
             if !step.bytecode_row.address.is_zero() {
@@ -363,8 +436,8 @@ where
             DensePolynomial::new(rs2),
             DensePolynomial::new(imm),
         ];
-        let t_read: DensePolynomial<F> = DensePolynomial::from_usize(&read_cts);
-        let t_final: DensePolynomial<F> = DensePolynomial::from_usize(&final_cts);
+        let t_read: DensePolynomial<F> = DensePolynomial::from_small_scalars(&read_cts);
+        let t_final: DensePolynomial<F> = DensePolynomial::from_small_scalars(&final_cts);
 
         #[cfg(test)]
         let mut init_tuples: HashSet<(u64, [F; 6], u64)> = HashSet::new();
@@ -440,14 +513,21 @@ where
             assert_eq!(set_difference.len(), 0);
         }
 
-        BytecodeStuff {
+        let polynomials = BytecodeStuff {
             a_read_write,
             v_read_write,
             t_read,
             t_final,
             a_init_final: None,
             v_init_final: None,
-        }
+        };
+        let compact = BytecodeStuff {
+            t_read: CompactEvals::U32(read_cts),
+            t_final: CompactEvals::U32(final_cts),
+            ..Default::default()
+        };
+
+        (polynomials, compact)
     }
 
     #[tracing::instrument(skip_all, name = "BytecodePolynomials::validate_bytecode")]
@@ -606,6 +686,31 @@ where
         )
     }
 
+    fn prove_preprocessed_openings(
+        preprocessing: &BytecodePreprocessing<F>,
+        openings: &mut Self::Openings,
+        opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
+        r_init_final: &[F],
+        transcript: &mut ProofTranscript,
+    ) {
+        let chis = EqPolynomial::evals(r_init_final);
+        let v_init_final: [F; 6] = preprocessing
+            .v_init_final
+            .par_iter()
+            .map(|poly| poly.evaluate_at_chi(&chis))
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        opening_accumulator.append(
+            &preprocessing.v_init_final.each_ref(),
+            DensePolynomial::new(chis),
+            r_init_final.to_vec(),
+            &v_init_final.each_ref(),
+            transcript,
+        );
+        openings.v_init_final = Some(v_init_final);
+    }
+
     fn protocol_name() -> &'static [u8] {
         b"Bytecode memory checking"
     }
@@ -618,25 +723,42 @@ where
     PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 {
+    fn verify_preprocessed_openings(
+        preprocessing: &BytecodePreprocessing<F>,
+        commitments: &Self::Commitments,
+        openings: &Self::Openings,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
+        r_init_final: &[F],
+        transcript: &mut ProofTranscript,
+    ) -> Result<(), MemoryCheckingError> {
+        if r_init_final.len() != preprocessing.code_size.log_2() {
+            return Err(MemoryCheckingError::PreprocessedOpeningPoint {
+                expected: preprocessing.code_size.log_2(),
+                actual: r_init_final.len(),
+            });
+        }
+        let (Some(v_init_final_commitments), Some(v_init_final)) =
+            (&commitments.v_init_final, &openings.v_init_final)
+        else {
+            return Err(MemoryCheckingError::MissingPreprocessedOpening);
+        };
+        opening_accumulator.append(
+            &v_init_final_commitments.each_ref(),
+            r_init_final.to_vec(),
+            &v_init_final.each_ref(),
+            transcript,
+        );
+        Ok(())
+    }
+
     fn compute_verifier_openings(
         openings: &mut BytecodeOpenings<F>,
-        preprocessing: &Self::Preprocessing,
+        _preprocessing: &Self::Preprocessing,
         _r_read_write: &[F],
         r_init_final: &[F],
     ) {
         openings.a_init_final =
             Some(IdentityPolynomial::new(r_init_final.len()).evaluate(r_init_final));
-
-        let chis = EqPolynomial::evals(r_init_final);
-        openings.v_init_final = Some(
-            preprocessing
-                .v_init_final
-                .par_iter()
-                .map(|poly| poly.evaluate_at_chi(&chis))
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap(),
-        );
     }
 
     fn read_tuples(
@@ -709,7 +831,10 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::{jolt::vm::rv32i_vm::RV32I, poly::commitment::hyrax::HyraxScheme};
+    use crate::{
+        jolt::vm::rv32i_vm::RV32I,
+        poly::commitment::{hyrax::HyraxScheme, mock::MockCommitScheme},
+    };
 
     use super::*;
     use crate::utils::transcript::KeccakTranscript;
@@ -751,6 +876,31 @@ mod tests {
         BytecodeOpenings::<Fr>::test_ordering_consistency(&preprocessing);
     }
 
+    #[test]
+    fn verifier_preprocessing_commits_to_bytecode() {
+        let program = vec![
+            BytecodeRow::new(to_ram_address(0), 2u64, 2u64, 2u64, 2u64, -2i64),
+            BytecodeRow::new(to_ram_address(1), 4u64, 4u64, 4u64, 4u64, 4i64),
+            BytecodeRow::new(to_ram_address(2), 8u64, 8u64, 8u64, 8u64, i64::MIN + 1),
+            BytecodeRow::new(to_ram_address(3), 16u64, 16u64, 16u64, 16u64, 0i64),
+        ];
+        let preprocessing = BytecodePreprocessing::<Fr>::preprocess(program);
+        type PCS = MockCommitScheme<Fr, KeccakTranscript>;
+        let verifier_preprocessing =
+            preprocessing.to_verifier_preprocessing::<PCS, KeccakTranscript>(&());
+        for (commitment, poly) in verifier_preprocessing
+            .v_init_final
+            .iter()
+            .zip(preprocessing.v_init_final.iter())
+        {
+            assert_eq!(*commitment, PCS::commit(poly, &()));
+        }
+
+        let rebuilt = verifier_preprocessing.to_preprocessing();
+        assert_eq!(rebuilt.code_size, preprocessing.code_size);
+        assert!(rebuilt.virtual_address_map.is_empty());
+    }
+
     #[test]
     #[should_panic]
     fn bytecode_validation_fake_trace() {
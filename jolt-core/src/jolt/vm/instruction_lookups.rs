@@ -1,5 +1,5 @@
 use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
-use crate::subprotocols::grand_product::BatchedGrandProduct;
+use crate::subprotocols::grand_product::{BatchedGrandProduct, GrandProductBackend};
 use crate::subprotocols::sparse_grand_product::ToggledBatchedGrandProduct;
 use crate::utils::thread::{drop_in_background_thread, unsafe_allocate_zero_vec};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -13,8 +13,8 @@ use crate::field::JoltField;
 use crate::jolt::instruction::{JoltInstructionSet, SubtableIndices};
 use crate::jolt::subtable::JoltSubtableSet;
 use crate::lasso::memory_checking::{
-    Initializable, MultisetHashes, NoExogenousOpenings, StructuredPolynomialData,
-    VerifierComputedOpening,
+    check_multiset_hashes, check_num_hashes, Initializable, MultisetHashes, NoExogenousOpenings,
+    StructuredPolynomialData, VerifierComputedOpening,
 };
 use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
 use crate::utils::mul_0_1_optimized;
@@ -28,10 +28,14 @@ use crate::{
         unipoly::{CompressedUniPoly, UniPoly},
     },
     subprotocols::sumcheck::SumcheckInstanceProof,
-    utils::{errors::ProofVerifyError, math::Math, transcript::AppendToTranscript},
+    utils::{
+        errors::{MemoryCheckingError, Mismatch, ProofVerifyError, SumcheckError},
+        math::Math,
+        transcript::AppendToTranscript,
+    },
 };
 
-use super::{JoltCommitments, JoltPolynomials, JoltTraceStep};
+use super::{CompactEvals, JoltCommitments, JoltPolynomials, JoltTraceStep};
 
 #[derive(Debug, Default, CanonicalSerialize, CanonicalDeserialize)]
 pub struct InstructionLookupStuff<T: CanonicalSerialize + CanonicalDeserialize> {
@@ -146,16 +150,16 @@ impl<T: CanonicalSerialize + CanonicalDeserialize> StructuredPolynomialData<T>
 
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
 /// Polynomial openings associated with the "primary sumcheck" of Jolt instruction lookups.
-struct PrimarySumcheckOpenings<F>
+pub(crate) struct PrimarySumcheckOpenings<F>
 where
     F: JoltField,
 {
     /// Evaluations of the E_i polynomials at the opening point. Vector is of length NUM_MEMORIES.
-    E_poly_openings: Vec<F>,
+    pub(crate) E_poly_openings: Vec<F>,
     /// Evaluations of the flag polynomials at the opening point. Vector is of length NUM_INSTRUCTIONS.
-    flag_openings: Vec<F>,
+    pub(crate) flag_openings: Vec<F>,
     /// Evaluation of the lookup_outputs polynomial at the opening point.
-    lookup_outputs_opening: F,
+    pub(crate) lookup_outputs_opening: F,
 }
 
 impl<const C: usize, const M: usize, F, PCS, InstructionSet, Subtables, ProofTranscript>
@@ -330,35 +334,22 @@ where
     fn check_multiset_equality(
         preprocessing: &InstructionLookupsPreprocessing<C, F>,
         multiset_hashes: &MultisetHashes<F>,
-    ) {
-        assert_eq!(multiset_hashes.init_hashes.len(), Self::NUM_SUBTABLES);
-        assert_eq!(
-            multiset_hashes.read_hashes.len(),
-            preprocessing.num_memories
-        );
-        assert_eq!(
-            multiset_hashes.write_hashes.len(),
-            preprocessing.num_memories
-        );
-        assert_eq!(
-            multiset_hashes.final_hashes.len(),
-            preprocessing.num_memories
-        );
+    ) -> Result<(), MemoryCheckingError> {
+        check_num_hashes(&multiset_hashes.init_hashes, Self::NUM_SUBTABLES)?;
+        check_num_hashes(&multiset_hashes.read_hashes, preprocessing.num_memories)?;
+        check_num_hashes(&multiset_hashes.write_hashes, preprocessing.num_memories)?;
+        check_num_hashes(&multiset_hashes.final_hashes, preprocessing.num_memories)?;
 
         (0..preprocessing.num_memories)
             .into_par_iter()
-            .for_each(|i| {
+            .try_for_each(|i| {
                 let read_hash = multiset_hashes.read_hashes[i];
                 let write_hash = multiset_hashes.write_hashes[i];
                 let init_hash =
                     multiset_hashes.init_hashes[preprocessing.memory_to_subtable_index[i]];
                 let final_hash = multiset_hashes.final_hashes[i];
-                assert_eq!(
-                    init_hash * write_hash,
-                    final_hash * read_hash,
-                    "Multiset hashes don't match"
-                );
-            });
+                check_multiset_hashes(i, init_hash * write_hash, final_hash * read_hash)
+            })
     }
 
     fn protocol_name() -> &'static [u8] {
@@ -468,7 +459,7 @@ where
         exogenous_openings: &NoExogenousOpenings,
         gamma: &F,
         tau: &F,
-    ) {
+    ) -> Result<(), MemoryCheckingError> {
         let read_tuples: Vec<_> = Self::read_tuples(preprocessing, openings, exogenous_openings);
         let write_tuples: Vec<_> = Self::write_tuples(preprocessing, openings, exogenous_openings);
         let init_tuples: Vec<_> = Self::init_tuples(preprocessing, openings, exogenous_openings);
@@ -537,10 +528,11 @@ where
         // W(r', r'') = flags(r', r'') * fingerprints(r', r'') + 1 - flags(r', r'')
         //
         // and this should equal the claim output by the read-write grand product.
-        assert_eq!(
+        Mismatch::check(
+            read_write_claim,
             combined_flags * combined_read_write_fingerprint + F::one() - combined_flags,
-            read_write_claim
-        );
+        )
+        .map_err(MemoryCheckingError::ReadWriteFingerprints)?;
 
         // The init-final grand product isn't toggled using flags (it's just a "normal" grand product)
         // so we combine the openings the normal way.
@@ -549,7 +541,8 @@ where
             .zip(EqPolynomial::evals(r_init_final_batch_index).iter())
             .map(|(tuple, eq_eval)| Self::fingerprint(tuple, gamma, tau) * eq_eval)
             .sum();
-        assert_eq!(combined_init_final_fingerprint, init_final_claim);
+        Mismatch::check(init_final_claim, combined_init_final_fingerprint)
+            .map_err(MemoryCheckingError::InitFinalFingerprints)
     }
 }
 
@@ -572,8 +565,8 @@ pub struct InstructionLookupsProof<
 {
     _instructions: PhantomData<InstructionSet>,
     _subtables: PhantomData<Subtables>,
-    primary_sumcheck: PrimarySumcheck<F, ProofTranscript>,
-    memory_checking: MemoryCheckingProof<
+    pub(crate) primary_sumcheck: PrimarySumcheck<F, ProofTranscript>,
+    pub(crate) memory_checking: MemoryCheckingProof<
         F,
         PCS,
         InstructionLookupOpenings<F>,
@@ -584,14 +577,14 @@ pub struct InstructionLookupsProof<
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct PrimarySumcheck<F: JoltField, ProofTranscript: Transcript> {
-    sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+    pub(crate) sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
     num_rounds: usize,
-    openings: PrimarySumcheckOpenings<F>,
+    pub(crate) openings: PrimarySumcheckOpenings<F>,
     // opening_proof: PCS::BatchedProof,
     _marker: PhantomData<ProofTranscript>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct InstructionLookupsPreprocessing<const C: usize, F: JoltField> {
     subtable_to_memory_indices: Vec<Vec<usize>>, // Vec<Range<usize>>?
     instruction_to_memory_indices: Vec<Vec<usize>>,
@@ -608,8 +601,20 @@ impl<const C: usize, F: JoltField> InstructionLookupsPreprocessing<C, F> {
         InstructionSet: JoltInstructionSet,
         Subtables: JoltSubtableSet<F>,
     {
-        let materialized_subtables = Self::materialize_subtables::<M, Subtables>();
+        Self {
+            materialized_subtables: Self::materialize_subtables::<M, Subtables>(),
+            ..Self::verifier_preprocess::<M, InstructionSet, Subtables>()
+        }
+    }
 
+    /// Same as [`Self::preprocess`], but skips materializing the subtables, which only
+    /// the prover reads. This is cheap enough to recompute on every verification.
+    #[tracing::instrument(skip_all, name = "InstructionLookups::verifier_preprocess")]
+    pub fn verifier_preprocess<const M: usize, InstructionSet, Subtables>() -> Self
+    where
+        InstructionSet: JoltInstructionSet,
+        Subtables: JoltSubtableSet<F>,
+    {
         // Build a mapping from subtable type => chunk indices that access that subtable type
         let mut subtable_indices: Vec<SubtableIndices> =
             vec![SubtableIndices::with_capacity(C); Subtables::COUNT];
@@ -650,7 +655,7 @@ impl<const C: usize, F: JoltField> InstructionLookupsPreprocessing<C, F> {
 
         Self {
             num_memories,
-            materialized_subtables,
+            materialized_subtables: vec![],
             subtable_to_memory_indices,
             memory_to_subtable_index,
             memory_to_dimension_index,
@@ -691,6 +696,7 @@ where
         preprocessing: &InstructionLookupsPreprocessing<C, F>,
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
+        grand_product_backend: GrandProductBackend,
     ) -> InstructionLookupsProof<C, M, F, PCS, InstructionSet, Subtables, ProofTranscript> {
         let protocol_name = Self::protocol_name();
         transcript.append_message(protocol_name);
@@ -759,6 +765,7 @@ where
             polynomials,
             opening_accumulator,
             transcript,
+            grand_product_backend,
         );
 
         InstructionLookupsProof {
@@ -771,7 +778,7 @@ where
 
     pub fn verify(
         preprocessing: &InstructionLookupsPreprocessing<C, F>,
-        pcs_setup: &PCS::Setup,
+        pcs_setup: &PCS::VerifierSetup,
         proof: InstructionLookupsProof<C, M, F, PCS, InstructionSet, Subtables, ProofTranscript>,
         commitments: &JoltCommitments<PCS, ProofTranscript>,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
@@ -783,25 +790,31 @@ where
         let r_eq = transcript.challenge_vector(proof.primary_sumcheck.num_rounds);
 
         // TODO: compartmentalize all primary sumcheck logic
-        let (claim_last, r_primary_sumcheck) = proof.primary_sumcheck.sumcheck_proof.verify(
-            F::zero(),
-            proof.primary_sumcheck.num_rounds,
-            Self::sumcheck_poly_degree(),
-            transcript,
-        )?;
+        let (claim_last, r_primary_sumcheck) = proof
+            .primary_sumcheck
+            .sumcheck_proof
+            .verify(
+                F::zero(),
+                proof.primary_sumcheck.num_rounds,
+                Self::sumcheck_poly_degree(),
+                transcript,
+            )
+            .map_err(ProofVerifyError::InstructionLookupsSumcheck)?;
 
         // Verify that eq(r, r_z) * [f_1(r_z) * g(E_1(r_z)) + ... + f_F(r_z) * E_F(r_z))] = claim_last
         let eq_eval = EqPolynomial::new(r_eq.to_vec()).evaluate(&r_primary_sumcheck);
-        assert_eq!(
+        Mismatch::check(
+            claim_last,
             eq_eval
                 * (Self::combine_lookups(
                     preprocessing,
                     &proof.primary_sumcheck.openings.E_poly_openings,
                     &proof.primary_sumcheck.openings.flag_openings,
                 ) - proof.primary_sumcheck.openings.lookup_outputs_opening),
-            claim_last,
-            "Primary sumcheck check failed."
-        );
+        )
+        .map_err(|mismatch| {
+            ProofVerifyError::InstructionLookupsSumcheck(SumcheckError::FinalClaim(mismatch))
+        })?;
 
         let primary_sumcheck_commitments = commitments
             .instruction_lookups
@@ -835,9 +848,8 @@ where
             commitments,
             opening_accumulator,
             transcript,
-        )?;
-
-        Ok(())
+        )
+        .map_err(ProofVerifyError::InstructionLookupsMemoryChecking)
     }
 
     /// Constructs the polynomials used in the primary sumcheck and memory checking.
@@ -845,21 +857,24 @@ where
     pub fn generate_witness(
         preprocessing: &InstructionLookupsPreprocessing<C, F>,
         ops: &Vec<JoltTraceStep<InstructionSet>>,
-    ) -> InstructionLookupPolynomials<F> {
+    ) -> (
+        InstructionLookupPolynomials<F>,
+        InstructionLookupStuff<CompactEvals>,
+    ) {
         let m: usize = ops.len().next_power_of_two();
+        assert!(M <= 1 << 16, "subtable indices must fit in a u16");
 
         let subtable_lookup_indices: Vec<Vec<usize>> = Self::subtable_lookup_indices(ops);
 
-        let polys: Vec<(DensePolynomial<F>, DensePolynomial<F>, DensePolynomial<F>)> = (0
-            ..preprocessing.num_memories)
+        let polys: Vec<(Vec<u32>, Vec<u32>, DensePolynomial<F>)> = (0..preprocessing.num_memories)
             .into_par_iter()
             .map(|memory_index| {
                 let dim_index = preprocessing.memory_to_dimension_index[memory_index];
                 let subtable_index = preprocessing.memory_to_subtable_index[memory_index];
                 let access_sequence: &Vec<usize> = &subtable_lookup_indices[dim_index];
 
-                let mut final_cts_i = vec![0usize; M];
-                let mut read_cts_i = vec![0usize; m];
+                let mut final_cts_i = vec![0u32; M];
+                let mut read_cts_i = vec![0u32; m];
                 let mut subtable_lookups = vec![F::zero(); m];
 
                 for (j, op) in ops.iter().enumerate() {
@@ -880,17 +895,17 @@ where
                 }
 
                 (
-                    DensePolynomial::from_usize(&read_cts_i),
-                    DensePolynomial::from_usize(&final_cts_i),
+                    read_cts_i,
+                    final_cts_i,
                     DensePolynomial::new(subtable_lookups),
                 )
             })
             .collect();
 
-        // Vec<(DensePolynomial<F>, DensePolynomial<F>, DensePolynomial<F>)> -> (Vec<DensePolynomial<F>>, Vec<DensePolynomial<F>>, Vec<DensePolynomial<F>>)
-        let (read_cts, final_cts, E_polys): (
-            Vec<DensePolynomial<F>>,
-            Vec<DensePolynomial<F>>,
+        // Vec<(Vec<u32>, Vec<u32>, DensePolynomial<F>)> -> (Vec<Vec<u32>>, Vec<Vec<u32>>, Vec<DensePolynomial<F>>)
+        let (read_cts_u32, final_cts_u32, E_polys): (
+            Vec<Vec<u32>>,
+            Vec<Vec<u32>>,
             Vec<DensePolynomial<F>>,
         ) = polys.into_iter().fold(
             (Vec::new(), Vec::new(), Vec::new()),
@@ -902,32 +917,45 @@ where
             },
         );
 
-        let dim: Vec<DensePolynomial<F>> = (0..C)
-            .into_par_iter()
-            .map(|i| {
-                let access_sequence: &Vec<usize> = &subtable_lookup_indices[i];
-                DensePolynomial::from_usize(access_sequence)
-            })
+        let read_cts: Vec<DensePolynomial<F>> = read_cts_u32
+            .par_iter()
+            .map(|read_cts| DensePolynomial::from_small_scalars(read_cts))
+            .collect();
+        let final_cts: Vec<DensePolynomial<F>> = final_cts_u32
+            .par_iter()
+            .map(|final_cts| DensePolynomial::from_small_scalars(final_cts))
+            .collect();
+
+        let dim_u16: Vec<Vec<u16>> = subtable_lookup_indices[..C]
+            .par_iter()
+            .map(|access_sequence| access_sequence.iter().map(|&index| index as u16).collect())
+            .collect();
+        let dim: Vec<DensePolynomial<F>> = dim_u16
+            .par_iter()
+            .map(|access_sequence| DensePolynomial::from_small_scalars(access_sequence))
             .collect();
 
         let mut instruction_flag_bitvectors: Vec<Vec<u64>> =
             vec![vec![0u64; m]; Self::NUM_INSTRUCTIONS];
+        let mut instruction_flag_bits: Vec<Vec<bool>> =
+            vec![vec![false; m]; Self::NUM_INSTRUCTIONS];
         for (j, op) in ops.iter().enumerate() {
             if let Some(instr) = &op.instruction_lookup {
                 instruction_flag_bitvectors[InstructionSet::enum_index(instr)][j] = 1;
+                instruction_flag_bits[InstructionSet::enum_index(instr)][j] = true;
             }
         }
 
-        let instruction_flag_polys: Vec<DensePolynomial<F>> = instruction_flag_bitvectors
+        let instruction_flag_polys: Vec<DensePolynomial<F>> = instruction_flag_bits
             .par_iter()
-            .map(|flag_bitvector| DensePolynomial::from_u64(flag_bitvector))
+            .map(|flag_bits| DensePolynomial::from_small_scalars(flag_bits))
             .collect();
 
         let mut lookup_outputs = Self::compute_lookup_outputs(ops);
         lookup_outputs.resize(m, F::zero());
         let lookup_outputs = DensePolynomial::new(lookup_outputs);
 
-        InstructionLookupPolynomials {
+        let polynomials = InstructionLookupPolynomials {
             dim,
             read_cts,
             final_cts,
@@ -937,7 +965,25 @@ where
             a_init_final: None,
             v_init_final: None,
             instruction_flag_bitvectors: Some(instruction_flag_bitvectors),
-        }
+        };
+        let compact = InstructionLookupStuff {
+            dim: dim_u16.into_iter().map(CompactEvals::U16).collect(),
+            read_cts: read_cts_u32.into_iter().map(CompactEvals::U32).collect(),
+            final_cts: final_cts_u32.into_iter().map(CompactEvals::U32).collect(),
+            E_polys: std::iter::repeat_with(CompactEvals::default)
+                .take(preprocessing.num_memories)
+                .collect(),
+            instruction_flags: instruction_flag_bits
+                .into_iter()
+                .map(CompactEvals::Bits)
+                .collect(),
+            lookup_outputs: CompactEvals::Dense,
+            a_init_final: None,
+            v_init_final: None,
+            instruction_flag_bitvectors: None,
+        };
+
+        (polynomials, compact)
     }
 
     /// Prove Jolt primary sumcheck including instruction collation.
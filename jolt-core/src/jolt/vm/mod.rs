@@ -7,8 +7,12 @@ use crate::poly::opening_proof::{
 };
 use crate::r1cs::constraints::R1CSConstraints;
 use crate::r1cs::spartan::{self, UniformSpartanProof};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use common::rv_trace::{MemoryLayout, NUM_CIRCUIT_FLAGS};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 use strum::EnumCount;
@@ -26,10 +30,11 @@ use crate::jolt::{
 use crate::lasso::memory_checking::{
     Initializable, MemoryCheckingProver, MemoryCheckingVerifier, StructuredPolynomialData,
 };
-use crate::poly::commitment::commitment_scheme::{BatchType, CommitmentScheme};
+use crate::msm::SmallScalar;
+use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::r1cs::inputs::{ConstraintInput, R1CSPolynomials, R1CSProof, R1CSStuff};
-use crate::utils::errors::ProofVerifyError;
+use crate::utils::errors::{ProofVerifyError, ProverError, SRSError};
 use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::{AppendToTranscript, Transcript};
 use common::{
@@ -37,13 +42,16 @@ use common::{
     rv_trace::{ELFInstruction, JoltDevice, MemoryOp},
 };
 
-use self::bytecode::{BytecodePreprocessing, BytecodeProof, BytecodeRow, BytecodeStuff};
+use self::bytecode::{
+    BytecodePreprocessing, BytecodeProof, BytecodeRow, BytecodeStuff, BytecodeVerifierPreprocessing,
+};
 use self::instruction_lookups::{
     InstructionLookupStuff, InstructionLookupsPreprocessing, InstructionLookupsProof,
 };
+use self::prover_config::{ProgressReporter, ProverConfig, ProverStage};
 use self::read_write_memory::{
     ReadWriteMemoryPolynomials, ReadWriteMemoryPreprocessing, ReadWriteMemoryProof,
-    ReadWriteMemoryStuff,
+    ReadWriteMemoryStuff, ReadWriteMemoryVerifierPreprocessing,
 };
 
 use super::instruction::lb::LBInstruction;
@@ -54,7 +62,7 @@ use super::instruction::sb::SBInstruction;
 use super::instruction::sh::SHInstruction;
 use super::instruction::JoltInstructionSet;
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct JoltPreprocessing<const C: usize, F, PCS, ProofTranscript>
 where
     F: JoltField,
@@ -68,6 +76,46 @@ where
     pub memory_layout: MemoryLayout,
 }
 
+/// The subset of [`JoltPreprocessing`] needed to verify a proof: the PCS verifier key,
+/// commitments to the bytecode and initial memory contents, and the memory layout. Its size
+/// is independent of the program's length, so it can be shipped to light clients (e.g. the
+/// wasm verifier).
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct JoltVerifierPreprocessing<F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    pub generators: PCS::VerifierSetup,
+    pub bytecode: BytecodeVerifierPreprocessing<PCS, ProofTranscript>,
+    pub read_write_memory: ReadWriteMemoryVerifierPreprocessing<PCS, ProofTranscript>,
+    pub memory_layout: MemoryLayout,
+    _field: PhantomData<F>,
+}
+
+impl<const C: usize, F, PCS, ProofTranscript> From<&JoltPreprocessing<C, F, PCS, ProofTranscript>>
+    for JoltVerifierPreprocessing<F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    fn from(preprocessing: &JoltPreprocessing<C, F, PCS, ProofTranscript>) -> Self {
+        Self {
+            generators: PCS::verifier_setup(&preprocessing.generators),
+            bytecode: preprocessing
+                .bytecode
+                .to_verifier_preprocessing(&preprocessing.generators),
+            read_write_memory: preprocessing
+                .read_write_memory
+                .to_verifier_preprocessing(&preprocessing.generators),
+            memory_layout: preprocessing.memory_layout.clone(),
+            _field: PhantomData,
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct JoltTraceStep<InstructionSet: JoltInstructionSet> {
     pub instruction_lookup: Option<InstructionSet>,
@@ -76,13 +124,17 @@ pub struct JoltTraceStep<InstructionSet: JoltInstructionSet> {
     pub circuit_flags: [bool; NUM_CIRCUIT_FLAGS],
 }
 
-pub struct ProverDebugInfo<F, ProofTranscript>
+pub struct ProverDebugInfo<F, PCS, ProofTranscript>
 where
     F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 {
     pub(crate) transcript: ProofTranscript,
-    pub(crate) opening_accumulator: ProverOpeningAccumulator<F, ProofTranscript>,
+    pub(crate) opening_accumulator: ProverOpeningAccumulator<'static, F, ProofTranscript>,
+    /// The verifier only holds `PCS::VerifierSetup`, so the prover's setup is passed
+    /// along to let it recompute commitments when comparing openings.
+    pub(crate) pcs_setup: PCS::Setup,
 }
 
 impl<InstructionSet: JoltInstructionSet> JoltTraceStep<InstructionSet> {
@@ -206,6 +258,90 @@ pub type JoltPolynomials<F: JoltField> = JoltStuff<DensePolynomial<F>>;
 pub type JoltCommitments<PCS: CommitmentScheme<ProofTranscript>, ProofTranscript: Transcript> =
     JoltStuff<PCS::Commitment>;
 
+/// The evaluations of a witness polynomial as produced by witness generation. Flags, chunks
+/// and timestamps are kept as integers so that they can be committed to through
+/// [`CommitmentScheme::batch_commit_small`]; every other polynomial is `Dense`, i.e. only
+/// exists as field elements in [`JoltPolynomials`].
+#[derive(Debug, Default)]
+pub enum CompactEvals {
+    #[default]
+    Dense,
+    Bits(Vec<bool>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl CompactEvals {
+    fn len(&self) -> Option<usize> {
+        match self {
+            CompactEvals::Dense => None,
+            CompactEvals::Bits(evals) => Some(evals.len()),
+            CompactEvals::U16(evals) => Some(evals.len()),
+            CompactEvals::U32(evals) => Some(evals.len()),
+        }
+    }
+}
+
+impl CanonicalSerialize for CompactEvals {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        match self {
+            CompactEvals::Dense => 0u8.serialize_with_mode(&mut writer, compress),
+            CompactEvals::Bits(evals) => {
+                1u8.serialize_with_mode(&mut writer, compress)?;
+                evals.serialize_with_mode(&mut writer, compress)
+            }
+            CompactEvals::U16(evals) => {
+                2u8.serialize_with_mode(&mut writer, compress)?;
+                evals.serialize_with_mode(&mut writer, compress)
+            }
+            CompactEvals::U32(evals) => {
+                3u8.serialize_with_mode(&mut writer, compress)?;
+                evals.serialize_with_mode(&mut writer, compress)
+            }
+        }
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        1 + match self {
+            CompactEvals::Dense => 0,
+            CompactEvals::Bits(evals) => evals.serialized_size(compress),
+            CompactEvals::U16(evals) => evals.serialized_size(compress),
+            CompactEvals::U32(evals) => evals.serialized_size(compress),
+        }
+    }
+}
+
+impl Valid for CompactEvals {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for CompactEvals {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(
+            match u8::deserialize_with_mode(&mut reader, compress, validate)? {
+                0 => CompactEvals::Dense,
+                1 => CompactEvals::Bits(Vec::deserialize_with_mode(reader, compress, validate)?),
+                2 => CompactEvals::U16(Vec::deserialize_with_mode(reader, compress, validate)?),
+                3 => CompactEvals::U32(Vec::deserialize_with_mode(reader, compress, validate)?),
+                _ => return Err(SerializationError::InvalidData),
+            },
+        )
+    }
+}
+
+/// The compact evaluations of [`JoltPolynomials`], field by field.
+pub type JoltCompactWitness = JoltStuff<CompactEvals>;
+
 impl<
         const C: usize,
         T: CanonicalSerialize + CanonicalDeserialize + Default + Sync,
@@ -229,45 +365,120 @@ impl<
 }
 
 impl<F: JoltField> JoltPolynomials<F> {
+    /// Commits to the polynomials, also returning the [`CommitmentScheme::OpeningHint`]s of
+    /// `read_write_values()` followed by `init_final_values()`, for
+    /// [`ProverOpeningAccumulator::reduce_and_prove`]. Polynomials whose `compact` evaluations
+    /// are integers are committed to from those, through the small-scalar path.
     #[tracing::instrument(skip_all, name = "JoltPolynomials::commit")]
     pub fn commit<const C: usize, PCS, ProofTranscript>(
         &self,
+        compact: &JoltCompactWitness,
         preprocessing: &JoltPreprocessing<C, F, PCS, ProofTranscript>,
-    ) -> JoltCommitments<PCS, ProofTranscript>
+    ) -> (JoltCommitments<PCS, ProofTranscript>, Vec<PCS::OpeningHint>)
     where
         PCS: CommitmentScheme<ProofTranscript, Field = F>,
         ProofTranscript: Transcript,
     {
         let mut commitments = JoltCommitments::<PCS, ProofTranscript>::initialize(preprocessing);
+        let generators = &preprocessing.generators;
 
         let trace_polys = self.read_write_values();
-        let trace_comitments =
-            PCS::batch_commit_polys_ref(&trace_polys, &preprocessing.generators, BatchType::Big);
-        commitments
+        let compact_trace_polys = compact.read_write_values();
+        assert_eq!(trace_polys.len(), compact_trace_polys.len());
+        let mut bits: Vec<&[bool]> = vec![];
+        let mut u16s: Vec<&[u16]> = vec![];
+        let mut u32s: Vec<&[u32]> = vec![];
+        let mut dense: Vec<&[F]> = vec![];
+        for (poly, compact) in trace_polys.iter().zip(compact_trace_polys.iter()) {
+            debug_assert!(compact.len().map_or(true, |len| len == poly.len()));
+            match compact {
+                CompactEvals::Dense => dense.push(poly.evals_ref()),
+                CompactEvals::Bits(evals) => bits.push(evals),
+                CompactEvals::U16(evals) => u16s.push(evals),
+                CompactEvals::U32(evals) => u32s.push(evals),
+            }
+        }
+        let ((bits, u16s), (u32s, dense)) = rayon::join(
+            || {
+                rayon::join(
+                    || PCS::batch_commit_small_with_hints(&bits, generators, BatchType::Big),
+                    || PCS::batch_commit_small_with_hints(&u16s, generators, BatchType::Big),
+                )
+            },
+            || {
+                rayon::join(
+                    || PCS::batch_commit_small_with_hints(&u32s, generators, BatchType::Big),
+                    || PCS::batch_commit_with_hints(&dense, generators, BatchType::Big),
+                )
+            },
+        );
+        let mut bits = bits.0.into_iter().zip(bits.1);
+        let mut u16s = u16s.0.into_iter().zip(u16s.1);
+        let mut u32s = u32s.0.into_iter().zip(u32s.1);
+        let mut dense = dense.0.into_iter().zip(dense.1);
+        let mut hints: Vec<PCS::OpeningHint> = commitments
             .read_write_values_mut()
             .into_iter()
-            .zip(trace_comitments.into_iter())
-            .for_each(|(dest, src)| *dest = src);
+            .zip(compact_trace_polys)
+            .map(|(dest, compact)| {
+                let (commitment, hint) = match compact {
+                    CompactEvals::Dense => dense.next(),
+                    CompactEvals::Bits(_) => bits.next(),
+                    CompactEvals::U16(_) => u16s.next(),
+                    CompactEvals::U32(_) => u32s.next(),
+                }
+                .unwrap();
+                *dest = commitment;
+                hint
+            })
+            .collect();
 
-        commitments.bytecode.t_final =
-            PCS::commit(&self.bytecode.t_final, &preprocessing.generators);
-        (
-            commitments.read_write_memory.v_final,
-            commitments.read_write_memory.t_final,
-        ) = rayon::join(
-            || PCS::commit(&self.read_write_memory.v_final, &preprocessing.generators),
-            || PCS::commit(&self.read_write_memory.t_final, &preprocessing.generators),
-        );
-        commitments.instruction_lookups.final_cts = PCS::batch_commit_polys(
-            &self.instruction_lookups.final_cts,
-            &preprocessing.generators,
-            BatchType::Big,
-        );
+        let init_final_polys = self.init_final_values();
+        let compact_init_final_polys = compact.init_final_values();
+        assert_eq!(init_final_polys.len(), compact_init_final_polys.len());
+        let init_final_commitments: Vec<_> = init_final_polys
+            .into_par_iter()
+            .zip(compact_init_final_polys.into_par_iter())
+            .map(|(poly, compact)| match compact {
+                CompactEvals::Dense => PCS::commit_with_hint(poly, generators),
+                CompactEvals::Bits(evals) => {
+                    commit_small::<PCS, ProofTranscript, _>(evals, generators)
+                }
+                CompactEvals::U16(evals) => {
+                    commit_small::<PCS, ProofTranscript, _>(evals, generators)
+                }
+                CompactEvals::U32(evals) => {
+                    commit_small::<PCS, ProofTranscript, _>(evals, generators)
+                }
+            })
+            .collect();
+        for (dest, (commitment, hint)) in commitments
+            .init_final_values_mut()
+            .into_iter()
+            .zip(init_final_commitments)
+        {
+            *dest = commitment;
+            hints.push(hint);
+        }
 
-        commitments
+        (commitments, hints)
     }
 }
 
+/// Commits to a single polynomial from its compact evaluations.
+fn commit_small<PCS, ProofTranscript, S: SmallScalar>(
+    evals: &[S],
+    setup: &PCS::Setup,
+) -> (PCS::Commitment, PCS::OpeningHint)
+where
+    PCS: CommitmentScheme<ProofTranscript>,
+    ProofTranscript: Transcript,
+{
+    let (mut commitments, mut hints) =
+        PCS::batch_commit_small_with_hints(&[evals], setup, BatchType::Small);
+    (commitments.pop().unwrap(), hints.pop().unwrap())
+}
+
 pub trait Jolt<F, PCS, const C: usize, const M: usize, ProofTranscript>
 where
     F: JoltField,
@@ -278,8 +489,36 @@ where
     type Subtables: JoltSubtableSet<F>;
     type Constraints: R1CSConstraints<C, F>;
 
+    /// Preprocesses a program for proving programs within the given bounds, deriving the
+    /// PCS setup from `srs`. Fails if `srs` is too small for these bounds.
     #[tracing::instrument(skip_all, name = "Jolt::preprocess")]
     fn preprocess(
+        bytecode: Vec<ELFInstruction>,
+        memory_layout: MemoryLayout,
+        memory_init: Vec<(u64, u8)>,
+        srs: &PCS::SRS,
+        max_bytecode_size: usize,
+        max_memory_address: usize,
+        max_trace_length: usize,
+    ) -> Result<JoltPreprocessing<C, F, PCS, ProofTranscript>, SRSError> {
+        let commitment_shapes =
+            Self::commitment_shapes(max_bytecode_size, max_memory_address, max_trace_length);
+        let generators = PCS::setup_from_srs(srs, &commitment_shapes)?;
+
+        Ok(Self::preprocess_with_generators(
+            bytecode,
+            memory_layout,
+            memory_init,
+            generators,
+        ))
+    }
+
+    /// Same as [`Jolt::preprocess`], but with the setup derived from
+    /// [`CommitmentScheme::insecure_srs`]. Proofs made with it can be forged, so it is only
+    /// available in tests and with the `insecure-setup` feature.
+    #[cfg(any(test, feature = "insecure-setup"))]
+    #[tracing::instrument(skip_all, name = "Jolt::insecure_preprocess")]
+    fn insecure_preprocess(
         bytecode: Vec<ELFInstruction>,
         memory_layout: MemoryLayout,
         memory_init: Vec<(u64, u8)>,
@@ -287,6 +526,21 @@ where
         max_memory_address: usize,
         max_trace_length: usize,
     ) -> JoltPreprocessing<C, F, PCS, ProofTranscript> {
+        let commitment_shapes =
+            Self::commitment_shapes(max_bytecode_size, max_memory_address, max_trace_length);
+        let generators = PCS::setup(&commitment_shapes);
+
+        Self::preprocess_with_generators(bytecode, memory_layout, memory_init, generators)
+    }
+
+    /// The shapes of all polynomials committed to when proving programs within the given
+    /// bounds. Pass these to [`CommitmentScheme::setup_from_srs`] to derive generators
+    /// for [`Jolt::preprocess_with_generators`] from an externally provided SRS.
+    fn commitment_shapes(
+        max_bytecode_size: usize,
+        max_memory_address: usize,
+        max_trace_length: usize,
+    ) -> Vec<CommitShape> {
         let bytecode_commitment_shapes = BytecodeProof::<F, PCS, ProofTranscript>::commit_shapes(
             max_bytecode_size,
             max_trace_length,
@@ -308,13 +562,32 @@ where
             ProofTranscript,
         >::commitment_shapes(max_trace_length);
 
+        [
+            bytecode_commitment_shapes,
+            ram_commitment_shapes,
+            timestamp_range_check_commitment_shapes,
+            instruction_lookups_commitment_shapes,
+        ]
+        .concat()
+    }
+
+    /// Same as [`Jolt::preprocess`], but takes already derived `generators` instead of an
+    /// SRS, e.g. when the setup was loaded from disk.
+    #[tracing::instrument(skip_all, name = "Jolt::preprocess_with_generators")]
+    fn preprocess_with_generators(
+        bytecode: Vec<ELFInstruction>,
+        memory_layout: MemoryLayout,
+        memory_init: Vec<(u64, u8)>,
+        generators: PCS::Setup,
+    ) -> JoltPreprocessing<C, F, PCS, ProofTranscript> {
         let instruction_lookups_preprocessing = InstructionLookupsPreprocessing::preprocess::<
             M,
             Self::InstructionSet,
             Self::Subtables,
         >();
 
-        let read_write_memory_preprocessing = ReadWriteMemoryPreprocessing::preprocess(memory_init);
+        let read_write_memory_preprocessing =
+            ReadWriteMemoryPreprocessing::preprocess(memory_init, &memory_layout);
 
         let bytecode_rows: Vec<BytecodeRow> = bytecode
             .into_iter()
@@ -337,15 +610,6 @@ where
             .collect();
         let bytecode_preprocessing = BytecodePreprocessing::<F>::preprocess(bytecode_rows);
 
-        let commitment_shapes = [
-            bytecode_commitment_shapes,
-            ram_commitment_shapes,
-            timestamp_range_check_commitment_shapes,
-            instruction_lookups_commitment_shapes,
-        ]
-        .concat();
-        let generators = PCS::setup(&commitment_shapes);
-
         JoltPreprocessing {
             generators,
             memory_layout,
@@ -355,40 +619,82 @@ where
         }
     }
 
+    /// Proves execution of `trace`. The prover is deterministic: for a fixed
+    /// `preprocessing`, `program_io` and `trace`, the serialized proof and commitments
+    /// are byte-identical across runs and across `config.num_threads`. The possible
+    /// sources of nondeterminism are controlled as follows:
+    /// - Randomness: the prover samples none. Every challenge is derived from the
+    ///   Fiat-Shamir transcript, and the PCS setup is part of `preprocessing`.
+    /// - Rayon reductions: parallel sums and MSMs are over field and group elements, whose
+    ///   arithmetic is exact, associative and commutative, so the result does not depend
+    ///   on how rayon splits the work. Group elements are normalized to affine form before
+    ///   they are serialized.
+    /// - Hash map iteration: no `HashMap` or `HashSet` is iterated when computing the
+    ///   proof. The only hash sets are test-only sanity checks of the memory-checking
+    ///   multisets.
+    ///
+    /// `fib_proof_reproducible_across_runs_and_thread_counts` checks this guarantee.
     #[tracing::instrument(skip_all, name = "Jolt::prove")]
     fn prove(
         program_io: JoltDevice,
         mut trace: Vec<JoltTraceStep<Self::InstructionSet>>,
         preprocessing: JoltPreprocessing<C, F, PCS, ProofTranscript>,
-    ) -> (
-        JoltProof<
-            C,
-            M,
-            <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
-            F,
-            PCS,
-            Self::InstructionSet,
-            Self::Subtables,
-            ProofTranscript,
-        >,
-        JoltCommitments<PCS, ProofTranscript>,
-        Option<ProverDebugInfo<F, ProofTranscript>>,
-    ) {
+        config: &ProverConfig,
+    ) -> Result<
+        (
+            JoltProof<
+                C,
+                M,
+                <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+                F,
+                PCS,
+                Self::InstructionSet,
+                Self::Subtables,
+                ProofTranscript,
+            >,
+            JoltCommitments<PCS, ProofTranscript>,
+            Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
+        ),
+        ProverError,
+    > {
+        if let Some(num_threads) = config.num_threads {
+            let inner_config = ProverConfig {
+                num_threads: None,
+                ..config.clone()
+            };
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .expect("failed to build prover thread pool");
+            return pool.install(|| Self::prove(program_io, trace, preprocessing, &inner_config));
+        }
+
+        let progress = ProgressReporter::new(config);
         let trace_length = trace.len();
         let padded_trace_length = trace_length.next_power_of_two();
-        println!("Trace length: {}", trace_length);
+        tracing::info!(trace_length, "Jolt::prove");
 
         JoltTraceStep::pad(&mut trace);
 
+        assert_eq!(
+            program_io.memory_layout.private_inputs, preprocessing.memory_layout.private_inputs,
+            "Program IO and preprocessing disagree on whether inputs are private"
+        );
+        // Private inputs are committed to as part of the witness, so they are
+        // omitted from the proof's (public) program IO.
+        let public_io = program_io.public_io();
+
         let mut transcript = ProofTranscript::new(b"Jolt transcript");
+        transcript.begin_stage("Preamble");
         Self::fiat_shamir_preamble(
             &mut transcript,
-            &program_io,
+            &public_io,
             &program_io.memory_layout,
             trace_length,
         );
 
-        let instruction_polynomials =
+        progress.stage(ProverStage::WitnessGeneration)?;
+        let (instruction_polynomials, instruction_compact) =
             InstructionLookupsProof::<
                 C,
                 M,
@@ -399,25 +705,26 @@ where
                 ProofTranscript,
             >::generate_witness(&preprocessing.instruction_lookups, &trace);
 
-        let (memory_polynomials, read_timestamps) = ReadWriteMemoryPolynomials::generate_witness(
+        let (memory_polynomials, memory_compact) = ReadWriteMemoryPolynomials::generate_witness(
             &program_io,
             &preprocessing.read_write_memory,
             &trace,
         );
 
-        let (bytecode_polynomials, range_check_polys) = rayon::join(
-            || {
-                BytecodeProof::<F, PCS, ProofTranscript>::generate_witness(
-                    &preprocessing.bytecode,
-                    &mut trace,
-                )
-            },
-            || {
-                TimestampValidityProof::<F, PCS, ProofTranscript>::generate_witness(
-                    &read_timestamps,
-                )
-            },
-        );
+        let ((bytecode_polynomials, bytecode_compact), (range_check_polys, range_check_compact)) =
+            rayon::join(
+                || {
+                    BytecodeProof::<F, PCS, ProofTranscript>::generate_witness(
+                        &preprocessing.bytecode,
+                        &mut trace,
+                    )
+                },
+                || {
+                    TimestampValidityProof::<F, PCS, ProofTranscript>::generate_witness(
+                        &memory_compact.read_timestamps(),
+                    )
+                },
+            );
 
         let r1cs_builder = Self::Constraints::construct_constraints(
             padded_trace_length,
@@ -430,7 +737,7 @@ where
             ProofTranscript,
         >::setup(&r1cs_builder, padded_trace_length);
 
-        let r1cs_polynomials = R1CSPolynomials::new::<
+        let (r1cs_polynomials, r1cs_compact) = R1CSPolynomials::new::<
             C,
             M,
             Self::InstructionSet,
@@ -444,11 +751,22 @@ where
             instruction_lookups: instruction_polynomials,
             r1cs: r1cs_polynomials,
         };
+        let compact_witness = JoltCompactWitness {
+            bytecode: bytecode_compact,
+            read_write_memory: memory_compact,
+            timestamp_range_check: range_check_compact,
+            instruction_lookups: instruction_compact,
+            r1cs: r1cs_compact,
+        };
 
         r1cs_builder.compute_aux(&mut jolt_polynomials);
 
-        let jolt_commitments = jolt_polynomials.commit::<C, PCS, ProofTranscript>(&preprocessing);
+        progress.stage(ProverStage::Commitments)?;
+        let (jolt_commitments, opening_hints) =
+            jolt_polynomials.commit::<C, PCS, ProofTranscript>(&compact_witness, &preprocessing);
+        drop_in_background_thread(compact_witness);
 
+        transcript.begin_stage("Commitments");
         transcript.append_scalar(&spartan_key.vk_digest);
 
         jolt_commitments
@@ -461,8 +779,16 @@ where
             .for_each(|value| value.append_to_transcript(&mut transcript));
 
         let mut opening_accumulator: ProverOpeningAccumulator<F, ProofTranscript> =
-            ProverOpeningAccumulator::new();
+            ProverOpeningAccumulator::new::<PCS>();
+        let committed_polynomials = [
+            jolt_polynomials.read_write_values(),
+            jolt_polynomials.init_final_values(),
+        ]
+        .concat();
+        opening_accumulator.register_committed_polynomials(&committed_polynomials);
 
+        progress.stage(ProverStage::BytecodeMemoryChecking)?;
+        transcript.begin_stage("BytecodeMemoryChecking");
         let bytecode_proof = BytecodeProof::prove_memory_checking(
             &preprocessing.generators,
             &preprocessing.bytecode,
@@ -470,16 +796,22 @@ where
             &jolt_polynomials,
             &mut opening_accumulator,
             &mut transcript,
+            config.grand_product,
         );
 
+        progress.stage(ProverStage::InstructionLookups)?;
+        transcript.begin_stage("InstructionLookups");
         let instruction_proof = InstructionLookupsProof::prove(
             &preprocessing.generators,
             &jolt_polynomials,
             &preprocessing.instruction_lookups,
             &mut opening_accumulator,
             &mut transcript,
+            config.grand_product,
         );
 
+        progress.stage(ProverStage::ReadWriteMemoryChecking)?;
+        transcript.begin_stage("ReadWriteMemoryChecking");
         let memory_proof = ReadWriteMemoryProof::prove(
             &preprocessing.generators,
             &preprocessing.read_write_memory,
@@ -487,8 +819,12 @@ where
             &program_io,
             &mut opening_accumulator,
             &mut transcript,
-        );
+            config.grand_product,
+            &progress,
+        )?;
 
+        progress.stage(ProverStage::Spartan)?;
+        transcript.begin_stage("Spartan");
         let spartan_proof = UniformSpartanProof::<
             C,
             <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
@@ -504,34 +840,38 @@ where
         .expect("r1cs proof failed");
 
         // Batch-prove all openings
-        let opening_proof =
-            opening_accumulator.reduce_and_prove::<PCS>(&preprocessing.generators, &mut transcript);
+        progress.stage(ProverStage::OpeningProof)?;
+        transcript.begin_stage("OpeningProof");
+        let opening_proof = opening_accumulator.reduce_and_prove::<PCS>(
+            &preprocessing.generators,
+            &opening_hints,
+            &mut transcript,
+        );
+        drop_in_background_thread(opening_hints);
+        let debug_info = config.collect_debug_info.then(|| ProverDebugInfo {
+            transcript,
+            opening_accumulator: opening_accumulator.into_owned(),
+            pcs_setup: preprocessing.generators.clone(),
+        });
 
         drop_in_background_thread(jolt_polynomials);
+        progress.stage(ProverStage::Done)?;
 
         let jolt_proof = JoltProof {
             trace_length,
-            program_io,
+            program_io: public_io,
             bytecode: bytecode_proof,
             read_write_memory: memory_proof,
             instruction_lookups: instruction_proof,
             r1cs: spartan_proof,
             opening_proof,
         };
-
-        #[cfg(test)]
-        let debug_info = Some(ProverDebugInfo {
-            transcript,
-            opening_accumulator,
-        });
-        #[cfg(not(test))]
-        let debug_info = None;
-        (jolt_proof, jolt_commitments, debug_info)
+        Ok((jolt_proof, jolt_commitments, debug_info))
     }
 
     #[tracing::instrument(skip_all)]
     fn verify(
-        mut preprocessing: JoltPreprocessing<C, F, PCS, ProofTranscript>,
+        preprocessing: JoltVerifierPreprocessing<F, PCS, ProofTranscript>,
         proof: JoltProof<
             C,
             M,
@@ -542,8 +882,8 @@ where
             Self::Subtables,
             ProofTranscript,
         >,
-        commitments: JoltCommitments<PCS, ProofTranscript>,
-        _debug_info: Option<ProverDebugInfo<F, ProofTranscript>>,
+        mut commitments: JoltCommitments<PCS, ProofTranscript>,
+        _debug_info: Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
     ) -> Result<(), ProofVerifyError> {
         let mut transcript = ProofTranscript::new(b"Jolt transcript");
         let mut opening_accumulator: VerifierOpeningAccumulator<F, PCS, ProofTranscript> =
@@ -552,9 +892,9 @@ where
         #[cfg(test)]
         if let Some(debug_info) = _debug_info {
             transcript.compare_to(debug_info.transcript);
-            opening_accumulator
-                .compare_to(debug_info.opening_accumulator, &preprocessing.generators);
+            opening_accumulator.compare_to(debug_info.opening_accumulator, &debug_info.pcs_setup);
         }
+        transcript.begin_stage("Preamble");
         Self::fiat_shamir_preamble(
             &mut transcript,
             &proof.program_io,
@@ -571,6 +911,7 @@ where
             &r1cs_builder,
             padded_trace_length,
         );
+        transcript.begin_stage("Commitments");
         transcript.append_scalar(&spartan_key.vk_digest);
 
         let r1cs_proof = R1CSProof {
@@ -588,24 +929,43 @@ where
             .iter()
             .for_each(|value| value.append_to_transcript(&mut transcript));
 
+        // The bytecode and initial memory are committed to in the preprocessing rather than
+        // by the prover, so their commitments are taken from the trusted verifier key.
+        commitments.bytecode.v_init_final = Some(preprocessing.bytecode.v_init_final.clone());
+        commitments.read_write_memory.v_init_bytecode =
+            Some(preprocessing.read_write_memory.v_init_bytecode.clone());
+
+        let bytecode_preprocessing = preprocessing.bytecode.to_preprocessing();
+        let mut read_write_memory_preprocessing =
+            preprocessing.read_write_memory.to_preprocessing();
+        let instruction_lookups_preprocessing =
+            InstructionLookupsPreprocessing::verifier_preprocess::<
+                M,
+                Self::InstructionSet,
+                Self::Subtables,
+            >();
+
+        transcript.begin_stage("BytecodeMemoryChecking");
         Self::verify_bytecode(
-            &preprocessing.bytecode,
+            &bytecode_preprocessing,
             &preprocessing.generators,
             proof.bytecode,
             &commitments,
             &mut opening_accumulator,
             &mut transcript,
         )?;
+        transcript.begin_stage("InstructionLookups");
         Self::verify_instruction_lookups(
-            &preprocessing.instruction_lookups,
+            &instruction_lookups_preprocessing,
             &preprocessing.generators,
             proof.instruction_lookups,
             &commitments,
             &mut opening_accumulator,
             &mut transcript,
         )?;
+        transcript.begin_stage("ReadWriteMemoryChecking");
         Self::verify_memory(
-            &mut preprocessing.read_write_memory,
+            &mut read_write_memory_preprocessing,
             &preprocessing.generators,
             &preprocessing.memory_layout,
             proof.read_write_memory,
@@ -614,6 +974,7 @@ where
             &mut opening_accumulator,
             &mut transcript,
         )?;
+        transcript.begin_stage("Spartan");
         Self::verify_r1cs(
             r1cs_proof,
             &commitments,
@@ -622,6 +983,7 @@ where
         )?;
 
         // Batch-verify all openings
+        transcript.begin_stage("OpeningProof");
         opening_accumulator.reduce_and_verify(
             &preprocessing.generators,
             &proof.opening_proof,
@@ -634,7 +996,7 @@ where
     #[tracing::instrument(skip_all)]
     fn verify_instruction_lookups<'a>(
         preprocessing: &InstructionLookupsPreprocessing<C, F>,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         proof: InstructionLookupsProof<
             C,
             M,
@@ -661,7 +1023,7 @@ where
     #[tracing::instrument(skip_all)]
     fn verify_bytecode<'a>(
         preprocessing: &BytecodePreprocessing<F>,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         proof: BytecodeProof<F, PCS, ProofTranscript>,
         commitments: &'a JoltCommitments<PCS, ProofTranscript>,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
@@ -676,13 +1038,14 @@ where
             opening_accumulator,
             transcript,
         )
+        .map_err(ProofVerifyError::BytecodeMemoryChecking)
     }
 
     #[allow(clippy::too_many_arguments)]
     #[tracing::instrument(skip_all)]
     fn verify_memory<'a>(
         preprocessing: &mut ReadWriteMemoryPreprocessing,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         memory_layout: &MemoryLayout,
         proof: ReadWriteMemoryProof<F, PCS, ProofTranscript>,
         commitment: &'a JoltCommitments<PCS, ProofTranscript>,
@@ -690,14 +1053,25 @@ where
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        assert!(program_io.inputs.len() <= memory_layout.max_input_size as usize);
-        assert!(program_io.outputs.len() <= memory_layout.max_output_size as usize);
+        if program_io.inputs.len() > memory_layout.max_input_size as usize {
+            return Err(ProofVerifyError::InputTooLarge);
+        }
+        if program_io.outputs.len() > memory_layout.max_output_size as usize {
+            return Err(ProofVerifyError::OutputTooLarge);
+        }
+        if memory_layout.private_inputs && !program_io.inputs.is_empty() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                0,
+                program_io.inputs.len(),
+            ));
+        }
         // pair the memory layout with the program io from the proof
         preprocessing.program_io = Some(JoltDevice {
             inputs: program_io.inputs,
             outputs: program_io.outputs,
             panic: program_io.panic,
             memory_layout: memory_layout.clone(),
+            private_outputs: vec![],
         });
 
         ReadWriteMemoryProof::verify(
@@ -722,9 +1096,7 @@ where
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        proof
-            .verify(commitments, opening_accumulator, transcript)
-            .map_err(|e| ProofVerifyError::SpartanError(e.to_string()))
+        proof.verify(commitments, opening_accumulator, transcript)
     }
 
     fn fiat_shamir_preamble(
@@ -740,6 +1112,7 @@ where
         transcript.append_u64(Self::Subtables::COUNT as u64);
         transcript.append_u64(memory_layout.max_input_size);
         transcript.append_u64(memory_layout.max_output_size);
+        transcript.append_u64(memory_layout.private_inputs as u64);
         transcript.append_bytes(&program_io.inputs);
         transcript.append_bytes(&program_io.outputs);
         transcript.append_u64(program_io.panic as u64);
@@ -748,6 +1121,9 @@ where
 
 pub mod bytecode;
 pub mod instruction_lookups;
+pub mod prover_config;
 pub mod read_write_memory;
 pub mod rv32i_vm;
+#[cfg(test)]
+mod tamper;
 pub mod timestamp_range_check;
@@ -0,0 +1,181 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::subprotocols::grand_product::GrandProductBackend;
+use crate::utils::cancellation::CancellationToken;
+use crate::utils::errors::ProverError;
+
+/// Prover-side knobs for [`Jolt::prove`](super::Jolt::prove). None of these affect
+/// what the verifier checks, so proofs generated with different configs verify
+/// against the same preprocessing.
+#[derive(Clone)]
+pub struct ProverConfig {
+    /// Number of threads in a dedicated rayon pool to prove in. `None` uses the
+    /// global rayon pool.
+    pub num_threads: Option<usize>,
+    /// Whether to return `ProverDebugInfo` (the prover's transcript and opening
+    /// accumulator) alongside the proof. Defaults to `true` only in tests.
+    pub collect_debug_info: bool,
+    /// Backend used for the memory-checking grand products.
+    pub grand_product: GrandProductBackend,
+    /// Whether to emit a `tracing` event at each prover stage boundary.
+    pub report_progress: bool,
+    /// Invoked synchronously at each prover stage boundary, so it should return
+    /// quickly (e.g. by forwarding to a channel).
+    pub progress_callback: Option<ProgressCallback>,
+    /// If set, proving returns `ProverError::Cancelled` at the first stage boundary
+    /// after the token is cancelled.
+    pub cancellation_token: Option<CancellationToken>,
+}
+
+pub type ProgressCallback = Arc<dyn Fn(ProverProgress) + Send + Sync>;
+
+impl Default for ProverConfig {
+    fn default() -> Self {
+        Self {
+            num_threads: None,
+            collect_debug_info: cfg!(test),
+            grand_product: GrandProductBackend::default(),
+            report_progress: false,
+            progress_callback: None,
+            cancellation_token: None,
+        }
+    }
+}
+
+impl fmt::Debug for ProverConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProverConfig")
+            .field("num_threads", &self.num_threads)
+            .field("collect_debug_info", &self.collect_debug_info)
+            .field("grand_product", &self.grand_product)
+            .field("report_progress", &self.report_progress)
+            .field("progress_callback", &self.progress_callback.is_some())
+            .field("cancellation_token", &self.cancellation_token)
+            .finish()
+    }
+}
+
+impl ProverConfig {
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = Some(num_threads);
+        self
+    }
+
+    pub fn with_debug_info(mut self, collect_debug_info: bool) -> Self {
+        self.collect_debug_info = collect_debug_info;
+        self
+    }
+
+    pub fn with_grand_product(mut self, grand_product: GrandProductBackend) -> Self {
+        self.grand_product = grand_product;
+        self
+    }
+
+    pub fn with_progress(mut self, report_progress: bool) -> Self {
+        self.report_progress = report_progress;
+        self
+    }
+
+    pub fn with_progress_callback(
+        mut self,
+        callback: impl Fn(ProverProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress_callback = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+}
+
+/// The stages of [`Jolt::prove`](super::Jolt::prove), in the order they run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProverStage {
+    WitnessGeneration,
+    Commitments,
+    BytecodeMemoryChecking,
+    InstructionLookups,
+    ReadWriteMemoryChecking,
+    TimestampRangeCheck,
+    Spartan,
+    OpeningProof,
+    Done,
+}
+
+impl ProverStage {
+    /// Fixed estimate of the fraction of total proving time spent before this
+    /// stage starts. The actual split depends on the program and the PCS.
+    pub fn fraction_complete(self) -> f64 {
+        match self {
+            ProverStage::WitnessGeneration => 0.0,
+            ProverStage::Commitments => 0.05,
+            ProverStage::BytecodeMemoryChecking => 0.25,
+            ProverStage::InstructionLookups => 0.35,
+            ProverStage::ReadWriteMemoryChecking => 0.6,
+            ProverStage::TimestampRangeCheck => 0.7,
+            ProverStage::Spartan => 0.8,
+            ProverStage::OpeningProof => 0.9,
+            ProverStage::Done => 1.0,
+        }
+    }
+}
+
+/// Reported at the start of each [`ProverStage`].
+#[derive(Clone, Copy, Debug)]
+pub struct ProverProgress {
+    pub stage: ProverStage,
+    /// Time since `prove` was called.
+    pub elapsed: Duration,
+    /// Estimate of the fraction of proving work already done, in `[0, 1]`.
+    pub fraction_complete: f64,
+}
+
+/// Reports stage boundaries of a single proof according to a [`ProverConfig`], and
+/// checks at each boundary whether the proof has been cancelled.
+pub struct ProgressReporter<'a> {
+    config: &'a ProverConfig,
+    start: Instant,
+}
+
+impl<'a> ProgressReporter<'a> {
+    pub fn new(config: &'a ProverConfig) -> Self {
+        Self {
+            config,
+            start: Instant::now(),
+        }
+    }
+
+    /// Reports the start of `stage`, or returns `ProverError::Cancelled` if the
+    /// config's cancellation token has been cancelled.
+    pub fn stage(&self, stage: ProverStage) -> Result<(), ProverError> {
+        if self
+            .config
+            .cancellation_token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(ProverError::Cancelled);
+        }
+        let progress = ProverProgress {
+            stage,
+            elapsed: self.start.elapsed(),
+            fraction_complete: stage.fraction_complete(),
+        };
+        if self.config.report_progress {
+            tracing::info!(
+                stage = ?progress.stage,
+                elapsed_ms = progress.elapsed.as_millis() as u64,
+                fraction_complete = progress.fraction_complete,
+                "Jolt prover stage"
+            );
+        }
+        if let Some(callback) = &self.config.progress_callback {
+            callback(progress);
+        }
+        Ok(())
+    }
+}
@@ -14,13 +14,18 @@ use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, Commitm
 use crate::utils::transcript::Transcript;
 use crate::{
     lasso::memory_checking::{
-        MemoryCheckingProof, MemoryCheckingProver, MemoryCheckingVerifier, MultisetHashes,
+        check_multiset_hashes, check_num_hashes, MemoryCheckingProof, MemoryCheckingProver,
+        MemoryCheckingVerifier, MultisetHashes,
     },
     poly::{
         dense_mlpoly::DensePolynomial, eq_poly::EqPolynomial, identity_poly::IdentityPolynomial,
     },
-    subprotocols::sumcheck::SumcheckInstanceProof,
-    utils::{errors::ProofVerifyError, math::Math, mul_0_optimized},
+    subprotocols::{grand_product::GrandProductBackend, sumcheck::SumcheckInstanceProof},
+    utils::{
+        errors::{MemoryCheckingError, Mismatch, ProofVerifyError, ProverError, SumcheckError},
+        math::Math,
+        mul_0_optimized,
+    },
 };
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use common::constants::{
@@ -28,13 +33,24 @@ use common::constants::{
 };
 use common::rv_trace::{JoltDevice, MemoryLayout, MemoryOp};
 
-use super::{timestamp_range_check::TimestampValidityProof, JoltCommitments};
-use super::{JoltPolynomials, JoltStuff, JoltTraceStep};
+use super::{
+    prover_config::{ProgressReporter, ProverStage},
+    timestamp_range_check::TimestampValidityProof,
+    JoltCommitments,
+};
+use super::{CompactEvals, JoltPolynomials, JoltStuff, JoltTraceStep};
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ReadWriteMemoryPreprocessing {
-    min_bytecode_address: u64,
+    /// Witness index of the first word of `bytecode_words`.
+    bytecode_start: usize,
     bytecode_words: Vec<u32>,
+    /// Number of variables of `v_init_bytecode`, the initial memory restricted to the
+    /// smallest power-of-two prefix containing the bytecode.
+    bytecode_num_vars: usize,
+    /// Whether the memory layout marks the inputs as private, in which case `v_input`
+    /// is part of the witness.
+    private_inputs: bool,
     // HACK: The verifier will populate this field by copying inputs/outputs from the
     // `ReadWriteMemoryProof` and the memory layout from preprocessing.
     // Having `program_io` in this preprocessing struct allows the verifier to access it
@@ -45,18 +61,18 @@ pub struct ReadWriteMemoryPreprocessing {
 
 impl ReadWriteMemoryPreprocessing {
     #[tracing::instrument(skip_all, name = "ReadWriteMemoryPreprocessing::preprocess")]
-    pub fn preprocess(memory_init: Vec<(u64, u8)>) -> Self {
+    pub fn preprocess(memory_init: Vec<(u64, u8)>, memory_layout: &MemoryLayout) -> Self {
         let min_bytecode_address = memory_init
             .iter()
             .map(|(address, _)| *address)
             .min()
-            .unwrap_or(0);
+            .unwrap_or(RAM_START_ADDRESS);
 
         let max_bytecode_address = memory_init
             .iter()
             .map(|(address, _)| *address)
             .max()
-            .unwrap_or(0)
+            .unwrap_or(RAM_START_ADDRESS)
             + (BYTES_PER_INSTRUCTION as u64 - 1); // For RV32I, instructions occupy 4 bytes, so the max bytecode address is the max instruction address + 3
 
         let num_words = max_bytecode_address.next_multiple_of(4) / 4 - min_bytecode_address / 4 + 1;
@@ -73,9 +89,79 @@ impl ReadWriteMemoryPreprocessing {
             bytecode_words[remapped_index] = word;
         }
 
+        let bytecode_start = memory_address_to_witness_index(min_bytecode_address, memory_layout);
+        let bytecode_num_vars = (bytecode_start + bytecode_words.len())
+            .next_power_of_two()
+            .log_2();
+
         Self {
-            min_bytecode_address,
+            bytecode_start,
             bytecode_words,
+            bytecode_num_vars,
+            private_inputs: memory_layout.private_inputs,
+            program_io: None,
+        }
+    }
+
+    /// The initial memory restricted to its first `2^bytecode_num_vars` cells, which only
+    /// hold the bytecode. The verifier holds a commitment to this polynomial, and the
+    /// prover opens it at a suffix of the init/final opening point.
+    fn v_init_bytecode<F: JoltField>(&self) -> DensePolynomial<F> {
+        let mut v_init_bytecode: Vec<u64> = vec![0; self.bytecode_num_vars.pow2()];
+        v_init_bytecode[self.bytecode_start..self.bytecode_start + self.bytecode_words.len()]
+            .iter_mut()
+            .zip(self.bytecode_words.iter())
+            .for_each(|(v, word)| *v = *word as u64);
+        DensePolynomial::from_u64(&v_init_bytecode)
+    }
+
+    /// Strips the preprocessing down to what the verifier needs, replacing the bytecode
+    /// words with a commitment to `v_init_bytecode`.
+    pub fn to_verifier_preprocessing<PCS, ProofTranscript>(
+        &self,
+        setup: &PCS::Setup,
+    ) -> ReadWriteMemoryVerifierPreprocessing<PCS, ProofTranscript>
+    where
+        PCS: CommitmentScheme<ProofTranscript>,
+        ProofTranscript: Transcript,
+    {
+        ReadWriteMemoryVerifierPreprocessing {
+            bytecode_num_vars: self.bytecode_num_vars,
+            private_inputs: self.private_inputs,
+            v_init_bytecode: PCS::commit(&self.v_init_bytecode::<PCS::Field>(), setup),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Succinct form of [`ReadWriteMemoryPreprocessing`] shipped to verifiers. Its size is
+/// independent of the length of the program.
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ReadWriteMemoryVerifierPreprocessing<PCS, ProofTranscript>
+where
+    PCS: CommitmentScheme<ProofTranscript>,
+    ProofTranscript: Transcript,
+{
+    bytecode_num_vars: usize,
+    private_inputs: bool,
+    /// Commitment to the initial memory restricted to the bytecode.
+    pub(crate) v_init_bytecode: PCS::Commitment,
+    _marker: PhantomData<ProofTranscript>,
+}
+
+impl<PCS, ProofTranscript> ReadWriteMemoryVerifierPreprocessing<PCS, ProofTranscript>
+where
+    PCS: CommitmentScheme<ProofTranscript>,
+    ProofTranscript: Transcript,
+{
+    /// The [`ReadWriteMemoryPreprocessing`] used by the verifier, without the bytecode
+    /// words (which are only needed for witness generation).
+    pub fn to_preprocessing(&self) -> ReadWriteMemoryPreprocessing {
+        ReadWriteMemoryPreprocessing {
+            bytecode_start: 0,
+            bytecode_words: vec![],
+            bytecode_num_vars: self.bytecode_num_vars,
+            private_inputs: self.private_inputs,
             program_io: None,
         }
     }
@@ -131,10 +217,16 @@ pub struct ReadWriteMemoryStuff<T: CanonicalSerialize + CanonicalDeserialize> {
     pub t_read_ram: T,
     /// Final timestamps.
     pub t_final: T,
+    /// Input values, committed by the prover when the memory layout marks the inputs
+    /// as private (in which case the verifier cannot compute their contribution to
+    /// `v_init` on its own). `None` otherwise.
+    pub v_input: Option<T>,
 
     a_init_final: VerifierComputedOpening<T>,
     /// Initial memory values. RAM is initialized to contain the program bytecode and inputs.
     v_init: VerifierComputedOpening<T>,
+    /// Opening of `v_init_bytecode`, proven against the commitment held by the verifier.
+    pub(crate) v_init_bytecode: VerifierComputedOpening<T>,
     identity: VerifierComputedOpening<T>,
 }
 
@@ -174,11 +266,15 @@ impl<T: CanonicalSerialize + CanonicalDeserialize> StructuredPolynomialData<T>
     }
 
     fn init_final_values(&self) -> Vec<&T> {
-        vec![&self.v_final, &self.t_final]
+        let mut values = vec![&self.v_final, &self.t_final];
+        values.extend(self.v_input.as_ref());
+        values
     }
 
     fn init_final_values_mut(&mut self) -> Vec<&mut T> {
-        vec![&mut self.v_final, &mut self.t_final]
+        let mut values = vec![&mut self.v_final, &mut self.t_final];
+        values.extend(self.v_input.as_mut());
+        values
     }
 }
 
@@ -207,6 +303,12 @@ pub type ReadWriteMemoryCommitments<
 impl<T: CanonicalSerialize + CanonicalDeserialize + Default>
     Initializable<T, ReadWriteMemoryPreprocessing> for ReadWriteMemoryStuff<T>
 {
+    fn initialize(preprocessing: &ReadWriteMemoryPreprocessing) -> Self {
+        Self {
+            v_input: preprocessing.private_inputs.then(T::default),
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
@@ -244,18 +346,36 @@ fn map_to_polys<F: JoltField, const N: usize>(vals: [&[u64]; N]) -> [DensePolyno
         .unwrap()
 }
 
+impl ReadWriteMemoryStuff<CompactEvals> {
+    /// The read timestamps of rd, rs1, rs2 and RAM, as checked by
+    /// [`TimestampValidityProof`](super::timestamp_range_check::TimestampValidityProof).
+    pub fn read_timestamps(&self) -> [&[u32]; MEMORY_OPS_PER_INSTRUCTION] {
+        [
+            &self.t_read_rd,
+            &self.t_read_rs1,
+            &self.t_read_rs2,
+            &self.t_read_ram,
+        ]
+        .map(|t_read| match t_read {
+            CompactEvals::U32(t_read) => t_read.as_slice(),
+            _ => panic!("read timestamps are generated as u32s"),
+        })
+    }
+}
+
 impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
     #[tracing::instrument(skip_all, name = "ReadWriteMemory::new")]
     pub fn generate_witness<InstructionSet: JoltInstructionSet>(
         program_io: &JoltDevice,
         preprocessing: &ReadWriteMemoryPreprocessing,
         trace: &[JoltTraceStep<InstructionSet>],
-    ) -> (Self, [Vec<u64>; MEMORY_OPS_PER_INSTRUCTION]) {
+    ) -> (Self, ReadWriteMemoryStuff<CompactEvals>) {
         assert!(program_io.inputs.len() <= program_io.memory_layout.max_input_size as usize);
         assert!(program_io.outputs.len() <= program_io.memory_layout.max_output_size as usize);
 
         let m = trace.len();
         assert!(m.is_power_of_two());
+        assert!(m - 1 <= u32::MAX as usize, "timestamps must fit in a u32");
 
         let max_trace_address = trace
             .iter()
@@ -269,10 +389,7 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
         let memory_size = max_trace_address.next_power_of_two() as usize;
         let mut v_init: Vec<u64> = vec![0; memory_size];
         // Copy bytecode
-        let mut v_init_index = memory_address_to_witness_index(
-            preprocessing.min_bytecode_address,
-            &program_io.memory_layout,
-        );
+        let mut v_init_index = preprocessing.bytecode_start;
         for word in preprocessing.bytecode_words.iter() {
             v_init[v_init_index] = *word as u64;
             v_init_index += 1;
@@ -292,18 +409,30 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
             v_init_index += 1;
         }
 
+        let v_input: Option<Vec<u64>> = program_io.memory_layout.private_inputs.then(|| {
+            let mut v_input = vec![0; memory_size];
+            let input_start_index = memory_address_to_witness_index(
+                program_io.memory_layout.input_start,
+                &program_io.memory_layout,
+            );
+            let input_end_index = input_start_index + program_io.inputs.len().div_ceil(4);
+            v_input[input_start_index..input_end_index]
+                .copy_from_slice(&v_init[input_start_index..input_end_index]);
+            v_input
+        });
+
         #[cfg(test)]
-        let mut init_tuples: HashSet<(usize, u64, u64)> = HashSet::new();
+        let mut init_tuples: HashSet<(usize, u64, u32)> = HashSet::new();
         #[cfg(test)]
         {
             for (a, v) in v_init.iter().enumerate() {
-                init_tuples.insert((a, *v, 0u64));
+                init_tuples.insert((a, *v, 0u32));
             }
         }
         #[cfg(test)]
-        let mut read_tuples: HashSet<(usize, u64, u64)> = HashSet::new();
+        let mut read_tuples: HashSet<(usize, u64, u32)> = HashSet::new();
         #[cfg(test)]
-        let mut write_tuples: HashSet<(usize, u64, u64)> = HashSet::new();
+        let mut write_tuples: HashSet<(usize, u64, u32)> = HashSet::new();
 
         let mut a_ram: Vec<u64> = Vec::with_capacity(m);
 
@@ -312,22 +441,22 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
         let mut v_read_rd: Vec<u64> = Vec::with_capacity(m);
         let mut v_read_ram: Vec<u64> = Vec::with_capacity(m);
 
-        let mut t_read_rs1: Vec<u64> = Vec::with_capacity(m);
-        let mut t_read_rs2: Vec<u64> = Vec::with_capacity(m);
-        let mut t_read_rd: Vec<u64> = Vec::with_capacity(m);
-        let mut t_read_ram: Vec<u64> = Vec::with_capacity(m);
+        let mut t_read_rs1: Vec<u32> = Vec::with_capacity(m);
+        let mut t_read_rs2: Vec<u32> = Vec::with_capacity(m);
+        let mut t_read_rd: Vec<u32> = Vec::with_capacity(m);
+        let mut t_read_ram: Vec<u32> = Vec::with_capacity(m);
 
         let mut v_write_rd: Vec<u64> = Vec::with_capacity(m);
         let mut v_write_ram: Vec<u64> = Vec::with_capacity(m);
 
-        let mut t_final = vec![0; memory_size];
+        let mut t_final: Vec<u32> = vec![0; memory_size];
         let mut v_final = v_init.clone();
 
         let span = tracing::span!(tracing::Level::DEBUG, "memory_trace_processing");
         let _enter = span.enter();
 
         for (i, step) in trace.iter().enumerate() {
-            let timestamp = i as u64;
+            let timestamp = i as u32;
 
             match step.memory_ops[RS1] {
                 MemoryOp::Read(a) => {
@@ -438,7 +567,7 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
 
         #[cfg(test)]
         {
-            let mut final_tuples: HashSet<(usize, u64, u64)> = HashSet::new();
+            let mut final_tuples: HashSet<(usize, u64, u32)> = HashSet::new();
             for (a, (v, t)) in v_final.iter().zip(t_final.iter()).enumerate() {
                 final_tuples.insert((a, *v, *t));
             }
@@ -449,7 +578,7 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
             assert_eq!(set_difference.len(), 0);
         }
 
-        let [a_ram, v_read_rd, v_read_rs1, v_read_rs2, v_read_ram, v_write_rd, v_write_ram, v_final, t_read_rd_poly, t_read_rs1_poly, t_read_rs2_poly, t_read_ram_poly, t_final, v_init] =
+        let [a_ram, v_read_rd, v_read_rs1, v_read_rs2, v_read_ram, v_write_rd, v_write_ram, v_final, v_init] =
             map_to_polys([
                 &a_ram,
                 &v_read_rd,
@@ -459,13 +588,16 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
                 &v_write_rd,
                 &v_write_ram,
                 &v_final,
-                &t_read_rd,
-                &t_read_rs1,
-                &t_read_rs2,
-                &t_read_ram,
-                &t_final,
                 &v_init,
             ]);
+        let [t_read_rd_poly, t_read_rs1_poly, t_read_rs2_poly, t_read_ram_poly, t_final_poly] =
+            [&t_read_rd, &t_read_rs1, &t_read_rs2, &t_read_ram, &t_final]
+                .par_iter()
+                .map(|t| DensePolynomial::from_small_scalars(t))
+                .collect::<Vec<DensePolynomial<F>>>()
+                .try_into()
+                .unwrap();
+        let v_input = v_input.map(|v_input| DensePolynomial::from_u64(&v_input));
 
         let polynomials = ReadWriteMemoryPolynomials {
             a_ram,
@@ -480,13 +612,24 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
             t_read_rs1: t_read_rs1_poly,
             t_read_rs2: t_read_rs2_poly,
             t_read_ram: t_read_ram_poly,
-            t_final,
+            t_final: t_final_poly,
+            v_input,
             v_init: Some(v_init),
+            v_init_bytecode: None,
             a_init_final: None,
             identity: None,
         };
 
-        (polynomials, [t_read_rd, t_read_rs1, t_read_rs2, t_read_ram])
+        let compact = ReadWriteMemoryStuff {
+            t_read_rd: CompactEvals::U32(t_read_rd),
+            t_read_rs1: CompactEvals::U32(t_read_rs1),
+            t_read_rs2: CompactEvals::U32(t_read_rs2),
+            t_read_ram: CompactEvals::U32(t_read_ram),
+            t_final: CompactEvals::U32(t_final),
+            ..Default::default()
+        };
+
+        (polynomials, compact)
     }
 
     /// Computes the shape of all commitments.
@@ -658,28 +801,41 @@ where
     fn check_multiset_equality(
         _preprocessing: &Self::Preprocessing,
         multiset_hashes: &MultisetHashes<F>,
-    ) {
-        assert_eq!(
-            multiset_hashes.read_hashes.len(),
-            MEMORY_OPS_PER_INSTRUCTION
-        );
-        assert_eq!(
-            multiset_hashes.write_hashes.len(),
-            MEMORY_OPS_PER_INSTRUCTION
-        );
-        assert_eq!(multiset_hashes.init_hashes.len(), 1);
-        assert_eq!(multiset_hashes.final_hashes.len(), 1);
+    ) -> Result<(), MemoryCheckingError> {
+        check_num_hashes(&multiset_hashes.read_hashes, MEMORY_OPS_PER_INSTRUCTION)?;
+        check_num_hashes(&multiset_hashes.write_hashes, MEMORY_OPS_PER_INSTRUCTION)?;
+        check_num_hashes(&multiset_hashes.init_hashes, 1)?;
+        check_num_hashes(&multiset_hashes.final_hashes, 1)?;
 
         let read_hash: F = multiset_hashes.read_hashes.iter().product();
         let write_hash: F = multiset_hashes.write_hashes.iter().product();
         let init_hash = multiset_hashes.init_hashes[0];
         let final_hash = multiset_hashes.final_hashes[0];
 
-        assert_eq!(
-            init_hash * write_hash,
-            final_hash * read_hash,
-            "Multiset hashes don't match"
+        check_multiset_hashes(0, init_hash * write_hash, final_hash * read_hash)
+    }
+
+    fn prove_preprocessed_openings(
+        preprocessing: &ReadWriteMemoryPreprocessing,
+        openings: &mut Self::Openings,
+        opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
+        r_init_final: &[F],
+        transcript: &mut ProofTranscript,
+    ) {
+        // `v_init_bytecode` agrees with `v_init` on its first `2^bytecode_num_vars` cells,
+        // which correspond to the last `bytecode_num_vars` variables.
+        let r_bytecode = &r_init_final[r_init_final.len() - preprocessing.bytecode_num_vars..];
+        let v_init_bytecode = preprocessing.v_init_bytecode();
+        let chis = EqPolynomial::evals(r_bytecode);
+        let claim = v_init_bytecode.evaluate_at_chi(&chis);
+        opening_accumulator.append(
+            &[&v_init_bytecode],
+            DensePolynomial::new(chis),
+            r_bytecode.to_vec(),
+            &[&claim],
+            transcript,
         );
+        openings.v_init_bytecode = Some(claim);
     }
 
     fn protocol_name() -> &'static [u8] {
@@ -694,6 +850,35 @@ where
     PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 {
+    fn verify_preprocessed_openings(
+        preprocessing: &ReadWriteMemoryPreprocessing,
+        commitments: &Self::Commitments,
+        openings: &Self::Openings,
+        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
+        r_init_final: &[F],
+        transcript: &mut ProofTranscript,
+    ) -> Result<(), MemoryCheckingError> {
+        if r_init_final.len() < preprocessing.bytecode_num_vars {
+            return Err(MemoryCheckingError::PreprocessedOpeningPoint {
+                expected: preprocessing.bytecode_num_vars,
+                actual: r_init_final.len(),
+            });
+        }
+        let (Some(v_init_bytecode_commitment), Some(v_init_bytecode)) =
+            (&commitments.v_init_bytecode, &openings.v_init_bytecode)
+        else {
+            return Err(MemoryCheckingError::MissingPreprocessedOpening);
+        };
+        let r_bytecode = &r_init_final[r_init_final.len() - preprocessing.bytecode_num_vars..];
+        opening_accumulator.append(
+            &[v_init_bytecode_commitment],
+            r_bytecode.to_vec(),
+            &[v_init_bytecode],
+            transcript,
+        );
+        Ok(())
+    }
+
     fn compute_verifier_openings(
         openings: &mut Self::Openings,
         preprocessing: &Self::Preprocessing,
@@ -706,31 +891,40 @@ where
         openings.a_init_final =
             Some(IdentityPolynomial::new(r_init_final.len()).evaluate(r_init_final));
 
-        let memory_layout = &preprocessing.program_io.as_ref().unwrap().memory_layout;
+        // `v_init_bytecode` is zero-padded to the full memory size by fixing the leading
+        // variables of `v_init` to zero.
+        let (r_prefix, _) =
+            r_init_final.split_at(r_init_final.len() - preprocessing.bytecode_num_vars);
+        let v_init_bytecode = r_prefix.iter().map(|r| F::one() - *r).product::<F>()
+            * openings.v_init_bytecode.unwrap();
 
-        // TODO(moodlezoup): Compute opening without instantiating v_init polynomial itself
-        let memory_size = r_init_final.len().pow2();
-        let mut v_init: Vec<u64> = vec![0; memory_size];
-        // Copy bytecode
-        let mut v_init_index =
-            memory_address_to_witness_index(preprocessing.min_bytecode_address, memory_layout);
-        for word in preprocessing.bytecode_words.iter() {
-            v_init[v_init_index] = *word as u64;
-            v_init_index += 1;
+        let program_io = preprocessing.program_io.as_ref().unwrap();
+        let memory_layout = &program_io.memory_layout;
+        if memory_layout.private_inputs {
+            // The inputs' contribution to `v_init` is given by the committed `v_input`
+            // (whose presence is checked by `ReadWriteMemoryProof::verify`).
+            openings.v_init = Some(v_init_bytecode + openings.v_input.unwrap_or(F::zero()));
+            return;
         }
+
+        // TODO(moodlezoup): Compute opening without instantiating v_input polynomial itself
+        let memory_size = r_init_final.len().pow2();
+        let mut v_input: Vec<u64> = vec![0; memory_size];
         // Copy input bytes
-        v_init_index = memory_address_to_witness_index(memory_layout.input_start, memory_layout);
-        for chunk in preprocessing.program_io.as_ref().unwrap().inputs.chunks(4) {
+        let mut v_input_index =
+            memory_address_to_witness_index(memory_layout.input_start, memory_layout);
+        for chunk in program_io.inputs.chunks(4) {
             let mut word = [0u8; 4];
             for (i, byte) in chunk.iter().enumerate() {
                 word[i] = *byte;
             }
             let word = u32::from_le_bytes(word);
-            v_init[v_init_index] = word as u64;
-            v_init_index += 1;
+            v_input[v_input_index] = word as u64;
+            v_input_index += 1;
         }
 
-        openings.v_init = Some(DensePolynomial::from_u64(&v_init).evaluate(r_init_final));
+        openings.v_init =
+            Some(v_init_bytecode + DensePolynomial::from_u64(&v_input).evaluate(r_init_final));
     }
 
     fn read_tuples(
@@ -820,9 +1014,54 @@ where
     _pcs: PhantomData<(PCS, ProofTranscript)>,
     num_rounds: usize,
     /// Sumcheck proof that v_final is equal to the program outputs at the relevant indices.
-    sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+    pub(crate) sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
     /// Opening of v_final at the random point chosen over the course of sumcheck
-    opening: F,
+    pub(crate) opening: F,
+    /// Opening of v_input at the random point chosen over the course of sumcheck.
+    /// Only present if the inputs are private.
+    pub(crate) input_opening: Option<F>,
+}
+
+/// Packs the given bytes into words, starting at the witness index corresponding to `address`.
+fn copy_io_words(v_io: &mut [u64], bytes: &[u8], address: u64, memory_layout: &MemoryLayout) {
+    let mut index = memory_address_to_witness_index(address, memory_layout);
+    for chunk in bytes.chunks(4) {
+        let mut word = [0u8; 4];
+        for (i, byte) in chunk.iter().enumerate() {
+            word[i] = *byte;
+        }
+        let word = u32::from_le_bytes(word);
+        v_io[index] = word as u64;
+        index += 1;
+    }
+}
+
+/// Computes the expected values of the IO region of memory: inputs (unless they are
+/// private), outputs, panic bit, and termination bit.
+fn compute_v_io(program_io: &JoltDevice, memory_layout: &MemoryLayout, len: usize) -> Vec<u64> {
+    let mut v_io: Vec<u64> = vec![0; len];
+    if !memory_layout.private_inputs {
+        copy_io_words(
+            &mut v_io,
+            &program_io.inputs,
+            memory_layout.input_start,
+            memory_layout,
+        );
+    }
+    copy_io_words(
+        &mut v_io,
+        &program_io.outputs,
+        memory_layout.output_start,
+        memory_layout,
+    );
+    // Copy panic bit
+    v_io[memory_address_to_witness_index(memory_layout.panic, memory_layout)] =
+        program_io.panic as u64;
+    if !program_io.panic {
+        // Set termination bit
+        v_io[memory_address_to_witness_index(memory_layout.termination, memory_layout)] = 1;
+    }
+    v_io
 }
 
 impl<F, PCS, ProofTranscript> OutputSumcheckProof<F, PCS, ProofTranscript>
@@ -837,17 +1076,18 @@ where
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Self {
+        let memory_layout = &program_io.memory_layout;
         let memory_size = polynomials.v_final.len();
         let num_rounds = memory_size.log_2();
         let r_eq = transcript.challenge_vector(num_rounds);
         let eq: DensePolynomial<F> = DensePolynomial::new(EqPolynomial::evals(&r_eq));
 
-        let input_start_index = memory_address_to_witness_index(
-            program_io.memory_layout.input_start,
-            &program_io.memory_layout,
-        ) as u64;
+        let input_start_index =
+            memory_address_to_witness_index(memory_layout.input_start, memory_layout) as u64;
+        let input_end_index =
+            memory_address_to_witness_index(memory_layout.input_end, memory_layout) as u64;
         let ram_start_index =
-            memory_address_to_witness_index(RAM_START_ADDRESS, &program_io.memory_layout) as u64;
+            memory_address_to_witness_index(RAM_START_ADDRESS, memory_layout) as u64;
 
         let io_witness_range: Vec<_> = (0..memory_size as u64)
             .map(|i| {
@@ -859,58 +1099,77 @@ where
             })
             .collect();
 
-        let mut v_io: Vec<u64> = vec![0; memory_size];
-        // Copy input bytes
-        let mut input_index = memory_address_to_witness_index(
-            program_io.memory_layout.input_start,
-            &program_io.memory_layout,
-        );
-        for chunk in program_io.inputs.chunks(4) {
-            let mut word = [0u8; 4];
-            for (i, byte) in chunk.iter().enumerate() {
-                word[i] = *byte;
-            }
-            let word = u32::from_le_bytes(word);
-            v_io[input_index] = word as u64;
-            input_index += 1;
-        }
-        // Copy output bytes
-        let mut output_index = memory_address_to_witness_index(
-            program_io.memory_layout.output_start,
-            &program_io.memory_layout,
-        );
-        for chunk in program_io.outputs.chunks(4) {
-            let mut word = [0u8; 4];
-            for (i, byte) in chunk.iter().enumerate() {
-                word[i] = *byte;
-            }
-            let word = u32::from_le_bytes(word);
-            v_io[output_index] = word as u64;
-            output_index += 1;
-        }
+        let v_io = compute_v_io(program_io, memory_layout, memory_size);
+
+        if !memory_layout.private_inputs {
+            let mut sumcheck_polys = vec![
+                eq,
+                DensePolynomial::new(io_witness_range),
+                polynomials.v_final.clone(),
+                DensePolynomial::from_u64(&v_io),
+            ];
+
+            // eq * io_witness_range * (v_final - v_io)
+            let output_check_fn = |vals: &[F]| -> F { vals[0] * vals[1] * (vals[2] - vals[3]) };
+
+            let (sumcheck_proof, r_sumcheck, sumcheck_openings) =
+                SumcheckInstanceProof::<F, ProofTranscript>::prove_arbitrary::<_>(
+                    &F::zero(),
+                    num_rounds,
+                    &mut sumcheck_polys,
+                    output_check_fn,
+                    3,
+                    transcript,
+                );
+
+            opening_accumulator.append(
+                &[&polynomials.v_final],
+                DensePolynomial::new(EqPolynomial::evals(&r_sumcheck)),
+                r_sumcheck.to_vec(),
+                &[&sumcheck_openings[2]],
+                transcript,
+            );
 
-        // Copy panic bit
-        v_io[memory_address_to_witness_index(
-            program_io.memory_layout.panic,
-            &program_io.memory_layout,
-        )] = program_io.panic as u64;
-        if !program_io.panic {
-            // Set termination bit
-            v_io[memory_address_to_witness_index(
-                program_io.memory_layout.termination,
-                &program_io.memory_layout,
-            )] = 1;
+            return Self {
+                num_rounds,
+                sumcheck_proof,
+                opening: sumcheck_openings[2], // only need v_final; verifier computes the rest on its own
+                input_opening: None,
+                _pcs: PhantomData,
+            };
         }
 
+        // With private inputs, we additionally check that v_input is zero outside of
+        // the input region, using a random linear combination of the two zero-checks.
+        let v_input = polynomials
+            .v_input
+            .as_ref()
+            .expect("v_input is part of the witness when inputs are private");
+        let beta: F = transcript.challenge_scalar();
+        let input_range: Vec<_> = (0..memory_size as u64)
+            .map(|i| {
+                if i >= input_start_index && i < input_end_index {
+                    F::one()
+                } else {
+                    F::zero()
+                }
+            })
+            .collect();
+
         let mut sumcheck_polys = vec![
             eq,
             DensePolynomial::new(io_witness_range),
             polynomials.v_final.clone(),
             DensePolynomial::from_u64(&v_io),
+            v_input.clone(),
+            DensePolynomial::new(input_range),
         ];
 
-        // eq * io_witness_range * (v_final - v_io)
-        let output_check_fn = |vals: &[F]| -> F { vals[0] * vals[1] * (vals[2] - vals[3]) };
+        // eq * (io_witness_range * (v_final - v_io - v_input) + beta * (1 - input_range) * v_input)
+        let output_check_fn = |vals: &[F]| -> F {
+            vals[0]
+                * (vals[1] * (vals[2] - vals[3] - vals[4]) + beta * (F::one() - vals[5]) * vals[4])
+        };
 
         let (sumcheck_proof, r_sumcheck, sumcheck_openings) =
             SumcheckInstanceProof::<F, ProofTranscript>::prove_arbitrary::<_>(
@@ -923,17 +1182,18 @@ where
             );
 
         opening_accumulator.append(
-            &[&polynomials.v_final],
+            &[&polynomials.v_final, v_input],
             DensePolynomial::new(EqPolynomial::evals(&r_sumcheck)),
             r_sumcheck.to_vec(),
-            &[&sumcheck_openings[2]],
+            &[&sumcheck_openings[2], &sumcheck_openings[4]],
             transcript,
         );
 
         Self {
             num_rounds,
             sumcheck_proof,
-            opening: sumcheck_openings[2], // only need v_final; verifier computes the rest on its own
+            opening: sumcheck_openings[2],
+            input_opening: Some(sumcheck_openings[4]),
             _pcs: PhantomData,
         }
     }
@@ -945,29 +1205,53 @@ where
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
+        let program_io = preprocessing
+            .program_io
+            .as_ref()
+            .ok_or(ProofVerifyError::MissingProgramIO)?;
+        let memory_layout = &program_io.memory_layout;
+
+        if memory_layout.input_start > RAM_START_ADDRESS {
+            return Err(ProofVerifyError::InvalidMemoryLayout);
+        }
+        let io_memory_size = memory_address_to_witness_index(RAM_START_ADDRESS, memory_layout);
+        if !io_memory_size.is_power_of_two() {
+            return Err(ProofVerifyError::InvalidMemoryLayout);
+        }
+        let log_io_memory_size = io_memory_size.log_2();
+        // Memory is indexed by `usize`, so it cannot have more than `usize::BITS` variables
+        let max_num_rounds = usize::BITS as usize;
+        if proof.num_rounds < log_io_memory_size || proof.num_rounds > max_num_rounds {
+            return Err(ProofVerifyError::OutputCheckNumRounds {
+                min: log_io_memory_size,
+                max: max_num_rounds,
+                actual: proof.num_rounds,
+            });
+        }
+
         let r_eq = transcript.challenge_vector(proof.num_rounds);
 
-        let (sumcheck_claim, r_sumcheck) =
-            proof
-                .sumcheck_proof
-                .verify(F::zero(), proof.num_rounds, 3, transcript)?;
+        let beta: Option<F> = if memory_layout.private_inputs {
+            Some(transcript.challenge_scalar())
+        } else {
+            None
+        };
 
-        let eq_eval = EqPolynomial::new(r_eq.to_vec()).evaluate(&r_sumcheck);
+        let (sumcheck_claim, r_sumcheck) = proof
+            .sumcheck_proof
+            .verify(F::zero(), proof.num_rounds, 3, transcript)
+            .map_err(ProofVerifyError::OutputCheck)?;
+        if r_sumcheck.len() != proof.num_rounds {
+            return Err(ProofVerifyError::OutputCheck(SumcheckError::NumRounds {
+                expected: proof.num_rounds,
+                actual: r_sumcheck.len(),
+            }));
+        }
 
-        let program_io = preprocessing.program_io.as_ref().unwrap();
-        let memory_layout = &program_io.memory_layout;
+        let eq_eval = EqPolynomial::new(r_eq.to_vec()).evaluate(&r_sumcheck);
 
         let input_start_index =
             memory_address_to_witness_index(memory_layout.input_start, memory_layout);
-        let ram_start_index =
-            memory_address_to_witness_index(RAM_START_ADDRESS, memory_layout) as u64;
-        assert!(
-            ram_start_index.is_power_of_two(),
-            "ram_start_index must be a power of two"
-        );
-
-        let io_memory_size = ram_start_index as usize;
-        let log_io_memory_size = io_memory_size.log_2();
 
         let io_witness_range: Vec<_> = (0..io_memory_size)
             .map(|i| {
@@ -987,53 +1271,60 @@ where
             .product();
         io_witness_range_eval *= r_prod;
 
-        let mut v_io: Vec<u64> = vec![0; io_memory_size];
-        // Copy input bytes
-        let mut input_index =
-            memory_address_to_witness_index(memory_layout.input_start, memory_layout);
-        for chunk in program_io.inputs.chunks(4) {
-            let mut word = [0u8; 4];
-            for (i, byte) in chunk.iter().enumerate() {
-                word[i] = *byte;
-            }
-            let word = u32::from_le_bytes(word);
-            v_io[input_index] = word as u64;
-            input_index += 1;
-        }
-        // Copy output bytes
-        let mut output_index =
-            memory_address_to_witness_index(memory_layout.output_start, memory_layout);
-        for chunk in program_io.outputs.chunks(4) {
-            let mut word = [0u8; 4];
-            for (i, byte) in chunk.iter().enumerate() {
-                word[i] = *byte;
-            }
-            let word = u32::from_le_bytes(word);
-            v_io[output_index] = word as u64;
-            output_index += 1;
-        }
-        // Copy panic bit
-        v_io[memory_address_to_witness_index(memory_layout.panic, memory_layout)] =
-            program_io.panic as u64;
-        if !program_io.panic {
-            // Set termination bit
-            v_io[memory_address_to_witness_index(memory_layout.termination, memory_layout)] = 1;
-        }
-
+        let v_io = compute_v_io(program_io, memory_layout, io_memory_size);
         let mut v_io_eval = DensePolynomial::from_u64(&v_io)
             .evaluate(&r_sumcheck[(proof.num_rounds - log_io_memory_size)..]);
         v_io_eval *= r_prod;
 
-        assert_eq!(
-            eq_eval * io_witness_range_eval * (proof.opening - v_io_eval),
+        let Some(beta) = beta else {
+            check_output_claim(
+                sumcheck_claim,
+                eq_eval * io_witness_range_eval * (proof.opening - v_io_eval),
+            )?;
+
+            opening_accumulator.append(
+                &[&commitment.v_final],
+                r_sumcheck,
+                &[&proof.opening],
+                transcript,
+            );
+
+            return Ok(());
+        };
+
+        let input_opening = proof
+            .input_opening
+            .ok_or(ProofVerifyError::InputCommitmentShape)?;
+        let input_commitment = commitment
+            .v_input
+            .as_ref()
+            .ok_or(ProofVerifyError::InputCommitmentShape)?;
+        let input_end_index =
+            memory_address_to_witness_index(memory_layout.input_end, memory_layout);
+        let input_range: Vec<_> = (0..io_memory_size)
+            .map(|i| {
+                if i >= input_start_index && i < input_end_index {
+                    F::one()
+                } else {
+                    F::zero()
+                }
+            })
+            .collect();
+        let mut input_range_eval = DensePolynomial::new(input_range)
+            .evaluate(&r_sumcheck[(proof.num_rounds - log_io_memory_size)..]);
+        input_range_eval *= r_prod;
+
+        check_output_claim(
             sumcheck_claim,
-            "Output sumcheck check failed."
-        );
+            eq_eval
+                * (io_witness_range_eval * (proof.opening - v_io_eval - input_opening)
+                    + beta * (F::one() - input_range_eval) * input_opening),
+        )?;
 
         opening_accumulator.append(
-            &[&commitment.v_final],
+            &[&commitment.v_final, input_commitment],
             r_sumcheck,
-            &[&proof.opening],
+            &[&proof.opening, &input_opening],
             transcript,
         );
 
@@ -1041,6 +1332,11 @@ where
     }
 }
 
+fn check_output_claim<F: JoltField>(claimed: F, expected: F) -> Result<(), ProofVerifyError> {
+    Mismatch::check(claimed, expected)
+        .map_err(|mismatch| ProofVerifyError::OutputCheck(SumcheckError::FinalClaim(mismatch)))
+}
+
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct ReadWriteMemoryProof<F, PCS, ProofTranscript>
 where
@@ -1073,7 +1369,9 @@ where
         program_io: &JoltDevice,
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
-    ) -> Self {
+        grand_product_backend: GrandProductBackend,
+        progress: &ProgressReporter,
+    ) -> Result<Self, ProverError> {
         let memory_checking_proof = ReadWriteMemoryProof::prove_memory_checking(
             generators,
             preprocessing,
@@ -1081,6 +1379,7 @@ where
             polynomials,
             opening_accumulator,
             transcript,
+            grand_product_backend,
         );
 
         let output_proof = OutputSumcheckProof::prove_outputs(
@@ -1090,29 +1389,38 @@ where
             transcript,
         );
 
+        progress.stage(ProverStage::TimestampRangeCheck)?;
         let timestamp_validity_proof = TimestampValidityProof::prove(
             generators,
             &polynomials.timestamp_range_check,
             polynomials,
             opening_accumulator,
             transcript,
+            grand_product_backend,
         );
 
-        Self {
+        Ok(Self {
             memory_checking_proof,
             output_proof,
             timestamp_validity_proof,
-        }
+        })
     }
 
     pub fn verify(
         mut self,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         preprocessing: &ReadWriteMemoryPreprocessing,
         commitments: &JoltCommitments<PCS, ProofTranscript>,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
+        // `v_input` is committed to and opened if and only if the inputs are private.
+        if commitments.read_write_memory.v_input.is_some() != preprocessing.private_inputs
+            || self.memory_checking_proof.openings.v_input.is_some() != preprocessing.private_inputs
+            || self.output_proof.input_opening.is_some() != preprocessing.private_inputs
+        {
+            return Err(ProofVerifyError::InputCommitmentShape);
+        }
         ReadWriteMemoryProof::verify_memory_checking(
             preprocessing,
             generators,
@@ -1121,7 +1429,8 @@ where
             commitments,
             opening_accumulator,
             transcript,
-        )?;
+        )
+        .map_err(ProofVerifyError::ReadWriteMemoryChecking)?;
         OutputSumcheckProof::verify(
             &self.output_proof,
             preprocessing,
@@ -1142,12 +1451,84 @@ where
 #[cfg(test)]
 mod tests {
     use ark_bn254::Fr;
+    use ark_std::{test_rng, One};
 
     use super::*;
+    use crate::poly::commitment::mock::MockCommitScheme;
+    use crate::utils::transcript::KeccakTranscript;
+
+    type Scheme = MockCommitScheme<Fr, KeccakTranscript>;
 
     #[test]
     fn read_write_memory_stuff_ordering() {
-        let preprocessing = ReadWriteMemoryPreprocessing::preprocess(vec![]);
-        ReadWriteMemoryOpenings::<Fr>::test_ordering_consistency(&preprocessing);
+        for private_inputs in [false, true] {
+            let memory_layout = MemoryLayout {
+                private_inputs,
+                ..MemoryLayout::new(64, 64)
+            };
+            let preprocessing = ReadWriteMemoryPreprocessing::preprocess(vec![], &memory_layout);
+            ReadWriteMemoryOpenings::<Fr>::test_ordering_consistency(&preprocessing);
+        }
+    }
+
+    #[test]
+    fn v_init_bytecode_is_prefix_of_v_init() {
+        let memory_layout = MemoryLayout::new(64, 64);
+        let memory_init: Vec<(u64, u8)> = (0..40u64)
+            .map(|i| (RAM_START_ADDRESS + i, i as u8 + 1))
+            .collect();
+        let preprocessing = ReadWriteMemoryPreprocessing::preprocess(memory_init, &memory_layout);
+        let v_init_bytecode = preprocessing.v_init_bytecode::<Fr>();
+
+        let num_vars = preprocessing.bytecode_num_vars + 2;
+        let mut v_init = vec![0u64; num_vars.pow2()];
+        for (i, word) in preprocessing.bytecode_words.iter().enumerate() {
+            v_init[preprocessing.bytecode_start + i] = *word as u64;
+        }
+
+        let mut rng = test_rng();
+        let r: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+        let (r_prefix, r_bytecode) = r.split_at(2);
+        assert_eq!(
+            DensePolynomial::from_u64(&v_init).evaluate(&r),
+            (Fr::one() - r_prefix[0])
+                * (Fr::one() - r_prefix[1])
+                * v_init_bytecode.evaluate(r_bytecode)
+        );
+    }
+
+    #[test]
+    fn output_check_rejects_malformed_proofs() {
+        let verify = |preprocessing: &ReadWriteMemoryPreprocessing, num_rounds: usize| {
+            let proof = OutputSumcheckProof::<Fr, Scheme, KeccakTranscript> {
+                _pcs: PhantomData,
+                num_rounds,
+                sumcheck_proof: SumcheckInstanceProof::new(vec![]),
+                opening: Fr::zero(),
+                input_opening: None,
+            };
+            OutputSumcheckProof::verify(
+                &proof,
+                preprocessing,
+                &ReadWriteMemoryCommitments::<Scheme, KeccakTranscript>::default(),
+                &mut VerifierOpeningAccumulator::new(),
+                &mut KeccakTranscript::new(b"test"),
+            )
+        };
+
+        let mut preprocessing =
+            ReadWriteMemoryPreprocessing::preprocess(vec![], &MemoryLayout::new(64, 64));
+        assert!(matches!(
+            verify(&preprocessing, 20),
+            Err(ProofVerifyError::MissingProgramIO)
+        ));
+
+        preprocessing.program_io = Some(JoltDevice::new(64, 64));
+        for num_rounds in [0, usize::BITS as usize + 1, usize::MAX] {
+            assert!(matches!(
+                verify(&preprocessing, num_rounds),
+                Err(ProofVerifyError::OutputCheckNumRounds { .. })
+            ));
+        }
     }
 }
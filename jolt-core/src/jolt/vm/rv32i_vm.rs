@@ -9,7 +9,8 @@ use crate::jolt::subtable::right_is_zero::RightIsZeroSubtable;
 use crate::poly::commitment::hyperkzg::HyperKZG;
 use crate::r1cs::constraints::JoltRV32IMConstraints;
 use crate::r1cs::inputs::JoltR1CSInputs;
-use ark_bn254::{Bn254, Fr};
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use enum_dispatch::enum_dispatch;
 use rand::{prelude::StdRng, RngCore};
@@ -18,7 +19,7 @@ use std::any::TypeId;
 use strum::{EnumCount, IntoEnumIterator};
 use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
 
-use super::{Jolt, JoltCommitments, JoltProof};
+use super::{Jolt, JoltCommitments, JoltProof, JoltVerifierPreprocessing};
 use crate::jolt::instruction::{
     add::ADDInstruction, and::ANDInstruction, beq::BEQInstruction, bge::BGEInstruction,
     bgeu::BGEUInstruction, bne::BNEInstruction, mul::MULInstruction, mulhu::MULHUInstruction,
@@ -228,18 +229,53 @@ pub trait Serializable: CanonicalSerialize + CanonicalDeserialize + Sized {
 
 pub type ProofTranscript = KeccakTranscript;
 pub type PCS = HyperKZG<Bn254, ProofTranscript>;
+
+/// A proof with HyperKZG commitments over the pairing `P`: BN254 by default, or e.g.
+/// BLS12-381 for deployments tied to BLS12-381 ceremonies or to chains that only have
+/// BLS12-381 precompiles.
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
-pub struct JoltHyperKZGProof {
-    pub proof: RV32IJoltProof<Fr, PCS, ProofTranscript>,
-    pub commitments: JoltCommitments<PCS, ProofTranscript>,
+pub struct JoltHyperKZGProof<P: Pairing = Bn254>
+where
+    P::ScalarField: JoltField,
+{
+    pub proof: RV32IJoltProof<P::ScalarField, HyperKZG<P, ProofTranscript>, ProofTranscript>,
+    pub commitments: JoltCommitments<HyperKZG<P, ProofTranscript>, ProofTranscript>,
+}
+
+impl<P: Pairing> Serializable for JoltHyperKZGProof<P> where P::ScalarField: JoltField {}
+
+impl<P: Pairing> std::fmt::Debug for JoltHyperKZGProof<P>
+where
+    P::ScalarField: JoltField,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JoltHyperKZGProof")
+            .field("trace_length", &self.proof.trace_length)
+            .finish_non_exhaustive()
+    }
 }
 
-impl Serializable for JoltHyperKZGProof {}
+/// Verifier preprocessing for `JoltHyperKZGProof`s. `jolt build-wasm` writes this ahead of
+/// time, so the (wasm) verifier neither decodes the ELF nor runs the PCS setup.
+pub type JoltHyperKZGVerifierPreprocessing<P = Bn254> = JoltVerifierPreprocessing<
+    <P as Pairing>::ScalarField,
+    HyperKZG<P, ProofTranscript>,
+    ProofTranscript,
+>;
+
+impl<F, PCS, ProofTranscript> Serializable for JoltVerifierPreprocessing<F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+}
 
 // ==================== TEST ====================
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
+    use ark_bls12_381::Bls12_381;
     use ark_bn254::{Bn254, Fr, G1Projective};
 
     use std::collections::HashSet;
@@ -247,20 +283,34 @@ mod tests {
     use crate::field::JoltField;
     use crate::host;
     use crate::jolt::instruction::JoltInstruction;
-    use crate::jolt::vm::rv32i_vm::{Jolt, RV32IJoltVM, C, M};
+    use crate::jolt::vm::prover_config::{ProverConfig, ProverStage};
+    use crate::jolt::vm::rv32i_vm::{
+        Jolt, JoltHyperKZGVerifierPreprocessing, ProofTranscript, RV32IJoltProof, RV32IJoltVM,
+        Serializable, C, M, PCS,
+    };
+    use crate::jolt::vm::{JoltCommitments, JoltPreprocessing, JoltVerifierPreprocessing};
     use crate::poly::commitment::commitment_scheme::CommitmentScheme;
     use crate::poly::commitment::hyperkzg::HyperKZG;
     use crate::poly::commitment::hyrax::HyraxScheme;
-    use crate::poly::commitment::mock::MockCommitScheme;
+    use crate::poly::commitment::ligero::Ligero;
+    use crate::poly::commitment::mock::{BatchOpeningScheme, MockCommitScheme};
     use crate::poly::commitment::zeromorph::Zeromorph;
-    use crate::utils::transcript::{KeccakTranscript, Transcript};
-    use std::sync::Mutex;
+    use crate::subprotocols::grand_product::GrandProductBackend;
+    use crate::utils::cancellation::CancellationToken;
+    use crate::utils::errors::{ProofVerifyError, ProverError};
+    use crate::utils::recording_transcript::{RecordingTranscript, TranscriptLog};
+    use crate::utils::transcript::{KeccakTranscript, PoseidonTranscript, Transcript};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use common::rv_trace::MemoryLayout;
+    use itertools::Itertools;
+    use std::sync::{Arc, Mutex};
     use strum::{EnumCount, IntoEnumIterator};
 
     // If multiple tests try to read the same trace artifacts simultaneously, they will fail
     lazy_static::lazy_static! {
-        static ref FIB_FILE_LOCK: Mutex<()> = Mutex::new(());
-        static ref SHA3_FILE_LOCK: Mutex<()> = Mutex::new(());
+        pub(crate) static ref FIB_FILE_LOCK: Mutex<()> = Mutex::new(());
+        pub(crate) static ref SHA3_FILE_LOCK: Mutex<()> = Mutex::new(());
+        pub(crate) static ref MEMORY_OPS_FILE_LOCK: Mutex<()> = Mutex::new(());
     }
 
     fn test_instruction_set_subtables<PCS, ProofTranscript>()
@@ -298,6 +348,15 @@ mod tests {
     }
 
     fn fib_e2e<F, PCS, ProofTranscript>()
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<ProofTranscript, Field = F>,
+        ProofTranscript: Transcript,
+    {
+        fib_e2e_with_config::<F, PCS, ProofTranscript>(&ProverConfig::default());
+    }
+
+    fn fib_e2e_with_config<F, PCS, ProofTranscript>(config: &ProverConfig)
     where
         F: JoltField,
         PCS: CommitmentScheme<ProofTranscript, Field = F>,
@@ -310,7 +369,7 @@ mod tests {
         let (io_device, trace) = program.trace();
         drop(artifact_guard);
 
-        let preprocessing = RV32IJoltVM::preprocess(
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
             bytecode.clone(),
             io_device.memory_layout.clone(),
             memory_init,
@@ -323,9 +382,15 @@ mod tests {
                 io_device,
                 trace,
                 preprocessing.clone(),
-            );
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, proof, commitments, debug_info);
+                config,
+            )
+            .unwrap();
+        let verification_result = RV32IJoltVM::verify(
+            JoltVerifierPreprocessing::from(&preprocessing),
+            proof,
+            commitments,
+            debug_info,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -358,6 +423,441 @@ mod tests {
         fib_e2e::<Fr, HyperKZG<Bn254, KeccakTranscript>, KeccakTranscript>();
     }
 
+    #[test]
+    fn fib_e2e_hyperkzg_bls12_381() {
+        fib_e2e::<ark_bls12_381::Fr, HyperKZG<Bls12_381, KeccakTranscript>, KeccakTranscript>();
+    }
+
+    #[test]
+    fn fib_e2e_ligero() {
+        fib_e2e::<Fr, Ligero<Fr, KeccakTranscript>, KeccakTranscript>();
+    }
+
+    #[test]
+    fn fib_e2e_recording_transcript() {
+        type ProofTranscript = RecordingTranscript<KeccakTranscript>;
+        type Scheme = MockCommitScheme<Fr, ProofTranscript>;
+
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let ((proof, commitments, _), prover_log) = TranscriptLog::record(|| {
+            <RV32IJoltVM as Jolt<Fr, Scheme, C, M, ProofTranscript>>::prove(
+                io_device,
+                trace,
+                preprocessing.clone(),
+                &ProverConfig::default().with_debug_info(false),
+            )
+            .unwrap()
+        });
+        let mut proof_bytes = vec![];
+        proof.serialize_compressed(&mut proof_bytes).unwrap();
+        let mut commitment_bytes = vec![];
+        commitments
+            .serialize_compressed(&mut commitment_bytes)
+            .unwrap();
+
+        let verify = |tamper: fn(&mut RV32IJoltProof<Fr, Scheme, ProofTranscript>)| {
+            let mut proof = RV32IJoltProof::<Fr, Scheme, ProofTranscript>::deserialize_compressed(
+                &proof_bytes[..],
+            )
+            .unwrap();
+            tamper(&mut proof);
+            let commitments = JoltCommitments::<Scheme, ProofTranscript>::deserialize_compressed(
+                &commitment_bytes[..],
+            )
+            .unwrap();
+            TranscriptLog::record(|| {
+                RV32IJoltVM::verify(
+                    JoltVerifierPreprocessing::from(&preprocessing),
+                    proof,
+                    commitments,
+                    None,
+                )
+            })
+        };
+
+        let (result, verifier_log) = verify(|_| {});
+        assert!(result.is_ok());
+        assert_eq!(prover_log.first_divergence(&verifier_log), None);
+        let stages: HashSet<&str> = prover_log.events().iter().map(|e| e.stage).collect();
+        for stage in ["Preamble", "Commitments", "Spartan", "OpeningProof"] {
+            assert!(stages.contains(stage));
+        }
+
+        // The logs locate where the transcripts of an invalid proof diverge
+        let (result, verifier_log) = verify(|proof| proof.program_io.outputs.push(1));
+        assert!(result.is_err());
+        let divergence = prover_log.first_divergence(&verifier_log).unwrap();
+        let event = &verifier_log.events()[divergence];
+        assert_eq!((event.stage, event.operation), ("Preamble", "append_bytes"));
+    }
+
+    #[test]
+    fn fib_e2e_hyperkzg_poseidon() {
+        fib_e2e::<Fr, HyperKZG<Bn254, PoseidonTranscript>, PoseidonTranscript>();
+    }
+
+    #[test]
+    fn fib_e2e_mock_batch_openings() {
+        type Scheme = BatchOpeningScheme<MockCommitScheme<Fr, KeccakTranscript>>;
+        fib_e2e::<Fr, Scheme, KeccakTranscript>();
+    }
+
+    #[test]
+    fn fib_e2e_hyperkzg_batch_openings() {
+        fib_e2e::<Fr, BatchOpeningScheme<HyperKZG<Bn254, KeccakTranscript>>, KeccakTranscript>();
+    }
+
+    #[test]
+    fn fib_e2e_hyperkzg_quark_grand_products() {
+        let config = ProverConfig::default()
+            .with_grand_product(GrandProductBackend::QuarkHybrid(Default::default()));
+        fib_e2e_with_config::<Fr, HyperKZG<Bn254, KeccakTranscript>, KeccakTranscript>(&config);
+    }
+
+    #[test]
+    fn fib_e2e_mock_dedicated_thread_pool() {
+        let config = ProverConfig::default()
+            .with_num_threads(2)
+            .with_progress(true);
+        fib_e2e_with_config::<Fr, MockCommitScheme<Fr, KeccakTranscript>, KeccakTranscript>(
+            &config,
+        );
+    }
+
+    #[test]
+    fn fib_proof_reproducible_across_runs_and_thread_counts() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
+            bytecode.clone(),
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let prove_serialized = |config: &ProverConfig| {
+            let (proof, commitments, _) =
+                <RV32IJoltVM as Jolt<Fr, PCS, C, M, ProofTranscript>>::prove(
+                    io_device.clone(),
+                    trace.clone(),
+                    preprocessing.clone(),
+                    config,
+                )
+                .unwrap();
+            let mut bytes = vec![];
+            proof.serialize_compressed(&mut bytes).unwrap();
+            commitments.serialize_compressed(&mut bytes).unwrap();
+            bytes
+        };
+
+        let config = ProverConfig::default().with_debug_info(false);
+        let expected = prove_serialized(&config);
+        assert_eq!(prove_serialized(&config), expected);
+        for num_threads in [1, 3, 8] {
+            assert_eq!(
+                prove_serialized(&config.clone().with_num_threads(num_threads)),
+                expected,
+                "proof differs with {num_threads} threads"
+            );
+        }
+    }
+
+    #[test]
+    fn fib_e2e_mock_progress() {
+        let reports = Arc::new(Mutex::new(vec![]));
+        let reports_cp = reports.clone();
+        let config = ProverConfig::default()
+            .with_progress_callback(move |progress| reports_cp.lock().unwrap().push(progress));
+        fib_e2e_with_config::<Fr, MockCommitScheme<Fr, KeccakTranscript>, KeccakTranscript>(
+            &config,
+        );
+
+        let reports = reports.lock().unwrap();
+        let stages: Vec<_> = reports.iter().map(|progress| progress.stage).collect();
+        assert_eq!(
+            stages,
+            vec![
+                ProverStage::WitnessGeneration,
+                ProverStage::Commitments,
+                ProverStage::BytecodeMemoryChecking,
+                ProverStage::InstructionLookups,
+                ProverStage::ReadWriteMemoryChecking,
+                ProverStage::TimestampRangeCheck,
+                ProverStage::Spartan,
+                ProverStage::OpeningProof,
+                ProverStage::Done,
+            ]
+        );
+        for (prev, next) in reports.iter().tuple_windows() {
+            assert!(prev.elapsed <= next.elapsed);
+            assert!(prev.fraction_complete < next.fraction_complete);
+        }
+        assert_eq!(reports.last().unwrap().fraction_complete, 1.0);
+    }
+
+    #[test]
+    fn fib_e2e_mock_cancelled() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
+            bytecode.clone(),
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+
+        // Cancel from within the proof, so that it is aborted at the next stage boundary.
+        let token = CancellationToken::new();
+        let token_cp = token.clone();
+        let last_stage = Arc::new(Mutex::new(None));
+        let last_stage_cp = last_stage.clone();
+        let config = ProverConfig::default()
+            .with_cancellation_token(token.clone())
+            .with_progress_callback(move |progress| {
+                *last_stage_cp.lock().unwrap() = Some(progress.stage);
+                if progress.stage == ProverStage::InstructionLookups {
+                    token_cp.cancel();
+                }
+            });
+        let result = <RV32IJoltVM as Jolt<
+            Fr,
+            MockCommitScheme<Fr, KeccakTranscript>,
+            C,
+            M,
+            KeccakTranscript,
+        >>::prove(io_device, trace, preprocessing, &config);
+
+        assert!(matches!(result, Err(ProverError::Cancelled)));
+        assert!(token.is_cancelled());
+        assert_eq!(
+            *last_stage.lock().unwrap(),
+            Some(ProverStage::InstructionLookups)
+        );
+    }
+
+    #[test]
+    fn fib_e2e_hyperkzg_verifier_preprocessing() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let (proof, commitments, _) = <RV32IJoltVM as Jolt<Fr, PCS, C, M, ProofTranscript>>::prove(
+            io_device,
+            trace,
+            preprocessing.clone(),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+
+        let verifier_preprocessing = JoltHyperKZGVerifierPreprocessing::from(&preprocessing);
+        let serialized = verifier_preprocessing.serialize_to_bytes().unwrap();
+        // The verifier preprocessing excludes the SRS and materialized subtables
+        assert!(serialized.len() < preprocessing.compressed_size() / 100);
+
+        let deserialized =
+            JoltHyperKZGVerifierPreprocessing::deserialize_from_bytes(&serialized).unwrap();
+        assert_eq!(deserialized.serialize_to_bytes().unwrap(), serialized);
+        let verification_result = RV32IJoltVM::verify(deserialized, proof, commitments, None);
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn verifier_preprocessing_size_is_independent_of_program_length() {
+        let fib_guard = FIB_FILE_LOCK.lock().unwrap();
+        let (fib_bytecode, fib_memory_init) = host::Program::new("fibonacci-guest").decode();
+        drop(fib_guard);
+        let sha3_guard = SHA3_FILE_LOCK.lock().unwrap();
+        let (sha3_bytecode, sha3_memory_init) = host::Program::new("sha3-guest").decode();
+        drop(sha3_guard);
+        assert!(sha3_bytecode.len() > fib_bytecode.len());
+
+        let memory_layout = MemoryLayout::new(4096, 4096);
+        let fib_preprocessing: JoltPreprocessing<C, Fr, PCS, ProofTranscript> =
+            RV32IJoltVM::insecure_preprocess(
+                fib_bytecode,
+                memory_layout.clone(),
+                fib_memory_init,
+                1 << 20,
+                1 << 20,
+                1 << 20,
+            );
+        let sha3_preprocessing: JoltPreprocessing<C, Fr, PCS, ProofTranscript> =
+            RV32IJoltVM::preprocess_with_generators(
+                sha3_bytecode,
+                memory_layout,
+                sha3_memory_init,
+                fib_preprocessing.generators.clone(),
+            );
+
+        let fib_verifier_preprocessing =
+            JoltHyperKZGVerifierPreprocessing::from(&fib_preprocessing);
+        let sha3_verifier_preprocessing =
+            JoltHyperKZGVerifierPreprocessing::from(&sha3_preprocessing);
+        assert_eq!(
+            fib_verifier_preprocessing.compressed_size(),
+            sha3_verifier_preprocessing.compressed_size()
+        );
+    }
+
+    #[test]
+    fn fib_e2e_hyperkzg_serialized_preprocessing() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing: JoltPreprocessing<C, Fr, PCS, ProofTranscript> =
+            RV32IJoltVM::insecure_preprocess(
+                bytecode,
+                io_device.memory_layout.clone(),
+                memory_init,
+                1 << 20,
+                1 << 20,
+                1 << 20,
+            );
+        let mut serialized = Vec::new();
+        preprocessing
+            .serialize_uncompressed(&mut serialized)
+            .unwrap();
+        let deserialized = JoltPreprocessing::deserialize_uncompressed(&serialized[..]).unwrap();
+
+        let (proof, commitments, debug_info) =
+            <RV32IJoltVM as Jolt<Fr, PCS, C, M, ProofTranscript>>::prove(
+                io_device,
+                trace,
+                deserialized,
+                &ProverConfig::default(),
+            )
+            .unwrap();
+        let verification_result = RV32IJoltVM::verify(
+            JoltVerifierPreprocessing::from(&preprocessing),
+            proof,
+            commitments,
+            debug_info,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+    }
+
+    #[test]
+    fn fib_e2e_hyperkzg_private_inputs() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        program.set_private_inputs(true);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing: JoltPreprocessing<C, Fr, PCS, ProofTranscript> =
+            RV32IJoltVM::insecure_preprocess(
+                bytecode,
+                io_device.memory_layout.clone(),
+                memory_init,
+                1 << 20,
+                1 << 20,
+                1 << 20,
+            );
+        let (proof, commitments, _) = <RV32IJoltVM as Jolt<Fr, PCS, C, M, ProofTranscript>>::prove(
+            io_device,
+            trace,
+            preprocessing.clone(),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+        // The inputs are excluded from the public statement
+        assert!(proof.program_io.inputs.is_empty());
+        assert!(commitments.read_write_memory.v_input.is_some());
+
+        let mut proof_bytes = Vec::new();
+        proof.serialize_compressed(&mut proof_bytes).unwrap();
+        let mut commitment_bytes = Vec::new();
+        commitments
+            .serialize_compressed(&mut commitment_bytes)
+            .unwrap();
+        let verify = |tamper: fn(&mut JoltCommitments<PCS, ProofTranscript>)| {
+            let proof = RV32IJoltProof::<Fr, PCS, ProofTranscript>::deserialize_compressed(
+                &proof_bytes[..],
+            )
+            .unwrap();
+            let mut commitments = JoltCommitments::<PCS, ProofTranscript>::deserialize_compressed(
+                &commitment_bytes[..],
+            )
+            .unwrap();
+            tamper(&mut commitments);
+            RV32IJoltVM::verify(
+                JoltVerifierPreprocessing::from(&preprocessing),
+                proof,
+                commitments,
+                None,
+            )
+        };
+
+        let verification_result = verify(|_| {});
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+        // A commitment to some other input must be rejected
+        assert!(verify(|commitments| {
+            commitments.read_write_memory.v_input =
+                Some(commitments.read_write_memory.v_final.clone());
+        })
+        .is_err());
+        assert!(matches!(
+            verify(|commitments| commitments.read_write_memory.v_input = None),
+            Err(ProofVerifyError::InputCommitmentShape)
+        ));
+    }
+
     // TODO(sragss): Finish Binius.
     // #[test]
     // fn fib_e2e_binius() {
@@ -375,7 +875,7 @@ mod tests {
         let (bytecode, memory_init) = program.decode();
         let (io_device, trace) = program.trace();
 
-        let preprocessing = RV32IJoltVM::preprocess(
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
             bytecode.clone(),
             io_device.memory_layout.clone(),
             memory_init,
@@ -383,16 +883,25 @@ mod tests {
             1 << 20,
             1 << 20,
         );
-        let (jolt_proof, jolt_commitments, debug_info) =
-            <RV32IJoltVM as Jolt<
-                _,
-                HyraxScheme<G1Projective, KeccakTranscript>,
-                C,
-                M,
-                KeccakTranscript,
-            >>::prove(io_device, trace, preprocessing.clone());
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
+        let (jolt_proof, jolt_commitments, debug_info) = <RV32IJoltVM as Jolt<
+            _,
+            HyraxScheme<G1Projective, KeccakTranscript>,
+            C,
+            M,
+            KeccakTranscript,
+        >>::prove(
+            io_device,
+            trace,
+            preprocessing.clone(),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+        let verification_result = RV32IJoltVM::verify(
+            JoltVerifierPreprocessing::from(&preprocessing),
+            jolt_proof,
+            jolt_commitments,
+            debug_info,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -411,7 +920,7 @@ mod tests {
         let (io_device, trace) = program.trace();
         drop(guard);
 
-        let preprocessing = RV32IJoltVM::preprocess(
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
             bytecode.clone(),
             io_device.memory_layout.clone(),
             memory_init,
@@ -419,17 +928,26 @@ mod tests {
             1 << 20,
             1 << 20,
         );
-        let (jolt_proof, jolt_commitments, debug_info) =
-            <RV32IJoltVM as Jolt<
-                _,
-                HyraxScheme<G1Projective, KeccakTranscript>,
-                C,
-                M,
-                KeccakTranscript,
-            >>::prove(io_device, trace, preprocessing.clone());
+        let (jolt_proof, jolt_commitments, debug_info) = <RV32IJoltVM as Jolt<
+            _,
+            HyraxScheme<G1Projective, KeccakTranscript>,
+            C,
+            M,
+            KeccakTranscript,
+        >>::prove(
+            io_device,
+            trace,
+            preprocessing.clone(),
+            &ProverConfig::default(),
+        )
+        .unwrap();
 
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
+        let verification_result = RV32IJoltVM::verify(
+            JoltVerifierPreprocessing::from(&preprocessing),
+            jolt_proof,
+            jolt_commitments,
+            debug_info,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -447,7 +965,7 @@ mod tests {
         let (io_device, trace) = program.trace();
         drop(guard);
 
-        let preprocessing = RV32IJoltVM::preprocess(
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
             bytecode.clone(),
             io_device.memory_layout.clone(),
             memory_init,
@@ -462,11 +980,19 @@ mod tests {
             M,
             KeccakTranscript,
         >>::prove(
-            io_device, trace, preprocessing.clone()
-        );
+            io_device,
+            trace,
+            preprocessing.clone(),
+            &ProverConfig::default(),
+        )
+        .unwrap();
 
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
+        let verification_result = RV32IJoltVM::verify(
+            JoltVerifierPreprocessing::from(&preprocessing),
+            jolt_proof,
+            jolt_commitments,
+            debug_info,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -484,7 +1010,7 @@ mod tests {
         let (io_device, trace) = program.trace();
         drop(guard);
 
-        let preprocessing = RV32IJoltVM::preprocess(
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
             bytecode.clone(),
             io_device.memory_layout.clone(),
             memory_init,
@@ -499,11 +1025,19 @@ mod tests {
             M,
             KeccakTranscript,
         >>::prove(
-            io_device, trace, preprocessing.clone()
-        );
+            io_device,
+            trace,
+            preprocessing.clone(),
+            &ProverConfig::default(),
+        )
+        .unwrap();
 
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
+        let verification_result = RV32IJoltVM::verify(
+            JoltVerifierPreprocessing::from(&preprocessing),
+            jolt_proof,
+            jolt_commitments,
+            debug_info,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -513,11 +1047,13 @@ mod tests {
 
     #[test]
     fn memory_ops_e2e_hyperkzg() {
+        let guard = MEMORY_OPS_FILE_LOCK.lock().unwrap();
         let mut program = host::Program::new("memory-ops-guest");
         let (bytecode, memory_init) = program.decode();
         let (io_device, trace) = program.trace();
+        drop(guard);
 
-        let preprocessing = RV32IJoltVM::preprocess(
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
             bytecode.clone(),
             io_device.memory_layout.clone(),
             memory_init,
@@ -532,11 +1068,19 @@ mod tests {
             M,
             KeccakTranscript,
         >>::prove(
-            io_device, trace, preprocessing.clone()
-        );
+            io_device,
+            trace,
+            preprocessing.clone(),
+            &ProverConfig::default(),
+        )
+        .unwrap();
 
-        let verification_result =
-            RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
+        let verification_result = RV32IJoltVM::verify(
+            JoltVerifierPreprocessing::from(&preprocessing),
+            jolt_proof,
+            jolt_commitments,
+            debug_info,
+        );
         assert!(
             verification_result.is_ok(),
             "Verification failed with error: {:?}",
@@ -556,7 +1100,7 @@ mod tests {
         io_device.outputs[0] = 0; // change the output to 0
         drop(artifact_guard);
 
-        let preprocessing = RV32IJoltVM::preprocess(
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
             bytecode.clone(),
             io_device.memory_layout.clone(),
             memory_init,
@@ -571,10 +1115,18 @@ mod tests {
             M,
             KeccakTranscript,
         >>::prove(
-            io_device, trace, preprocessing.clone()
+            io_device,
+            trace,
+            preprocessing.clone(),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+        let _verification_result = RV32IJoltVM::verify(
+            JoltVerifierPreprocessing::from(&preprocessing),
+            proof,
+            commitments,
+            debug_info,
         );
-        let _verification_result =
-            RV32IJoltVM::verify(preprocessing, proof, commitments, debug_info);
     }
 
     #[test]
@@ -595,7 +1147,7 @@ mod tests {
         io_device.memory_layout.termination = io_device.memory_layout.input_start;
 
         // Since the preprocessing is done with the original memory layout, the verifier should fail
-        let preprocessing = RV32IJoltVM::preprocess(
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
             bytecode.clone(),
             memory_layout,
             memory_init,
@@ -610,9 +1162,17 @@ mod tests {
             M,
             KeccakTranscript,
         >>::prove(
-            io_device, trace, preprocessing.clone()
+            io_device,
+            trace,
+            preprocessing.clone(),
+            &ProverConfig::default(),
+        )
+        .unwrap();
+        let _verification_result = RV32IJoltVM::verify(
+            JoltVerifierPreprocessing::from(&preprocessing),
+            proof,
+            commitments,
+            debug_info,
         );
-        let _verification_result =
-            RV32IJoltVM::verify(preprocessing, proof, commitments, debug_info);
     }
 }
@@ -0,0 +1,527 @@
+//! Soundness regression tests: a valid proof is tampered with one field at a time, and
+//! `Jolt::verify` must reject every tampered proof.
+//!
+//! The suite runs against `MockCommitScheme` by default. The HyperKZG variants take
+//! considerably longer and are ignored; run them with `cargo test tamper -- --ignored`.
+
+use ark_bn254::{Bn254, Fr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::One;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use super::prover_config::ProverConfig;
+use super::rv32i_vm::tests::{FIB_FILE_LOCK, MEMORY_OPS_FILE_LOCK, SHA3_FILE_LOCK};
+use super::rv32i_vm::{RV32IJoltProof, RV32IJoltVM, C, M};
+use super::{Jolt, JoltCommitments, JoltVerifierPreprocessing};
+use crate::field::JoltField;
+use crate::host;
+use crate::lasso::memory_checking::{
+    ExogenousOpenings, MemoryCheckingProof, MultisetHashes, StructuredPolynomialData,
+};
+use crate::poly::commitment::commitment_scheme::AdditivelyHomomorphic;
+use crate::poly::commitment::hyperkzg::HyperKZG;
+use crate::poly::commitment::mock::MockCommitScheme;
+use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::unipoly::CompressedUniPoly;
+use crate::subprotocols::grand_product::BatchedGrandProductProof;
+use crate::utils::transcript::KeccakTranscript;
+
+type Proof<PCS> = RV32IJoltProof<Fr, PCS, KeccakTranscript>;
+type Commitments<PCS> = JoltCommitments<PCS, KeccakTranscript>;
+
+/// The kinds of proof data that the suite tampers with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+enum Target {
+    SumcheckCoefficient,
+    OpeningClaim,
+    Commitment,
+    GrandProductClaim,
+    ProgramOutput,
+    PanicBit,
+    TraceLength,
+}
+
+/// A valid proof for some guest program, kept serialized so that each tampering can
+/// start from a fresh copy.
+struct ProofFixture<PCS: AdditivelyHomomorphic<KeccakTranscript, Field = Fr>> {
+    preprocessing: JoltVerifierPreprocessing<Fr, PCS, KeccakTranscript>,
+    proof: Vec<u8>,
+    commitments: Vec<u8>,
+    /// Commitment to a fixed nonzero polynomial, added to a commitment to tamper with it.
+    commitment_delta: PCS::Commitment,
+}
+
+impl<PCS: AdditivelyHomomorphic<KeccakTranscript, Field = Fr>> ProofFixture<PCS> {
+    fn new(mut program: host::Program, artifact_lock: &Mutex<()>) -> Self {
+        let artifact_guard = artifact_lock.lock().unwrap();
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing = RV32IJoltVM::insecure_preprocess(
+            bytecode,
+            io_device.memory_layout.clone(),
+            memory_init,
+            1 << 20,
+            1 << 20,
+            1 << 20,
+        );
+        let (proof, commitments, _) =
+            <RV32IJoltVM as Jolt<Fr, PCS, C, M, KeccakTranscript>>::prove(
+                io_device,
+                trace,
+                preprocessing.clone(),
+                &ProverConfig::default().with_debug_info(false),
+            )
+            .unwrap();
+
+        let mut serialized_proof = vec![];
+        proof.serialize_uncompressed(&mut serialized_proof).unwrap();
+        let mut serialized_commitments = vec![];
+        commitments
+            .serialize_uncompressed(&mut serialized_commitments)
+            .unwrap();
+        let commitment_delta = PCS::commit(
+            &DensePolynomial::new(vec![Fr::one(), Fr::from_u64(2).unwrap()]),
+            &preprocessing.generators,
+        );
+
+        Self {
+            preprocessing: JoltVerifierPreprocessing::from(&preprocessing),
+            proof: serialized_proof,
+            commitments: serialized_commitments,
+            commitment_delta,
+        }
+    }
+
+    fn proof(&self) -> Proof<PCS> {
+        Proof::<PCS>::deserialize_uncompressed_unchecked(&self.proof[..]).unwrap()
+    }
+
+    fn commitments(&self) -> Commitments<PCS> {
+        Commitments::<PCS>::deserialize_uncompressed_unchecked(&self.commitments[..]).unwrap()
+    }
+
+    /// Whether `Jolt::verify` accepts the given proof. The verifier must reject malformed
+    /// proofs with an error, so a panic fails the test.
+    fn verifies(&self, proof: Proof<PCS>, commitments: Commitments<PCS>) -> bool {
+        RV32IJoltVM::verify(self.preprocessing.clone(), proof, commitments, None).is_ok()
+    }
+
+    /// Applies the `index`-th tampering of the given target, returning its description,
+    /// or `None` if `index` is out of range.
+    fn tamper(
+        &self,
+        target: Target,
+        index: usize,
+        proof: &mut Proof<PCS>,
+        commitments: &mut Commitments<PCS>,
+    ) -> Option<String> {
+        let mut tamperer = Tamperer {
+            target,
+            index,
+            num_sites: 0,
+            tampered: None,
+            commitment_delta: &self.commitment_delta,
+        };
+        tamperer.proof(proof);
+        tamperer.commitments(commitments);
+        tamperer.tampered
+    }
+}
+
+/// Number of interior elements of a vector that are tampered with, besides the first and
+/// last.
+const NUM_INTERIOR_SAMPLES: usize = 3;
+
+/// Mutating every element of a long vector would make the suite slow, so only the first
+/// and last indices are tampered with, along with a few interior indices. The interior
+/// indices are sampled with a seed derived from the length, so that every walk over a
+/// proof visits the same sites.
+fn sampled_indices(len: usize) -> BTreeSet<usize> {
+    let mut indices: BTreeSet<usize> = [0, len.saturating_sub(1)].into();
+    if len > 2 {
+        let mut rng = ChaCha20Rng::seed_from_u64(len as u64);
+        for _ in 0..NUM_INTERIOR_SAMPLES {
+            indices.insert(1 + rng.next_u64() as usize % (len - 2));
+        }
+    }
+    indices
+}
+
+fn sampled<T>(items: Vec<T>) -> impl Iterator<Item = (usize, T)> {
+    let indices = sampled_indices(items.len());
+    items
+        .into_iter()
+        .enumerate()
+        .filter(move |(i, _)| indices.contains(i))
+}
+
+/// Walks the tampering sites of a proof and its commitments in a fixed order, and
+/// tampers with the `index`-th site of the given target.
+struct Tamperer<'a, PCS: AdditivelyHomomorphic<KeccakTranscript, Field = Fr>> {
+    target: Target,
+    index: usize,
+    /// Number of sites of `target` visited so far.
+    num_sites: usize,
+    tampered: Option<String>,
+    commitment_delta: &'a PCS::Commitment,
+}
+
+impl<'a, PCS: AdditivelyHomomorphic<KeccakTranscript, Field = Fr>> Tamperer<'a, PCS> {
+    /// Visits a site, returning whether it should be tampered with.
+    fn site(&mut self, target: Target, description: String) -> bool {
+        if target != self.target {
+            return false;
+        }
+        let selected = self.num_sites == self.index;
+        if selected {
+            self.tampered = Some(description);
+        }
+        self.num_sites += 1;
+        selected
+    }
+
+    fn field_element(&mut self, target: Target, description: String, value: &mut Fr) {
+        if self.site(target, description) {
+            *value += Fr::one();
+        }
+    }
+
+    fn sumcheck(&mut self, name: &str, rounds: Vec<&mut CompressedUniPoly<Fr>>) {
+        for (round, poly) in sampled(rounds) {
+            self.field_element(
+                Target::SumcheckCoefficient,
+                format!("{name} sumcheck round {round}"),
+                &mut poly.coeffs_except_linear_term[0],
+            );
+        }
+    }
+
+    fn openings(&mut self, name: &str, openings: Vec<&mut Fr>) {
+        for (i, opening) in sampled(openings) {
+            self.field_element(Target::OpeningClaim, format!("{name} opening {i}"), opening);
+        }
+    }
+
+    fn multiset_hashes(&mut self, name: &str, hashes: &mut MultisetHashes<Fr>) {
+        for (kind, hashes) in [
+            ("read", &mut hashes.read_hashes),
+            ("write", &mut hashes.write_hashes),
+            ("init", &mut hashes.init_hashes),
+            ("final", &mut hashes.final_hashes),
+        ] {
+            if let Some(hash) = hashes.first_mut() {
+                self.field_element(
+                    Target::GrandProductClaim,
+                    format!("{name} {kind} hash"),
+                    hash,
+                );
+            }
+        }
+    }
+
+    fn grand_product(
+        &mut self,
+        name: &str,
+        proof: &mut BatchedGrandProductProof<PCS, KeccakTranscript>,
+    ) {
+        let sampled_layers = sampled_indices(proof.gkr_layers.len());
+        let mut rounds = vec![];
+        for (i, layer) in proof.gkr_layers.iter_mut().enumerate() {
+            if sampled_layers.contains(&i) {
+                self.field_element(
+                    Target::GrandProductClaim,
+                    format!("{name} grand product layer {i} left claim"),
+                    &mut layer.left_claim,
+                );
+                self.field_element(
+                    Target::GrandProductClaim,
+                    format!("{name} grand product layer {i} right claim"),
+                    &mut layer.right_claim,
+                );
+            }
+            rounds.extend(layer.proof.compressed_polys.iter_mut());
+        }
+        self.sumcheck(&format!("{name} grand product"), rounds);
+    }
+
+    fn memory_checking<Openings, OtherOpenings>(
+        &mut self,
+        name: &str,
+        proof: &mut MemoryCheckingProof<Fr, PCS, Openings, OtherOpenings, KeccakTranscript>,
+    ) where
+        Openings: StructuredPolynomialData<Fr> + Sync + CanonicalSerialize + CanonicalDeserialize,
+        OtherOpenings: ExogenousOpenings<Fr> + Sync,
+    {
+        self.multiset_hashes(name, &mut proof.multiset_hashes);
+        self.grand_product(
+            &format!("{name} read-write"),
+            &mut proof.read_write_grand_product,
+        );
+        self.grand_product(
+            &format!("{name} init-final"),
+            &mut proof.init_final_grand_product,
+        );
+        self.openings(
+            &format!("{name} read-write"),
+            proof.openings.read_write_values_mut(),
+        );
+        self.openings(
+            &format!("{name} init-final"),
+            proof.openings.init_final_values_mut(),
+        );
+        self.openings(
+            &format!("{name} exogenous"),
+            proof.exogenous_openings.openings_mut(),
+        );
+    }
+
+    fn proof(&mut self, proof: &mut Proof<PCS>) {
+        self.memory_checking("bytecode", &mut proof.bytecode);
+
+        let primary_sumcheck = &mut proof.instruction_lookups.primary_sumcheck;
+        self.sumcheck(
+            "instruction lookups primary",
+            primary_sumcheck
+                .sumcheck_proof
+                .compressed_polys
+                .iter_mut()
+                .collect(),
+        );
+        let primary_openings = &mut primary_sumcheck.openings;
+        self.openings(
+            "instruction lookups E_poly",
+            primary_openings.E_poly_openings.iter_mut().collect(),
+        );
+        self.openings(
+            "instruction lookups flag",
+            primary_openings.flag_openings.iter_mut().collect(),
+        );
+        self.openings(
+            "instruction lookups lookup_outputs",
+            vec![&mut primary_openings.lookup_outputs_opening],
+        );
+        self.memory_checking(
+            "instruction lookups",
+            &mut proof.instruction_lookups.memory_checking,
+        );
+
+        let read_write_memory = &mut proof.read_write_memory;
+        self.memory_checking(
+            "read-write memory",
+            &mut read_write_memory.memory_checking_proof,
+        );
+        let timestamp_validity_proof = &mut read_write_memory.timestamp_validity_proof;
+        self.multiset_hashes(
+            "timestamp range check",
+            &mut timestamp_validity_proof.multiset_hashes,
+        );
+        self.grand_product(
+            "timestamp range check",
+            &mut timestamp_validity_proof.batched_grand_product,
+        );
+        self.openings(
+            "timestamp range check",
+            timestamp_validity_proof.openings.read_write_values_mut(),
+        );
+        self.openings(
+            "timestamp range check read timestamp",
+            timestamp_validity_proof
+                .exogenous_openings
+                .iter_mut()
+                .collect(),
+        );
+        let output_proof = &mut read_write_memory.output_proof;
+        self.sumcheck(
+            "output check",
+            output_proof
+                .sumcheck_proof
+                .compressed_polys
+                .iter_mut()
+                .collect(),
+        );
+        self.openings(
+            "output check",
+            [
+                Some(&mut output_proof.opening),
+                output_proof.input_opening.as_mut(),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        );
+
+        let r1cs = &mut proof.r1cs;
+        self.sumcheck(
+            "Spartan outer",
+            r1cs.outer_sumcheck_proof
+                .compressed_polys
+                .iter_mut()
+                .collect(),
+        );
+        let (claim_Az, claim_Bz, claim_Cz) = &mut r1cs.outer_sumcheck_claims;
+        self.openings("Spartan Az/Bz/Cz", vec![claim_Az, claim_Bz, claim_Cz]);
+        self.sumcheck(
+            "Spartan inner",
+            r1cs.inner_sumcheck_proof
+                .compressed_polys
+                .iter_mut()
+                .collect(),
+        );
+        self.openings(
+            "Spartan witness",
+            r1cs.claimed_witness_evals.iter_mut().collect(),
+        );
+
+        let opening_proof = &mut proof.opening_proof;
+        self.sumcheck(
+            "batched opening",
+            opening_proof
+                .sumcheck_proof
+                .compressed_polys
+                .iter_mut()
+                .collect(),
+        );
+        self.openings(
+            "batched opening",
+            opening_proof.sumcheck_claims.iter_mut().collect(),
+        );
+
+        let program_io = &mut proof.program_io;
+        if !program_io.outputs.is_empty()
+            && self.site(Target::ProgramOutput, "first output byte".to_string())
+        {
+            program_io.outputs[0] ^= 1;
+        }
+        if self.site(Target::ProgramOutput, "appended output byte".to_string()) {
+            program_io.outputs.push(1);
+        }
+        if self.site(Target::PanicBit, "panic bit".to_string()) {
+            program_io.panic = !program_io.panic;
+        }
+
+        let trace_length = proof.trace_length;
+        for tampered_length in [trace_length + 1, trace_length - 1, 2 * trace_length] {
+            if self.site(
+                Target::TraceLength,
+                format!("trace length ({trace_length} -> {tampered_length})"),
+            ) {
+                proof.trace_length = tampered_length;
+            }
+        }
+    }
+
+    fn commitment(&mut self, description: String, commitment: &mut PCS::Commitment) {
+        if self.site(Target::Commitment, description) {
+            *commitment = PCS::combine_commitments(
+                &[&*commitment, self.commitment_delta],
+                &[Fr::one(), Fr::one()],
+            );
+        }
+    }
+
+    fn component_commitments<T>(&mut self, name: &str, commitments: &mut T)
+    where
+        T: StructuredPolynomialData<PCS::Commitment>,
+    {
+        for (i, commitment) in sampled(commitments.read_write_values_mut()) {
+            self.commitment(format!("{name} read-write commitment {i}"), commitment);
+        }
+        for (i, commitment) in sampled(commitments.init_final_values_mut()) {
+            self.commitment(format!("{name} init-final commitment {i}"), commitment);
+        }
+    }
+
+    fn commitments(&mut self, commitments: &mut Commitments<PCS>) {
+        self.component_commitments("bytecode", &mut commitments.bytecode);
+        self.component_commitments("read-write memory", &mut commitments.read_write_memory);
+        self.component_commitments("instruction lookups", &mut commitments.instruction_lookups);
+        self.component_commitments(
+            "timestamp range check",
+            &mut commitments.timestamp_range_check,
+        );
+        self.component_commitments("R1CS", &mut commitments.r1cs);
+    }
+}
+
+fn assert_tampering_rejected<PCS: AdditivelyHomomorphic<KeccakTranscript, Field = Fr>>(
+    fixture: &ProofFixture<PCS>,
+) {
+    assert!(
+        fixture.verifies(fixture.proof(), fixture.commitments()),
+        "untampered proof should verify"
+    );
+
+    for target in Target::iter() {
+        let mut num_tamperings = 0;
+        loop {
+            let mut proof = fixture.proof();
+            let mut commitments = fixture.commitments();
+            let Some(description) =
+                fixture.tamper(target, num_tamperings, &mut proof, &mut commitments)
+            else {
+                break;
+            };
+            assert!(
+                !fixture.verifies(proof, commitments),
+                "verifier accepted a proof with tampered {description}"
+            );
+            num_tamperings += 1;
+        }
+        assert!(num_tamperings > 0, "no {target:?} to tamper with");
+    }
+}
+
+fn fib_fixture<PCS: AdditivelyHomomorphic<KeccakTranscript, Field = Fr>>() -> ProofFixture<PCS> {
+    let mut program = host::Program::new("fibonacci-guest");
+    program.set_input(&9u32);
+    ProofFixture::new(program, &FIB_FILE_LOCK)
+}
+
+fn memory_ops_fixture<PCS: AdditivelyHomomorphic<KeccakTranscript, Field = Fr>>(
+) -> ProofFixture<PCS> {
+    ProofFixture::new(
+        host::Program::new("memory-ops-guest"),
+        &MEMORY_OPS_FILE_LOCK,
+    )
+}
+
+fn sha3_fixture<PCS: AdditivelyHomomorphic<KeccakTranscript, Field = Fr>>() -> ProofFixture<PCS> {
+    let mut program = host::Program::new("sha3-guest");
+    program.set_input(&[5u8; 32]);
+    ProofFixture::new(program, &SHA3_FILE_LOCK)
+}
+
+#[test]
+fn fib_tampering_mock() {
+    assert_tampering_rejected(&fib_fixture::<MockCommitScheme<Fr, KeccakTranscript>>());
+}
+
+#[test]
+fn memory_ops_tampering_mock() {
+    assert_tampering_rejected(&memory_ops_fixture::<MockCommitScheme<Fr, KeccakTranscript>>());
+}
+
+#[ignore = "extended tampering suite; slow"]
+#[test]
+fn fib_tampering_hyperkzg() {
+    assert_tampering_rejected(&fib_fixture::<HyperKZG<Bn254, KeccakTranscript>>());
+}
+
+#[ignore = "extended tampering suite; slow"]
+#[test]
+fn memory_ops_tampering_hyperkzg() {
+    assert_tampering_rejected(&memory_ops_fixture::<HyperKZG<Bn254, KeccakTranscript>>());
+}
+
+#[ignore = "extended tampering suite; slow"]
+#[test]
+fn sha3_tampering_hyperkzg() {
+    assert_tampering_rejected(&sha3_fixture::<HyperKZG<Bn254, KeccakTranscript>>());
+}
@@ -4,8 +4,8 @@ use crate::lasso::memory_checking::{
 };
 use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
 use crate::subprotocols::grand_product::{
-    BatchedDenseGrandProduct, BatchedGrandProduct, BatchedGrandProductLayer,
-    BatchedGrandProductProof,
+    BatchedGrandProduct, BatchedGrandProductLayer, BatchedGrandProductProof,
+    ConfigurableGrandProduct, GrandProductBackend,
 };
 use crate::utils::math::Math;
 use crate::utils::thread::drop_in_background_thread;
@@ -20,16 +20,16 @@ use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, Commitm
 use crate::utils::transcript::Transcript;
 use crate::{
     lasso::memory_checking::{
-        MemoryCheckingProof, MemoryCheckingProver, MemoryCheckingVerifier, MultisetHashes,
-        NoPreprocessing,
+        check_multiset_hashes, check_num_hashes, MemoryCheckingProof, MemoryCheckingProver,
+        MemoryCheckingVerifier, MultisetHashes, NoPreprocessing,
     },
     poly::{
         dense_mlpoly::DensePolynomial, eq_poly::EqPolynomial, identity_poly::IdentityPolynomial,
     },
-    utils::errors::ProofVerifyError,
+    utils::errors::{GrandProductError, MemoryCheckingError, Mismatch, ProofVerifyError},
 };
 
-use super::{JoltCommitments, JoltPolynomials, JoltStuff};
+use super::{CompactEvals, JoltCommitments, JoltPolynomials, JoltStuff};
 
 #[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
 pub struct TimestampRangeCheckStuff<T: CanonicalSerialize + CanonicalDeserialize + Sync> {
@@ -127,26 +127,29 @@ where
 {
     #[tracing::instrument(skip_all, name = "TimestampRangeCheckWitness::new")]
     pub fn generate_witness(
-        read_timestamps: &[Vec<u64>; MEMORY_OPS_PER_INSTRUCTION],
-    ) -> TimestampRangeCheckPolynomials<F> {
+        read_timestamps: &[&[u32]; MEMORY_OPS_PER_INSTRUCTION],
+    ) -> (
+        TimestampRangeCheckPolynomials<F>,
+        TimestampRangeCheckStuff<CompactEvals>,
+    ) {
         let M = read_timestamps[0].len();
 
         #[cfg(test)]
-        let mut init_tuples: HashSet<(u64, u64)> = HashSet::new();
+        let mut init_tuples: HashSet<(u32, u32)> = HashSet::new();
         #[cfg(test)]
         {
             for i in 0..M {
-                init_tuples.insert((i as u64, 0u64));
+                init_tuples.insert((i as u32, 0u32));
             }
         }
 
-        let read_and_final_cts: Vec<[Vec<u64>; 4]> = (0..MEMORY_OPS_PER_INSTRUCTION)
+        let read_and_final_cts: Vec<[Vec<u32>; 4]> = (0..MEMORY_OPS_PER_INSTRUCTION)
             .into_par_iter()
             .map(|i| {
-                let mut read_cts_read_timestamp: Vec<u64> = vec![0; M];
-                let mut read_cts_global_minus_read: Vec<u64> = vec![0; M];
-                let mut final_cts_read_timestamp: Vec<u64> = vec![0; M];
-                let mut final_cts_global_minus_read: Vec<u64> = vec![0; M];
+                let mut read_cts_read_timestamp: Vec<u32> = vec![0; M];
+                let mut read_cts_global_minus_read: Vec<u32> = vec![0; M];
+                let mut final_cts_read_timestamp: Vec<u32> = vec![0; M];
+                let mut final_cts_global_minus_read: Vec<u32> = vec![0; M];
 
                 for (j, read_timestamp) in read_timestamps[i].iter().enumerate() {
                     read_cts_read_timestamp[j] = final_cts_read_timestamp[*read_timestamp as usize];
@@ -158,36 +161,36 @@ where
 
                 #[cfg(test)]
                 {
-                    let global_minus_read_timestamps = &read_timestamps[i]
+                    let global_minus_read_timestamps: Vec<u32> = read_timestamps[i]
                         .iter()
                         .enumerate()
-                        .map(|(j, timestamp)| j as u64 - *timestamp)
+                        .map(|(j, timestamp)| j as u32 - *timestamp)
                         .collect();
 
                     for (lookup_indices, read_cts, final_cts) in [
                         (
-                            &read_timestamps[i],
+                            read_timestamps[i],
                             &read_cts_read_timestamp,
                             &final_cts_read_timestamp,
                         ),
                         (
-                            global_minus_read_timestamps,
+                            &global_minus_read_timestamps[..],
                             &read_cts_global_minus_read,
                             &final_cts_global_minus_read,
                         ),
                     ]
                     .iter()
                     {
-                        let mut read_tuples: HashSet<(u64, u64)> = HashSet::new();
-                        let mut write_tuples: HashSet<(u64, u64)> = HashSet::new();
+                        let mut read_tuples: HashSet<(u32, u32)> = HashSet::new();
+                        let mut write_tuples: HashSet<(u32, u32)> = HashSet::new();
                         for (v, t) in lookup_indices.iter().zip(read_cts.iter()) {
                             read_tuples.insert((*v, *t));
                             write_tuples.insert((*v, *t + 1));
                         }
 
-                        let mut final_tuples: HashSet<(u64, u64)> = HashSet::new();
+                        let mut final_tuples: HashSet<(u32, u32)> = HashSet::new();
                         for (i, t) in final_cts.iter().enumerate() {
-                            final_tuples.insert((i as u64, *t));
+                            final_tuples.insert((i as u32, *t));
                         }
 
                         let init_write: HashSet<_> = init_tuples.union(&write_tuples).collect();
@@ -209,36 +212,50 @@ where
 
         let read_cts_read_timestamp = read_and_final_cts
             .par_iter()
-            .map(|cts| DensePolynomial::from_u64(&cts[0]))
+            .map(|cts| DensePolynomial::from_small_scalars(&cts[0]))
             .collect::<Vec<DensePolynomial<F>>>()
             .try_into()
             .unwrap();
         let read_cts_global_minus_read = read_and_final_cts
             .par_iter()
-            .map(|cts| DensePolynomial::from_u64(&cts[1]))
+            .map(|cts| DensePolynomial::from_small_scalars(&cts[1]))
             .collect::<Vec<DensePolynomial<F>>>()
             .try_into()
             .unwrap();
         let final_cts_read_timestamp = read_and_final_cts
             .par_iter()
-            .map(|cts| DensePolynomial::from_u64(&cts[2]))
+            .map(|cts| DensePolynomial::from_small_scalars(&cts[2]))
             .collect::<Vec<DensePolynomial<F>>>()
             .try_into()
             .unwrap();
         let final_cts_global_minus_read = read_and_final_cts
             .par_iter()
-            .map(|cts| DensePolynomial::from_u64(&cts[3]))
+            .map(|cts| DensePolynomial::from_small_scalars(&cts[3]))
             .collect::<Vec<DensePolynomial<F>>>()
             .try_into()
             .unwrap();
 
-        TimestampRangeCheckPolynomials {
+        let polynomials = TimestampRangeCheckPolynomials {
             read_cts_read_timestamp,
             read_cts_global_minus_read,
             final_cts_read_timestamp,
             final_cts_global_minus_read,
             identity: None,
+        };
+
+        let mut compact = TimestampRangeCheckStuff::<CompactEvals>::default();
+        for (
+            i,
+            [read_cts_read_timestamp, read_cts_global_minus_read, final_cts_read_timestamp, final_cts_global_minus_read],
+        ) in read_and_final_cts.into_iter().enumerate()
+        {
+            compact.read_cts_read_timestamp[i] = CompactEvals::U32(read_cts_read_timestamp);
+            compact.read_cts_global_minus_read[i] = CompactEvals::U32(read_cts_global_minus_read);
+            compact.final_cts_read_timestamp[i] = CompactEvals::U32(final_cts_read_timestamp);
+            compact.final_cts_global_minus_read[i] = CompactEvals::U32(final_cts_global_minus_read);
         }
+
+        (polynomials, compact)
     }
 }
 
@@ -265,6 +282,7 @@ where
         _: &JoltPolynomials<F>,
         _: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         _: &mut ProofTranscript,
+        _: GrandProductBackend,
     ) -> MemoryCheckingProof<F, PCS, Self::Openings, Self::ExogenousOpenings, ProofTranscript> {
         unimplemented!("Use TimestampValidityProof::prove instead");
     }
@@ -415,24 +433,23 @@ where
         }
     }
 
-    fn check_multiset_equality(_: &NoPreprocessing, multiset_hashes: &MultisetHashes<F>) {
+    fn check_multiset_equality(
+        _: &NoPreprocessing,
+        multiset_hashes: &MultisetHashes<F>,
+    ) -> Result<(), MemoryCheckingError> {
         let num_memories = 2 * MEMORY_OPS_PER_INSTRUCTION;
-        assert_eq!(multiset_hashes.read_hashes.len(), num_memories);
-        assert_eq!(multiset_hashes.write_hashes.len(), num_memories);
-        assert_eq!(multiset_hashes.final_hashes.len(), num_memories);
-        assert_eq!(multiset_hashes.init_hashes.len(), 1);
+        check_num_hashes(&multiset_hashes.read_hashes, num_memories)?;
+        check_num_hashes(&multiset_hashes.write_hashes, num_memories)?;
+        check_num_hashes(&multiset_hashes.final_hashes, num_memories)?;
+        check_num_hashes(&multiset_hashes.init_hashes, 1)?;
 
-        (0..num_memories).into_par_iter().for_each(|i| {
+        (0..num_memories).into_par_iter().try_for_each(|i| {
             let read_hash = multiset_hashes.read_hashes[i];
             let write_hash = multiset_hashes.write_hashes[i];
             let init_hash = multiset_hashes.init_hashes[0];
             let final_hash = multiset_hashes.final_hashes[i];
-            assert_eq!(
-                init_hash * write_hash,
-                final_hash * read_hash,
-                "Multiset hashes don't match"
-            );
-        });
+            check_multiset_hashes(i, init_hash * write_hash, final_hash * read_hash)
+        })
     }
 
     fn protocol_name() -> &'static [u8] {
@@ -453,7 +470,7 @@ where
 
     fn verify_memory_checking(
         _: &NoPreprocessing,
-        _: &PCS::Setup,
+        _: &PCS::VerifierSetup,
         mut _proof: MemoryCheckingProof<
             F,
             PCS,
@@ -465,7 +482,7 @@ where
         _: &JoltCommitments<PCS, ProofTranscript>,
         _opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         _transcript: &mut ProofTranscript,
-    ) -> Result<(), ProofVerifyError> {
+    ) -> Result<(), MemoryCheckingError> {
         unimplemented!("Use TimestampValidityProof::verify instead");
     }
 
@@ -549,7 +566,7 @@ where
     ProofTranscript: Transcript,
 {
     type Leaves = ();
-    type Config = ();
+    type Config = GrandProductBackend;
 
     fn construct(_leaves: Self::Leaves) -> Self {
         unimplemented!("init/final grand products are batched with read/write grand products");
@@ -583,8 +600,8 @@ where
         _claims: &[F],
         _opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
         _transcript: &mut ProofTranscript,
-        _setup: Option<&PCS::Setup>,
-    ) -> (F, Vec<F>) {
+        _setup: Option<&PCS::VerifierSetup>,
+    ) -> Result<(F, Vec<F>), GrandProductError> {
         unimplemented!("init/final grand products are batched with read/write grand products")
     }
 }
@@ -596,10 +613,10 @@ where
     PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 {
-    multiset_hashes: MultisetHashes<F>,
-    openings: TimestampRangeCheckOpenings<F>,
-    exogenous_openings: ReadTimestampOpenings<F>,
-    batched_grand_product: BatchedGrandProductProof<PCS, ProofTranscript>,
+    pub(crate) multiset_hashes: MultisetHashes<F>,
+    pub(crate) openings: TimestampRangeCheckOpenings<F>,
+    pub(crate) exogenous_openings: ReadTimestampOpenings<F>,
+    pub(crate) batched_grand_product: BatchedGrandProductProof<PCS, ProofTranscript>,
 }
 
 impl<F, PCS, ProofTranscript> TimestampValidityProof<F, PCS, ProofTranscript>
@@ -615,6 +632,7 @@ where
         jolt_polynomials: &'a JoltPolynomials<F>,
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
+        grand_product_backend: GrandProductBackend,
     ) -> Self {
         let (batched_grand_product, multiset_hashes, r_grand_product) =
             TimestampValidityProof::prove_grand_products(
@@ -623,6 +641,7 @@ where
                 opening_accumulator,
                 transcript,
                 generators,
+                grand_product_backend,
             );
 
         let mut openings = TimestampRangeCheckOpenings::default();
@@ -680,6 +699,7 @@ where
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
         setup: &PCS::Setup,
+        grand_product_backend: GrandProductBackend,
     ) -> (
         BatchedGrandProductProof<PCS, ProofTranscript>,
         MultisetHashes<F>,
@@ -700,17 +720,19 @@ where
             &tau,
         );
 
-        let mut batched_circuit = <BatchedDenseGrandProduct<F> as BatchedGrandProduct<
-            F,
-            PCS,
-            ProofTranscript,
-        >>::construct(leaves);
-
-        let hashes: Vec<F> = <BatchedDenseGrandProduct<F> as BatchedGrandProduct<
-            F,
-            PCS,
-            ProofTranscript,
-        >>::claimed_outputs(&batched_circuit);
+        let mut batched_circuit =
+            <ConfigurableGrandProduct<F, ProofTranscript> as BatchedGrandProduct<
+                F,
+                PCS,
+                ProofTranscript,
+            >>::construct_with_config(leaves, grand_product_backend);
+
+        let hashes: Vec<F> =
+            <ConfigurableGrandProduct<F, ProofTranscript> as BatchedGrandProduct<
+                F,
+                PCS,
+                ProofTranscript,
+            >>::claimed_outputs(&batched_circuit);
         let (read_write_hashes, init_final_hashes) =
             hashes.split_at(4 * MEMORY_OPS_PER_INSTRUCTION);
         let multiset_hashes =
@@ -722,7 +744,8 @@ where
         TimestampValidityProof::<F, PCS, ProofTranscript>::check_multiset_equality(
             &NoPreprocessing,
             &multiset_hashes,
-        );
+        )
+        .expect("multiset hashes computed by the prover should match");
         multiset_hashes.append_to_transcript(transcript);
 
         let (batched_grand_product, r_grand_product) =
@@ -735,7 +758,7 @@ where
 
     pub fn verify(
         &mut self,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         commitments: &JoltCommitments<PCS, ProofTranscript>,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
@@ -751,7 +774,8 @@ where
         TimestampValidityProof::<F, PCS, ProofTranscript>::check_multiset_equality(
             &NoPreprocessing,
             &self.multiset_hashes,
-        );
+        )
+        .map_err(ProofVerifyError::TimestampRangeCheck)?;
         self.multiset_hashes.append_to_transcript(transcript);
 
         let (read_write_hashes, init_final_hashes) =
@@ -764,13 +788,17 @@ where
             );
         let concatenated_hashes = [read_write_hashes, init_final_hashes].concat();
         let batch_size = concatenated_hashes.len();
-        let (grand_product_claim, r_grand_product) = BatchedDenseGrandProduct::verify_grand_product(
-            &self.batched_grand_product,
-            &concatenated_hashes,
-            Some(opening_accumulator),
-            transcript,
-            Some(generators),
-        );
+        let (grand_product_claim, r_grand_product) =
+            ConfigurableGrandProduct::<F, ProofTranscript>::verify_grand_product(
+                &self.batched_grand_product,
+                &concatenated_hashes,
+                Some(opening_accumulator),
+                transcript,
+                Some(generators),
+            )
+            .map_err(|e| {
+                ProofVerifyError::TimestampRangeCheck(MemoryCheckingError::GrandProduct(e))
+            })?;
         let (r_batch_index, r_opening) =
             r_grand_product.split_at(batch_size.next_power_of_two().log_2());
 
@@ -849,9 +877,9 @@ where
             .zip(EqPolynomial::evals(r_batch_index).iter())
             .map(|(hash, eq_eval)| *hash * eq_eval)
             .sum();
-        assert_eq!(combined_hash, grand_product_claim);
-
-        Ok(())
+        Mismatch::check(grand_product_claim, combined_hash).map_err(|mismatch| {
+            ProofVerifyError::TimestampRangeCheck(MemoryCheckingError::Fingerprints(mismatch))
+        })
     }
 
     /// Computes the shape of all commitments.
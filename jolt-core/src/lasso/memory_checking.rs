@@ -5,14 +5,15 @@ use crate::jolt::vm::{JoltCommitments, JoltPolynomials, JoltStuff};
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::eq_poly::EqPolynomial;
 use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
-use crate::utils::errors::ProofVerifyError;
+use crate::utils::errors::{MemoryCheckingError, Mismatch};
 use crate::utils::math::Math;
 use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::Transcript;
 use crate::{
     poly::commitment::commitment_scheme::CommitmentScheme,
     subprotocols::grand_product::{
-        BatchedDenseGrandProduct, BatchedGrandProduct, BatchedGrandProductProof,
+        BatchedGrandProduct, BatchedGrandProductProof, ConfigurableGrandProduct,
+        GrandProductBackend,
     },
 };
 
@@ -209,10 +210,12 @@ where
     ProofTranscript: Transcript,
     Self: Sync,
 {
-    type ReadWriteGrandProduct: BatchedGrandProduct<F, PCS, ProofTranscript> + Send + 'static =
-        BatchedDenseGrandProduct<F>;
-    type InitFinalGrandProduct: BatchedGrandProduct<F, PCS, ProofTranscript> + Send + 'static =
-        BatchedDenseGrandProduct<F>;
+    type ReadWriteGrandProduct: BatchedGrandProduct<F, PCS, ProofTranscript, Config: From<GrandProductBackend>>
+        + Send
+        + 'static = ConfigurableGrandProduct<F, ProofTranscript>;
+    type InitFinalGrandProduct: BatchedGrandProduct<F, PCS, ProofTranscript, Config: From<GrandProductBackend>>
+        + Send
+        + 'static = ConfigurableGrandProduct<F, ProofTranscript>;
 
     type Polynomials: StructuredPolynomialData<DensePolynomial<F>>;
     type Openings: StructuredPolynomialData<F> + Sync + Initializable<F, Self::Preprocessing>;
@@ -233,6 +236,7 @@ where
         jolt_polynomials: &JoltPolynomials<F>,
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
+        grand_product_backend: GrandProductBackend,
     ) -> MemoryCheckingProof<F, PCS, Self::Openings, Self::ExogenousOpenings, ProofTranscript> {
         let (
             read_write_grand_product,
@@ -247,6 +251,7 @@ where
             opening_accumulator,
             transcript,
             pcs_setup,
+            grand_product_backend,
         );
 
         let read_write_batch_size =
@@ -290,6 +295,7 @@ where
         opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
         pcs_setup: &PCS::Setup,
+        grand_product_backend: GrandProductBackend,
     ) -> (
         BatchedGrandProductProof<PCS, ProofTranscript>,
         BatchedGrandProductProof<PCS, ProofTranscript>,
@@ -306,14 +312,23 @@ where
 
         let (read_write_leaves, init_final_leaves) =
             Self::compute_leaves(preprocessing, polynomials, jolt_polynomials, &gamma, &tau);
-        let (mut read_write_circuit, read_write_hashes) =
-            Self::read_write_grand_product(preprocessing, polynomials, read_write_leaves);
-        let (mut init_final_circuit, init_final_hashes) =
-            Self::init_final_grand_product(preprocessing, polynomials, init_final_leaves);
+        let (mut read_write_circuit, read_write_hashes) = Self::read_write_grand_product(
+            preprocessing,
+            polynomials,
+            read_write_leaves,
+            grand_product_backend,
+        );
+        let (mut init_final_circuit, init_final_hashes) = Self::init_final_grand_product(
+            preprocessing,
+            polynomials,
+            init_final_leaves,
+            grand_product_backend,
+        );
 
         let multiset_hashes =
             Self::uninterleave_hashes(preprocessing, read_write_hashes, init_final_hashes);
-        Self::check_multiset_equality(preprocessing, &multiset_hashes);
+        Self::check_multiset_equality(preprocessing, &multiset_hashes)
+            .expect("multiset hashes computed by the prover should match");
         multiset_hashes.append_to_transcript(transcript);
 
         let (read_write_grand_product, r_read_write) = read_write_circuit.prove_grand_product(
@@ -399,9 +414,29 @@ where
             transcript,
         );
 
+        Self::prove_preprocessed_openings(
+            preprocessing,
+            &mut openings,
+            opening_accumulator,
+            r_init_final,
+            transcript,
+        );
+
         (openings, exogenous_openings)
     }
 
+    /// Opens polynomials that are fixed by the preprocessing (e.g. the program bytecode) at
+    /// the init/final opening point. The verifier only holds commitments to these polynomials,
+    /// so unlike other verifier-computed openings their evaluations must be proven.
+    fn prove_preprocessed_openings(
+        _preprocessing: &Self::Preprocessing,
+        _openings: &mut Self::Openings,
+        _opening_accumulator: &mut ProverOpeningAccumulator<F, ProofTranscript>,
+        _r_init_final: &[F],
+        _transcript: &mut ProofTranscript,
+    ) {
+    }
+
     /// Constructs a batched grand product circuit for the read and write multisets associated
     /// with the given leaves. Also returns the corresponding multiset hashes for each memory.
     #[tracing::instrument(skip_all, name = "MemoryCheckingProver::read_write_grand_product")]
@@ -413,8 +448,12 @@ where
             PCS,
             ProofTranscript,
         >>::Leaves,
+        grand_product_backend: GrandProductBackend,
     ) -> (Self::ReadWriteGrandProduct, Vec<F>) {
-        let batched_circuit = Self::ReadWriteGrandProduct::construct(read_write_leaves);
+        let batched_circuit = Self::ReadWriteGrandProduct::construct_with_config(
+            read_write_leaves,
+            grand_product_backend.into(),
+        );
         let claims = batched_circuit.claimed_outputs();
         (batched_circuit, claims)
     }
@@ -430,8 +469,12 @@ where
             PCS,
             ProofTranscript,
         >>::Leaves,
+        grand_product_backend: GrandProductBackend,
     ) -> (Self::InitFinalGrandProduct, Vec<F>) {
-        let batched_circuit = Self::InitFinalGrandProduct::construct(init_final_leaves);
+        let batched_circuit = Self::InitFinalGrandProduct::construct_with_config(
+            init_final_leaves,
+            grand_product_backend.into(),
+        );
         let claims = batched_circuit.claimed_outputs();
         (batched_circuit, claims)
     }
@@ -482,23 +525,19 @@ where
     fn check_multiset_equality(
         _preprocessing: &Self::Preprocessing,
         multiset_hashes: &MultisetHashes<F>,
-    ) {
+    ) -> Result<(), MemoryCheckingError> {
         let num_memories = multiset_hashes.read_hashes.len();
-        assert_eq!(multiset_hashes.final_hashes.len(), num_memories);
-        assert_eq!(multiset_hashes.write_hashes.len(), num_memories);
-        assert_eq!(multiset_hashes.init_hashes.len(), num_memories);
+        check_num_hashes(&multiset_hashes.final_hashes, num_memories)?;
+        check_num_hashes(&multiset_hashes.write_hashes, num_memories)?;
+        check_num_hashes(&multiset_hashes.init_hashes, num_memories)?;
 
-        (0..num_memories).into_par_iter().for_each(|i| {
+        (0..num_memories).into_par_iter().try_for_each(|i| {
             let read_hash = multiset_hashes.read_hashes[i];
             let write_hash = multiset_hashes.write_hashes[i];
             let init_hash = multiset_hashes.init_hashes[i];
             let final_hash = multiset_hashes.final_hashes[i];
-            assert_eq!(
-                init_hash * write_hash,
-                final_hash * read_hash,
-                "Multiset hashes don't match"
-            );
-        });
+            check_multiset_hashes(i, init_hash * write_hash, final_hash * read_hash)
+        })
     }
 
     /// Computes the MLE of the leaves of the read, write, init, and final grand product circuits,
@@ -533,7 +572,7 @@ where
     /// Verifies a memory checking proof, given its associated polynomial `commitment`.
     fn verify_memory_checking(
         preprocessing: &Self::Preprocessing,
-        pcs_setup: &PCS::Setup,
+        pcs_setup: &PCS::VerifierSetup,
         mut proof: MemoryCheckingProof<
             F,
             PCS,
@@ -545,7 +584,7 @@ where
         jolt_commitments: &JoltCommitments<PCS, ProofTranscript>,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
-    ) -> Result<(), ProofVerifyError> {
+    ) -> Result<(), MemoryCheckingError> {
         // Fiat-Shamir randomness for multiset hashes
         let gamma: F = transcript.challenge_scalar();
         let tau: F = transcript.challenge_scalar();
@@ -553,7 +592,7 @@ where
         let protocol_name = Self::protocol_name();
         transcript.append_message(protocol_name);
 
-        Self::check_multiset_equality(preprocessing, &proof.multiset_hashes);
+        Self::check_multiset_equality(preprocessing, &proof.multiset_hashes)?;
         proof.multiset_hashes.append_to_transcript(transcript);
 
         let (read_write_hashes, init_final_hashes) = Self::interleave(
@@ -571,7 +610,8 @@ where
             Some(opening_accumulator),
             transcript,
             Some(pcs_setup),
-        );
+        )
+        .map_err(MemoryCheckingError::ReadWriteGrandProduct)?;
         // For a batch size of k, the first log2(k) elements of `r_read_write`/`r_init_final`
         // form the point at which the output layer's MLE is evaluated. The remaining elements
         // then form the point at which the leaf layer's polynomials are evaluated.
@@ -585,7 +625,8 @@ where
             Some(opening_accumulator),
             transcript,
             Some(pcs_setup),
-        );
+        )
+        .map_err(MemoryCheckingError::InitFinalGrandProduct)?;
         let (r_init_final_batch_index, r_init_final_opening) =
             r_init_final.split_at(init_final_batch_size.next_power_of_two().log_2());
 
@@ -613,6 +654,15 @@ where
             transcript,
         );
 
+        Self::verify_preprocessed_openings(
+            preprocessing,
+            commitments,
+            &proof.openings,
+            opening_accumulator,
+            r_init_final_opening,
+            transcript,
+        )?;
+
         Self::compute_verifier_openings(
             &mut proof.openings,
             preprocessing,
@@ -630,8 +680,20 @@ where
             &proof.exogenous_openings,
             &gamma,
             &tau,
-        );
+        )
+    }
 
+    /// Counterpart of [`MemoryCheckingProver::prove_preprocessed_openings`]: appends the claimed
+    /// openings of the preprocessed polynomials to the `opening_accumulator`, checked against
+    /// the commitments held in the verifier preprocessing.
+    fn verify_preprocessed_openings(
+        _preprocessing: &Self::Preprocessing,
+        _commitments: &Self::Commitments,
+        _openings: &Self::Openings,
+        _opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
+        _r_init_final: &[F],
+        _transcript: &mut ProofTranscript,
+    ) -> Result<(), MemoryCheckingError> {
         Ok(())
     }
 
@@ -683,7 +745,7 @@ where
         exogenous_openings: &Self::ExogenousOpenings,
         gamma: &F,
         tau: &F,
-    ) {
+    ) -> Result<(), MemoryCheckingError> {
         let read_hashes: Vec<_> = Self::read_tuples(preprocessing, openings, exogenous_openings)
             .iter()
             .map(|tuple| Self::fingerprint(tuple, gamma, tau))
@@ -725,13 +787,40 @@ where
             .zip(EqPolynomial::evals(r_read_write_batch_index).iter())
             .map(|(hash, eq_eval)| *hash * eq_eval)
             .sum();
-        assert_eq!(combined_read_write_hash, read_write_claim);
+        Mismatch::check(read_write_claim, combined_read_write_hash)
+            .map_err(MemoryCheckingError::ReadWriteFingerprints)?;
 
         let combined_init_final_hash: F = init_final_hashes
             .iter()
             .zip(EqPolynomial::evals(r_init_final_batch_index).iter())
             .map(|(hash, eq_eval)| *hash * eq_eval)
             .sum();
-        assert_eq!(combined_init_final_hash, init_final_claim);
+        Mismatch::check(init_final_claim, combined_init_final_hash)
+            .map_err(MemoryCheckingError::InitFinalFingerprints)
     }
 }
+
+/// Checks that a proof contains the expected number of multiset hashes of some kind.
+pub(crate) fn check_num_hashes<F>(
+    hashes: &[F],
+    expected: usize,
+) -> Result<(), MemoryCheckingError> {
+    if hashes.len() == expected {
+        Ok(())
+    } else {
+        Err(MemoryCheckingError::NumMultisetHashes {
+            expected,
+            actual: hashes.len(),
+        })
+    }
+}
+
+/// Checks that `init * write == final * read` for the memory at index `memory`.
+pub(crate) fn check_multiset_hashes<F: JoltField>(
+    memory: usize,
+    init_times_write: F,
+    final_times_read: F,
+) -> Result<(), MemoryCheckingError> {
+    Mismatch::check(init_times_write, final_times_read)
+        .map_err(|mismatch| MemoryCheckingError::MultisetHashes { memory, mismatch })
+}
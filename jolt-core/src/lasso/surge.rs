@@ -22,8 +22,13 @@ use crate::{
         eq_poly::EqPolynomial,
         identity_poly::IdentityPolynomial,
     },
-    subprotocols::sumcheck::SumcheckInstanceProof,
-    utils::{errors::ProofVerifyError, math::Math, mul_0_1_optimized, transcript::Transcript},
+    subprotocols::{grand_product::GrandProductBackend, sumcheck::SumcheckInstanceProof},
+    utils::{
+        errors::{Mismatch, ProofVerifyError, SumcheckError},
+        math::Math,
+        mul_0_1_optimized,
+        transcript::Transcript,
+    },
 };
 
 #[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
@@ -391,10 +396,10 @@ where
         preprocessing: &SurgePreprocessing<F, Instruction, C, M>,
         generators: &PCS::Setup,
         ops: Vec<Instruction>,
-    ) -> (Self, Option<ProverDebugInfo<F, ProofTranscript>>) {
+    ) -> (Self, Option<ProverDebugInfo<F, PCS, ProofTranscript>>) {
         let mut transcript = ProofTranscript::new(b"Surge transcript");
         let mut opening_accumulator: ProverOpeningAccumulator<F, ProofTranscript> =
-            ProverOpeningAccumulator::new();
+            ProverOpeningAccumulator::new::<PCS>();
         let protocol_name = Self::protocol_name();
         transcript.append_message(protocol_name);
 
@@ -472,6 +477,7 @@ where
             &JoltPolynomials::default(), // Hack: required by the memory-checking trait, but unused in Surge
             &mut opening_accumulator,
             &mut transcript,
+            GrandProductBackend::default(),
         );
 
         let proof = SurgeProof {
@@ -484,6 +490,7 @@ where
         let debug_info = Some(ProverDebugInfo {
             transcript,
             opening_accumulator,
+            pcs_setup: generators.clone(),
         });
         #[cfg(not(test))]
         let debug_info = None;
@@ -493,9 +500,9 @@ where
 
     pub fn verify(
         preprocessing: &SurgePreprocessing<F, Instruction, C, M>,
-        generators: &PCS::Setup,
+        generators: &PCS::VerifierSetup,
         proof: SurgeProof<F, PCS, Instruction, C, M, ProofTranscript>,
-        _debug_info: Option<ProverDebugInfo<F, ProofTranscript>>,
+        _debug_info: Option<ProverDebugInfo<F, PCS, ProofTranscript>>,
     ) -> Result<(), ProofVerifyError> {
         let mut transcript = ProofTranscript::new(b"Surge transcript");
         let mut opening_accumulator: VerifierOpeningAccumulator<F, PCS, ProofTranscript> =
@@ -503,7 +510,7 @@ where
         #[cfg(test)]
         if let Some(debug_info) = _debug_info {
             transcript.compare_to(debug_info.transcript);
-            opening_accumulator.compare_to(debug_info.opening_accumulator, &generators);
+            opening_accumulator.compare_to(debug_info.opening_accumulator, &debug_info.pcs_setup);
         }
 
         let protocol_name = Self::protocol_name();
@@ -514,19 +521,25 @@ where
 
         transcript.append_scalar(&proof.primary_sumcheck.claimed_evaluation);
         let primary_sumcheck_poly_degree = instruction.g_poly_degree(C) + 1;
-        let (claim_last, r_z) = proof.primary_sumcheck.sumcheck_proof.verify(
-            proof.primary_sumcheck.claimed_evaluation,
-            proof.primary_sumcheck.num_rounds,
-            primary_sumcheck_poly_degree,
-            &mut transcript,
-        )?;
+        let (claim_last, r_z) = proof
+            .primary_sumcheck
+            .sumcheck_proof
+            .verify(
+                proof.primary_sumcheck.claimed_evaluation,
+                proof.primary_sumcheck.num_rounds,
+                primary_sumcheck_poly_degree,
+                &mut transcript,
+            )
+            .map_err(ProofVerifyError::InstructionLookupsSumcheck)?;
 
         let eq_eval = EqPolynomial::new(r_primary_sumcheck.to_vec()).evaluate(&r_z);
-        assert_eq!(
-            eq_eval * instruction.combine_lookups(&proof.primary_sumcheck.E_poly_openings, C, M),
+        Mismatch::check(
             claim_last,
-            "Primary sumcheck check failed."
-        );
+            eq_eval * instruction.combine_lookups(&proof.primary_sumcheck.E_poly_openings, C, M),
+        )
+        .map_err(|mismatch| {
+            ProofVerifyError::InstructionLookupsSumcheck(SumcheckError::FinalClaim(mismatch))
+        })?;
 
         opening_accumulator.append(
             &proof.commitments.E_polys.iter().collect::<Vec<_>>(),
@@ -548,6 +561,7 @@ where
             &mut opening_accumulator,
             &mut transcript,
         )
+        .map_err(ProofVerifyError::InstructionLookupsMemoryChecking)
     }
 
     #[tracing::instrument(skip_all, name = "Surge::construct_polys")]
@@ -701,7 +715,7 @@ mod tests {
             KeccakTranscript,
         >::prove(&preprocessing, &generators, ops);
 
-        SurgeProof::verify(&preprocessing, &generators, proof, debug_info).expect("should work");
+        SurgeProof::verify(&preprocessing, &generators.1, proof, debug_info).expect("should work");
     }
 
     #[test]
@@ -733,6 +747,6 @@ mod tests {
             KeccakTranscript,
         >::prove(&preprocessing, &generators, ops);
 
-        SurgeProof::verify(&preprocessing, &generators, proof, debug_info).expect("should work");
+        SurgeProof::verify(&preprocessing, &generators.1, proof, debug_info).expect("should work");
     }
 }
@@ -12,7 +12,7 @@
 #![allow(type_alias_bounds)]
 #![allow(clippy::too_long_first_doc_paragraph)]
 
-#[cfg(feature = "host")]
+#[cfg(all(feature = "host", feature = "insecure-setup"))]
 pub mod benches;
 
 #[cfg(feature = "host")]
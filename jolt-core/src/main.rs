@@ -1,15 +1,30 @@
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use ark_bn254::{Bn254, Fr};
+#[cfg(feature = "insecure-setup")]
+use clap::ValueEnum;
+use clap::{Args, Parser, Subcommand};
 
+#[cfg(feature = "insecure-setup")]
 use jolt_core::benches::{
     bench::{benchmarks, BenchType, PCSType},
     sum_timer::CumulativeTimingLayer,
 };
+use jolt_core::jolt::vm::rv32i_vm::{RV32IJoltVM, C, M};
+use jolt_core::jolt::vm::Jolt;
+use jolt_core::poly::commitment::hyperkzg::HyperKZG;
+use jolt_core::poly::commitment::kzg::SRS;
+use jolt_core::utils::transcript::KeccakTranscript;
 
+#[cfg(feature = "insecure-setup")]
 use std::any::Any;
+use std::path::PathBuf;
 
+#[cfg(feature = "insecure-setup")]
 use tracing_chrome::ChromeLayerBuilder;
+#[cfg(feature = "insecure-setup")]
 use tracing_flame::FlameLayer;
+#[cfg(feature = "insecure-setup")]
 use tracing_subscriber::{self, fmt::format::FmtSpan, prelude::*};
+#[cfg(feature = "insecure-setup")]
 use tracing_texray::TeXRayLayer;
 
 /// Search for a pattern in a file and display the lines that contain it.
@@ -21,9 +36,14 @@ struct Cli {
 
 #[derive(Subcommand, Debug)]
 enum Commands {
+    /// Traces the benchmarks, which are preprocessed with an insecure setup
+    #[cfg(feature = "insecure-setup")]
     Trace(TraceArgs),
+    /// Writes the part of a KZG SRS needed to prove programs within the given bounds
+    TrimSrs(TrimSrsArgs),
 }
 
+#[cfg(feature = "insecure-setup")]
 #[derive(Args, Debug)]
 struct TraceArgs {
     /// Output formats
@@ -42,6 +62,30 @@ struct TraceArgs {
     num_cycles: Option<usize>,
 }
 
+#[derive(Args, Debug)]
+struct TrimSrsArgs {
+    /// Input SRS: a snarkjs `.ptau` file, or an SRS file written by this command
+    #[clap(short, long)]
+    input: PathBuf,
+
+    /// Output SRS file
+    #[clap(short, long)]
+    output: PathBuf,
+
+    /// Maximum trace length of the programs to prove
+    #[clap(long, default_value_t = 1 << 24)]
+    max_trace_length: usize,
+
+    /// Maximum bytecode size of the programs to prove
+    #[clap(long, default_value_t = 1 << 20)]
+    max_bytecode_size: usize,
+
+    /// Maximum memory address of the programs to prove
+    #[clap(long, default_value_t = 1 << 20)]
+    max_memory_address: usize,
+}
+
+#[cfg(feature = "insecure-setup")]
 #[derive(Args, Debug)]
 struct PlotArgs {
     /// Type of benchmark to run
@@ -65,6 +109,7 @@ struct PlotArgs {
     bytecode_size: Vec<usize>,
 }
 
+#[cfg(feature = "insecure-setup")]
 #[derive(Debug, Clone, ValueEnum, PartialEq)]
 enum Format {
     Default,
@@ -77,10 +122,51 @@ enum Format {
 fn main() {
     let cli = Cli::parse();
     match cli.command {
+        #[cfg(feature = "insecure-setup")]
         Commands::Trace(args) => trace(args),
+        Commands::TrimSrs(args) => trim_srs(args),
+    }
+}
+
+fn trim_srs(args: TrimSrsArgs) {
+    let shapes = <RV32IJoltVM as Jolt<
+        Fr,
+        HyperKZG<Bn254, KeccakTranscript>,
+        C,
+        M,
+        KeccakTranscript,
+    >>::commitment_shapes(
+        args.max_bytecode_size,
+        args.max_memory_address,
+        args.max_trace_length,
+    );
+    let (num_g1_powers, num_g2_powers) = HyperKZG::<Bn254, KeccakTranscript>::srs_size(&shapes);
+
+    // Writing the output truncates it, which must not happen to the memory-mapped input
+    let input = args
+        .input
+        .canonicalize()
+        .unwrap_or_else(|err| panic!("failed to open {}: {err}", args.input.display()));
+    if args
+        .output
+        .canonicalize()
+        .is_ok_and(|output| output == input)
+    {
+        panic!("the input and output must be different files");
     }
+
+    // SAFETY: the input is not the output, and nothing else modifies it while it is loaded
+    let srs = unsafe { SRS::<Bn254>::from_file(&input, num_g1_powers, num_g2_powers) }
+        .unwrap_or_else(|err| panic!("failed to load {}: {err}", args.input.display()));
+    srs.save_to_file(&args.output)
+        .unwrap_or_else(|err| panic!("failed to write {}: {err}", args.output.display()));
+    println!(
+        "Wrote {num_g1_powers} G1 and {num_g2_powers} G2 powers to {}",
+        args.output.display()
+    );
 }
 
+#[cfg(feature = "insecure-setup")]
 fn trace(args: TraceArgs) {
     let mut layers = Vec::new();
 
@@ -0,0 +1,196 @@
+use ark_ff::{prelude::*, PrimeField};
+use ark_serialize::{CanonicalDeserialize, Compress, Read, SerializationError, Validate};
+use ark_std::vec::Vec;
+use rayon::prelude::*;
+use std::fmt;
+
+use super::{msm_bigint, msm_bigint_wnaf, msm_u64, msm_u64_wnaf, VariableBaseMSM};
+
+/// Precomputed shifts of a fixed set of bases, for MSMs against bases that are reused across
+/// many commitments (e.g. an SRS or Pedersen generators).
+///
+/// Scalars are split into `num_windows` windows of `window_bits` bits, and the table stores
+/// `2^{k * window_bits} * base` for every base and window `k`. An MSM with full-size scalars
+/// then becomes a single Pippenger MSM over `num_windows` times as many bases but with
+/// `window_bits`-bit scalars, which needs `num_windows` times fewer bucket reductions, no
+/// doublings between windows, and can use larger buckets. More windows trade memory
+/// (`num_windows` points per base) for speed.
+#[derive(Clone)]
+pub struct FixedBaseMSMTable<V: VariableBaseMSM> {
+    num_bases: usize,
+    num_windows: usize,
+    window_bits: usize,
+    /// `table[i * num_windows + k] = 2^{k * window_bits} * bases[i]`, interleaved so that
+    /// any contiguous range of bases maps to a contiguous range of the table.
+    table: Vec<V::MulBase>,
+}
+
+impl<V: VariableBaseMSM> FixedBaseMSMTable<V> {
+    /// A good trade-off between speed and memory, which makes the windows 64 bits wide for
+    /// 254-bit scalar fields.
+    pub const DEFAULT_NUM_WINDOWS: usize = 4;
+    /// Tables with more windows take too much memory for the speedup to be worth it.
+    pub const MAX_NUM_WINDOWS: usize = 16;
+
+    #[tracing::instrument(skip_all, name = "FixedBaseMSMTable::new")]
+    pub fn new(bases: &[V::MulBase], num_windows: usize) -> Self {
+        assert!(
+            (1..=Self::MAX_NUM_WINDOWS).contains(&num_windows),
+            "num_windows must be between 1 and {}",
+            Self::MAX_NUM_WINDOWS
+        );
+        let window_bits = (V::ScalarField::MODULUS_BIT_SIZE as usize).div_ceil(num_windows);
+        let shifted_bases: Vec<V> = bases
+            .par_iter()
+            .flat_map_iter(|base| {
+                let mut shifted_base = V::zero();
+                shifted_base += base;
+                (0..num_windows).map(move |_| {
+                    let current = shifted_base;
+                    for _ in 0..window_bits {
+                        shifted_base.double_in_place();
+                    }
+                    current
+                })
+            })
+            .collect();
+
+        Self {
+            num_bases: bases.len(),
+            num_windows,
+            window_bits,
+            table: V::batch_convert_to_mul_base(&shifted_bases),
+        }
+    }
+
+    pub fn num_bases(&self) -> usize {
+        self.num_bases
+    }
+
+    pub fn num_windows(&self) -> usize {
+        self.num_windows
+    }
+
+    /// Deserializes the optional number of windows of a table stored alongside its bases,
+    /// rejecting numbers [`FixedBaseMSMTable::new`] would panic on or that would exhaust
+    /// memory.
+    pub(crate) fn deserialize_num_windows<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Option<usize>, SerializationError> {
+        match Option::<usize>::deserialize_with_mode(reader, compress, validate)? {
+            Some(num_windows) if !(1..=Self::MAX_NUM_WINDOWS).contains(&num_windows) => {
+                Err(SerializationError::InvalidData)
+            }
+            num_windows => Ok(num_windows),
+        }
+    }
+
+    /// Computes `sum_i scalars[i] * bases[offset + i]`, where `bases` are the bases the table
+    /// was built from.
+    pub(super) fn msm_bigint(
+        &self,
+        offset: usize,
+        scalars: &[<V::ScalarField as PrimeField>::BigInt],
+    ) -> V {
+        assert!(
+            offset + scalars.len() <= self.num_bases,
+            "not enough bases (req: {} from offset {}) in the table (length: {})",
+            scalars.len(),
+            offset,
+            self.num_bases
+        );
+        let bases =
+            &self.table[offset * self.num_windows..(offset + scalars.len()) * self.num_windows];
+        let windows = scalars.par_iter().flat_map_iter(|scalar| {
+            (0..self.num_windows)
+                .map(move |k| window(scalar, k * self.window_bits, self.window_bits))
+        });
+
+        if self.window_bits <= 64 {
+            let windows: Vec<u64> = windows.map(|window| window.as_ref()[0]).collect();
+            if V::NEGATION_IS_CHEAP {
+                msm_u64_wnaf(bases, &windows, self.window_bits)
+            } else {
+                msm_u64(bases, &windows, self.window_bits)
+            }
+        } else {
+            let windows: Vec<_> = windows.collect();
+            if V::NEGATION_IS_CHEAP {
+                msm_bigint_wnaf(bases, &windows, self.window_bits)
+            } else {
+                msm_bigint(bases, &windows, self.window_bits)
+            }
+        }
+    }
+}
+
+impl<V: VariableBaseMSM> fmt::Debug for FixedBaseMSMTable<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedBaseMSMTable")
+            .field("num_bases", &self.num_bases)
+            .field("num_windows", &self.num_windows)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns bits `start..start + len` of `scalar`.
+fn window<B: BigInteger>(scalar: &B, start: usize, len: usize) -> B {
+    let mut window = *scalar;
+    window.divn(start as u32);
+    for (i, limb) in window.as_mut().iter_mut().enumerate() {
+        let limb_start = i * 64;
+        if limb_start >= len {
+            *limb = 0;
+        } else if limb_start + 64 > len {
+            *limb &= (1 << (len - limb_start)) - 1;
+        }
+    }
+    window
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_ec::CurveGroup;
+    use ark_std::{test_rng, UniformRand};
+
+    #[test]
+    fn matches_variable_base_msm() {
+        let mut rng = test_rng();
+        let bases = G1Projective::normalize_batch(
+            &(0..300)
+                .map(|_| G1Projective::rand(&mut rng))
+                .collect::<Vec<_>>(),
+        );
+        let scalars: Vec<Fr> = (0..200).map(|_| Fr::rand(&mut rng)).collect();
+
+        for num_windows in [1, 2, 3, 4, 8] {
+            let table = FixedBaseMSMTable::<G1Projective>::new(&bases, num_windows);
+            for offset in [0, 100] {
+                let bases = &bases[offset..offset + scalars.len()];
+                let expected = <G1Projective as VariableBaseMSM>::msm(bases, &scalars).unwrap();
+                let actual = G1Projective::msm_precomputed(bases, &scalars, &table, offset);
+                assert_eq!(actual.unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn windows_recompose_scalar() {
+        let mut rng = test_rng();
+        let scalar = Fr::rand(&mut rng);
+        for window_bits in [8, 64, 100] {
+            let num_windows = (Fr::MODULUS_BIT_SIZE as usize).div_ceil(window_bits);
+            let shift = Fr::from(2u64).pow([window_bits as u64]);
+            let recomposed = (0..num_windows).rev().fold(Fr::zero(), |acc, k| {
+                let window = window(&scalar.into_bigint(), k * window_bits, window_bits);
+                assert!(window.num_bits() as usize <= window_bits);
+                acc * shift + Fr::from_bigint(window).unwrap()
+            });
+            assert_eq!(recomposed, scalar);
+        }
+    }
+}
@@ -1,58 +1,138 @@
 use ark_ec::{CurveGroup, ScalarMul};
 use ark_ff::{prelude::*, PrimeField};
+use ark_std::borrow::Cow;
 use ark_std::cmp::Ordering;
 use ark_std::vec::Vec;
 use rayon::prelude::*;
 
+pub mod fixed_base;
+
+pub use fixed_base::FixedBaseMSMTable;
+
 impl<G: CurveGroup> VariableBaseMSM for G {}
 
+/// Scalars small enough to be committed to without converting them to field elements, such as
+/// flags (`bool`), chunk indices (`u8`, `u16`) or timestamps (`u32`).
+pub trait SmallScalar: Copy + Send + Sync + Into<u64> {
+    #[inline(always)]
+    fn to_u64(self) -> u64 {
+        self.into()
+    }
+
+    /// Returns `scalars` as `u64`s, borrowing them if they already are.
+    fn slice_to_u64(scalars: &[Self]) -> Cow<'_, [u64]> {
+        Cow::Owned(scalars.par_iter().map(|s| s.to_u64()).collect())
+    }
+}
+
+impl SmallScalar for bool {}
+impl SmallScalar for u8 {}
+impl SmallScalar for u16 {}
+impl SmallScalar for u32 {}
+impl SmallScalar for u64 {
+    fn slice_to_u64(scalars: &[Self]) -> Cow<'_, [u64]> {
+        Cow::Borrowed(scalars)
+    }
+}
+
 /// Copy of ark_ec::VariableBaseMSM with minor modifications to speed up
 /// known small element sized MSMs.
 pub trait VariableBaseMSM: ScalarMul {
     fn msm(bases: &[Self::MulBase], scalars: &[Self::ScalarField]) -> Result<Self, usize> {
+        msm_inner(bases, scalars, None)
+    }
+
+    /// Like [`VariableBaseMSM::msm`], for `bases` that are the bases of `table` starting at
+    /// `offset`. Only scalars of more than 64 bits use the table's precomputed shifts; smaller
+    /// ones take the same path as [`VariableBaseMSM::msm_small_scalars`], for which the table
+    /// would only add windows of zeros.
+    fn msm_precomputed(
+        bases: &[Self::MulBase],
+        scalars: &[Self::ScalarField],
+        table: &FixedBaseMSMTable<Self>,
+        offset: usize,
+    ) -> Result<Self, usize> {
+        msm_inner(bases, scalars, Some((table, offset)))
+    }
+
+    /// Like [`VariableBaseMSM::msm`], for scalars given as small integers or bits, which are
+    /// bucketed directly rather than converted to field elements first.
+    fn msm_small_scalars<S: SmallScalar>(
+        bases: &[Self::MulBase],
+        scalars: &[S],
+    ) -> Result<Self, usize> {
         (bases.len() == scalars.len())
             .then(|| {
                 let max_num_bits = scalars
                     .par_iter()
-                    .map(|s| s.into_bigint().num_bits())
+                    .map(|s| 64 - s.to_u64().leading_zeros())
                     .max()
-                    .unwrap();
-
-                match max_num_bits {
-                    0 => Self::zero(),
-                    1 => {
-                        let scalars_u64 = &map_field_elements_to_u64::<Self>(scalars);
-                        msm_binary(bases, scalars_u64)
-                    }
-                    2..=10 => {
-                        let scalars_u64 = &map_field_elements_to_u64::<Self>(scalars);
-                        msm_small(bases, scalars_u64, max_num_bits as usize)
-                    }
-                    11..=64 => {
-                        let scalars_u64 = &map_field_elements_to_u64::<Self>(scalars);
-                        if Self::NEGATION_IS_CHEAP {
-                            msm_u64_wnaf(bases, scalars_u64, max_num_bits as usize)
-                        } else {
-                            msm_u64(bases, scalars_u64, max_num_bits as usize)
-                        }
-                    }
-                    _ => {
-                        let scalars = scalars
-                            .par_iter()
-                            .map(|s| s.into_bigint())
-                            .collect::<Vec<_>>();
-                        if Self::NEGATION_IS_CHEAP {
-                            msm_bigint_wnaf(bases, &scalars, max_num_bits as usize)
-                        } else {
-                            msm_bigint(bases, &scalars, max_num_bits as usize)
-                        }
-                    }
-                }
+                    .unwrap_or(0);
+                msm_up_to_64_bits(bases, scalars, max_num_bits as usize)
             })
             .ok_or_else(|| bases.len().min(scalars.len()))
     }
 }
 
+/// Computes an MSM with scalars of at most `max_num_bits <= 64` bits, whether they were given
+/// as field elements or as small integers or bits.
+fn msm_up_to_64_bits<V: VariableBaseMSM, S: SmallScalar>(
+    bases: &[V::MulBase],
+    scalars: &[S],
+    max_num_bits: usize,
+) -> V {
+    match max_num_bits {
+        0 => V::zero(),
+        1 => msm_binary(bases, scalars),
+        2..=10 => msm_small(bases, scalars, max_num_bits),
+        _ => {
+            let scalars_u64 = S::slice_to_u64(scalars);
+            if V::NEGATION_IS_CHEAP {
+                msm_u64_wnaf(bases, &scalars_u64, max_num_bits)
+            } else {
+                msm_u64(bases, &scalars_u64, max_num_bits)
+            }
+        }
+    }
+}
+
+fn msm_inner<V: VariableBaseMSM>(
+    bases: &[V::MulBase],
+    scalars: &[V::ScalarField],
+    table: Option<(&FixedBaseMSMTable<V>, usize)>,
+) -> Result<V, usize> {
+    (bases.len() == scalars.len())
+        .then(|| {
+            let max_num_bits = scalars
+                .par_iter()
+                .map(|s| s.into_bigint().num_bits())
+                .max()
+                .unwrap();
+
+            match max_num_bits {
+                0 => V::zero(),
+                1..=64 => {
+                    let scalars_u64 = &map_field_elements_to_u64::<V>(scalars);
+                    msm_up_to_64_bits(bases, scalars_u64, max_num_bits as usize)
+                }
+                _ => {
+                    let scalars = scalars
+                        .par_iter()
+                        .map(|s| s.into_bigint())
+                        .collect::<Vec<_>>();
+                    if let Some((table, offset)) = table {
+                        table.msm_bigint(offset, &scalars)
+                    } else if V::NEGATION_IS_CHEAP {
+                        msm_bigint_wnaf(bases, &scalars, max_num_bits as usize)
+                    } else {
+                        msm_bigint(bases, &scalars, max_num_bits as usize)
+                    }
+                }
+            }
+        })
+        .ok_or_else(|| bases.len().min(scalars.len()))
+}
+
 fn map_field_elements_to_u64<V: VariableBaseMSM>(field_elements: &[V::ScalarField]) -> Vec<u64> {
     field_elements
         .par_iter()
@@ -415,25 +495,30 @@ fn msm_u64<V: VariableBaseMSM>(bases: &[V::MulBase], scalars: &[u64], max_num_bi
 }
 
 #[tracing::instrument(skip_all, name = "msm_binary")]
-fn msm_binary<V: VariableBaseMSM>(bases: &[V::MulBase], scalars: &[u64]) -> V {
+fn msm_binary<V: VariableBaseMSM, S: SmallScalar>(bases: &[V::MulBase], scalars: &[S]) -> V {
     scalars
         .iter()
         .zip(bases)
-        .filter(|(&scalar, _base)| scalar != 0)
+        .filter(|(&scalar, _base)| scalar.to_u64() != 0)
         .map(|(_scalar, base)| base)
         .fold(V::zero(), |sum, base| sum + base)
 }
 
 #[tracing::instrument(skip_all, name = "msm_small")]
-fn msm_small<V: VariableBaseMSM>(bases: &[V::MulBase], scalars: &[u64], max_num_bits: usize) -> V {
+fn msm_small<V: VariableBaseMSM, S: SmallScalar>(
+    bases: &[V::MulBase],
+    scalars: &[S],
+    max_num_bits: usize,
+) -> V {
     let num_buckets: usize = 1 << max_num_bits;
     // Assign things to buckets based on the scalar
     let mut buckets: Vec<V> = vec![V::zero(); num_buckets];
     scalars
         .iter()
+        .map(|&scalar| scalar.to_u64())
         .zip(bases)
-        .filter(|(&scalar, _base)| scalar != 0)
-        .for_each(|(&scalar, base)| {
+        .filter(|(scalar, _base)| *scalar != 0)
+        .for_each(|(scalar, base)| {
             buckets[scalar as usize] += base;
         });
 
@@ -480,3 +565,61 @@ fn ln_without_floats(a: usize) -> usize {
     // log2(a) * ln(2)
     (ark_std::log2(a) * 69 / 100) as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::{test_rng, UniformRand};
+    use rand_core::RngCore;
+
+    fn check_small_scalars<S: SmallScalar>(
+        bases: &[<G1Projective as ScalarMul>::MulBase],
+        table: &FixedBaseMSMTable<G1Projective>,
+        scalars: &[S],
+    ) {
+        let field_scalars: Vec<Fr> = scalars.iter().map(|s| Fr::from(s.to_u64())).collect();
+        let expected = <G1Projective as VariableBaseMSM>::msm(bases, &field_scalars).unwrap();
+        assert_eq!(
+            G1Projective::msm_small_scalars(bases, scalars).unwrap(),
+            expected
+        );
+        assert_eq!(
+            G1Projective::msm_precomputed(bases, &field_scalars, table, 0).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn small_scalar_msm_matches_field_msm() {
+        let mut rng = test_rng();
+        let n = 100;
+        let bases = G1Projective::normalize_batch(
+            &(0..n)
+                .map(|_| G1Projective::rand(&mut rng))
+                .collect::<Vec<_>>(),
+        );
+        let table = FixedBaseMSMTable::new(
+            &bases,
+            FixedBaseMSMTable::<G1Projective>::DEFAULT_NUM_WINDOWS,
+        );
+
+        // Mostly zeros, as for one-hot flags
+        let flags: Vec<bool> = (0..n).map(|i| i % 7 == 0).collect();
+        check_small_scalars(&bases, &table, &flags);
+        check_small_scalars(&bases, &table, &vec![false; n]);
+        let chunks: Vec<u8> = (0..n).map(|_| rng.next_u32() as u8).collect();
+        check_small_scalars(&bases, &table, &chunks);
+        let chunks: Vec<u16> = (0..n).map(|_| rng.next_u32() as u16).collect();
+        check_small_scalars(&bases, &table, &chunks);
+        let timestamps: Vec<u32> = (0..n).map(|_| rng.next_u32()).collect();
+        check_small_scalars(&bases, &table, &timestamps);
+        let values: Vec<u64> = (0..n).map(|_| rng.next_u64()).collect();
+        check_small_scalars(&bases, &table, &values);
+
+        assert_eq!(
+            G1Projective::msm_small_scalars(&bases, &flags[1..]),
+            Err(n - 1)
+        );
+    }
+}
@@ -1,104 +1,225 @@
-#![allow(dead_code)]
-
-use crate::poly::commitment::commitment_scheme::BatchType;
-use crate::poly::commitment::commitment_scheme::CommitShape;
-use crate::poly::commitment::commitment_scheme::CommitmentScheme;
-use crate::poly::dense_mlpoly::DensePolynomial;
-use crate::utils::errors::ProofVerifyError;
-use crate::utils::transcript::{AppendToTranscript, Transcript};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use std::marker::PhantomData;
-
-#[derive(Clone)]
-pub struct Binius128Scheme<ProofTranscript: Transcript> {
-    _phantom: PhantomData<ProofTranscript>,
-}
+use binius_field::BinaryField128bPolyval;
 
-#[derive(Default, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
-pub struct BiniusCommitment {}
+use super::ligero::{Ligero, ReedSolomonField};
+use crate::field::binius::{BiniusField, BiniusSpecific};
+use crate::field::JoltField;
 
-impl AppendToTranscript for BiniusCommitment {
-    fn append_to_transcript<ProofTranscript: Transcript>(&self, _transcript: &mut ProofTranscript) {
-        todo!()
-    }
-}
+/// A transparent, hash-based commitment scheme over the 128-bit binary field, with rows
+/// encoded by the additive NTT of [`AdditiveNtt`].
+pub type Binius128Scheme<ProofTranscript> =
+    Ligero<BiniusField<BinaryField128bPolyval>, ProofTranscript>;
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
-pub struct BiniusProof {}
+/// The additive NTT of Lin, Chung and Han (https://arxiv.org/abs/1404.3458), which
+/// Reed–Solomon encodes a message over binary field points `from_u64(0..n)`.
+///
+/// The message holds the coefficients of a polynomial of degree less than `2^log_len` in
+/// the "novel" basis `X_j(x) = \prod_{i in bits(j)} \hat{W}_i(x)`, where `\hat{W}_i` is the
+/// (F_2-linear) vanishing polynomial of `span(from_u64(1), ..., from_u64(2^{i - 1}))`
+/// normalized so that `\hat{W}_i(from_u64(2^i)) = 1`.
+pub struct AdditiveNtt<F: JoltField> {
+    log_len: usize,
+    /// `twiddles[c][i][j] = \hat{W}_i(from_u64((c << log_len) | (j << (i + 1))))`: the
+    /// twiddles of layer `i` of the butterfly network evaluating over coset `c`.
+    twiddles: Vec<Vec<Vec<F>>>,
+}
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
-pub struct BiniusBatchedProof {}
+impl<F: JoltField> AdditiveNtt<F> {
+    pub fn new(log_len: usize, log_blowup: usize) -> Self {
+        let num_bits = log_len + log_blowup;
+        assert!(num_bits < 64);
+        let basis: Vec<F> = (0..num_bits)
+            .map(|k| F::from_u64(1 << k).unwrap())
+            .collect();
 
-#[derive(Clone)]
-pub struct None {}
+        // normalized_subspace_evals[i][k] = \hat{W}_i(from_u64(2^k)), computed from the
+        // unnormalized W_{i + 1}(x) = W_i(x) * (W_i(x) + W_i(from_u64(2^i)))
+        let mut normalized_subspace_evals = Vec::with_capacity(log_len);
+        let mut subspace_evals = basis;
+        for i in 0..log_len {
+            let normalizer = subspace_evals[i].inverse().unwrap();
+            normalized_subspace_evals.push(
+                subspace_evals
+                    .iter()
+                    .map(|eval| *eval * normalizer)
+                    .collect::<Vec<_>>(),
+            );
+            let pivot = subspace_evals[i];
+            subspace_evals = subspace_evals
+                .iter()
+                .map(|eval| *eval * (*eval + pivot))
+                .collect();
+        }
 
-impl<ProofTranscript: Transcript> CommitmentScheme<ProofTranscript>
-    for Binius128Scheme<ProofTranscript>
-{
-    type Field = crate::field::binius::BiniusField<binius_field::BinaryField128bPolyval>;
-    type Setup = None;
-    type Commitment = BiniusCommitment;
-    type Proof = BiniusProof;
-    type BatchedProof = BiniusBatchedProof;
+        let twiddles: Vec<Vec<Vec<F>>> = (0..1u64 << log_blowup)
+            .map(|coset| {
+                (0..log_len)
+                    .map(|i| {
+                        (0..1u64 << (log_len - 1 - i))
+                            .map(|j| {
+                                // \hat{W}_i is linear, so it is the sum of its evaluations at
+                                // the basis elements in the point
+                                let point = (coset << log_len) | (j << (i + 1));
+                                (0..num_bits)
+                                    .filter(|k| (point >> k) & 1 == 1)
+                                    .map(|k| normalized_subspace_evals[i][k])
+                                    .sum::<F>()
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
 
-    fn setup(_shapes: &[CommitShape]) -> Self::Setup {
-        None {}
-    }
-    fn commit(_poly: &DensePolynomial<Self::Field>, _setup: &Self::Setup) -> Self::Commitment {
-        todo!()
+        Self { log_len, twiddles }
     }
-    fn batch_commit(
-        _evals: &[&[Self::Field]],
-        _gens: &Self::Setup,
-        _batch_type: BatchType,
-    ) -> Vec<Self::Commitment> {
-        todo!()
+
+    /// Returns the evaluations of the polynomial with novel-basis coefficients `message` at
+    /// `from_u64(0..(message.len() << log_blowup))`.
+    pub fn encode(&self, message: &[F]) -> Vec<F> {
+        assert_eq!(message.len(), 1 << self.log_len);
+        let mut codeword = Vec::with_capacity(message.len() * self.twiddles.len());
+        for coset_twiddles in &self.twiddles {
+            let mut evals = message.to_vec();
+            for i in (0..self.log_len).rev() {
+                for (j, twiddle) in coset_twiddles[i].iter().enumerate() {
+                    for k in 0..1 << i {
+                        let index_0 = (j << (i + 1)) | k;
+                        let index_1 = index_0 | (1 << i);
+                        evals[index_0] = evals[index_0] + evals[index_1] * twiddle;
+                        evals[index_1] = evals[index_1] + evals[index_0];
+                    }
+                }
+            }
+            codeword.extend(evals);
+        }
+        codeword
     }
-    fn commit_slice(_evals: &[Self::Field], _setup: &Self::Setup) -> Self::Commitment {
-        todo!()
+}
+
+impl<F: BiniusSpecific> ReedSolomonField for BiniusField<F> {
+    type Encoder = AdditiveNtt<Self>;
+
+    fn encoder(log_message_len: usize, log_blowup: usize) -> Self::Encoder {
+        AdditiveNtt::new(log_message_len, log_blowup)
     }
-    fn prove(
-        _none: &Self::Setup,
-        _poly: &DensePolynomial<Self::Field>,
-        _opening_point: &[Self::Field],
-        _transcript: &mut ProofTranscript,
-    ) -> Self::Proof {
-        todo!()
+
+    fn encode(encoder: &Self::Encoder, message: &[Self]) -> Vec<Self> {
+        encoder.encode(message)
     }
-    fn batch_prove(
-        _none: &Self::Setup,
-        _polynomials: &[&DensePolynomial<Self::Field>],
-        _opening_point: &[Self::Field],
-        _openings: &[Self::Field],
-        _batch_type: BatchType,
-        _transcript: &mut ProofTranscript,
-    ) -> Self::BatchedProof {
-        todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::commitment_scheme::{BatchType, CommitmentScheme};
+    use crate::poly::dense_mlpoly::DensePolynomial;
+    use crate::utils::transcript::{KeccakTranscript, Transcript};
+    use ark_std::test_rng;
+
+    type F = BiniusField<BinaryField128bPolyval>;
+    type Scheme = Binius128Scheme<KeccakTranscript>;
+
+    /// Evaluates the polynomial with novel-basis coefficients `message` at `x` directly
+    /// from the definition of the basis.
+    fn novel_basis_eval(message: &[F], x: F) -> F {
+        // W_i(x) is the product of (x - u) over u in span(from_u64(1), ..., from_u64(2^{i - 1}))
+        let subspace_poly = |i: usize, x: F| -> F {
+            (0..1u64 << i)
+                .map(|u| x - F::from_u64(u).unwrap())
+                .product()
+        };
+        let normalized_subspace_poly =
+            |i: usize, x: F| subspace_poly(i, x) / subspace_poly(i, F::from_u64(1 << i).unwrap());
+
+        message
+            .iter()
+            .enumerate()
+            .map(|(j, coeff)| {
+                (0..usize::BITS as usize)
+                    .filter(|i| (j >> i) & 1 == 1)
+                    .map(|i| normalized_subspace_poly(i, x))
+                    .product::<F>()
+                    * coeff
+            })
+            .sum()
     }
 
-    fn verify(
-        _proof: &Self::Proof,
-        _setup: &Self::Setup,
-        _transcript: &mut ProofTranscript,
-        _opening_point: &[Self::Field],
-        _opening: &Self::Field,
-        _commitment: &Self::Commitment,
-    ) -> Result<(), ProofVerifyError> {
-        todo!()
+    #[test]
+    fn additive_ntt_matches_novel_basis() {
+        let mut rng = test_rng();
+        for log_len in 0..5 {
+            let message: Vec<F> = (0..1 << log_len).map(|_| F::random(&mut rng)).collect();
+            let codeword = AdditiveNtt::new(log_len, 2).encode(&message);
+            assert_eq!(codeword.len(), message.len() << 2);
+            for (x, eval) in codeword.iter().enumerate() {
+                assert_eq!(
+                    *eval,
+                    novel_basis_eval(&message, F::from_u64(x as u64).unwrap())
+                );
+            }
+        }
     }
 
-    fn batch_verify(
-        _batch_proof: &Self::BatchedProof,
-        _setup: &Self::Setup,
-        _opening_point: &[Self::Field],
-        _openings: &[Self::Field],
-        _commitments: &[&Self::Commitment],
-        _transcript: &mut ProofTranscript,
-    ) -> Result<(), ProofVerifyError> {
-        todo!()
+    #[test]
+    fn commit_prove_verify() {
+        super::super::ligero::tests::commit_prove_verify::<F>();
     }
 
-    fn protocol_name() -> &'static [u8] {
-        b"binius_commit"
+    #[test]
+    fn batch_prove_verify() {
+        let mut rng = test_rng();
+        let num_vars = 6;
+        let polys: Vec<DensePolynomial<F>> = (0..3)
+            .map(|_| {
+                DensePolynomial::new(
+                    (0..1 << num_vars)
+                        .map(|_| F::random(&mut rng))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        let poly_refs: Vec<&DensePolynomial<F>> = polys.iter().collect();
+        let point: Vec<F> = (0..num_vars).map(|_| F::random(&mut rng)).collect();
+        let evals: Vec<F> = polys.iter().map(|poly| poly.evaluate(&point)).collect();
+        let slices: Vec<&[F]> = polys.iter().map(|poly| poly.evals_ref()).collect();
+        let (commitments, hints) = Scheme::batch_commit_with_hints(&slices, &(), BatchType::Big);
+        let commitment_refs: Vec<_> = commitments.iter().collect();
+        let hint_refs: Vec<_> = hints.iter().collect();
+
+        let mut prover_transcript = KeccakTranscript::new(b"test_transcript");
+        let proof = Scheme::batch_prove_with_hints(
+            &(),
+            &poly_refs,
+            &hint_refs,
+            &point,
+            &evals,
+            BatchType::Big,
+            &mut prover_transcript,
+        );
+
+        let mut verifier_transcript = KeccakTranscript::new(b"test_transcript");
+        verifier_transcript.compare_to(prover_transcript);
+        Scheme::batch_verify(
+            &proof,
+            &(),
+            &point,
+            &evals,
+            &commitment_refs,
+            &mut verifier_transcript,
+        )
+        .unwrap();
+
+        // Swapping two commitments invalidates the proof
+        let swapped_commitment_refs = [commitment_refs[1], commitment_refs[0], commitment_refs[2]];
+        let mut verifier_transcript = KeccakTranscript::new(b"test_transcript");
+        assert!(Scheme::batch_verify(
+            &proof,
+            &(),
+            &point,
+            &evals,
+            &swapped_commitment_refs,
+            &mut verifier_transcript,
+        )
+        .is_err());
     }
 }
@@ -1,11 +1,16 @@
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rayon::prelude::*;
 use std::fmt::Debug;
 
 use crate::utils::transcript::Transcript;
 use crate::{
     field::JoltField,
+    msm::SmallScalar,
     poly::dense_mlpoly::DensePolynomial,
-    utils::{errors::ProofVerifyError, transcript::AppendToTranscript},
+    utils::{
+        errors::{ProofVerifyError, SRSError},
+        transcript::AppendToTranscript,
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -34,8 +39,14 @@ pub enum BatchType {
 
 pub trait CommitmentScheme<ProofTranscript: Transcript>: Clone + Sync + Send + 'static {
     type Field: JoltField + Sized;
-    type Setup: Clone + Sync + Send;
+    /// Public parameters a `Setup` can be derived from, e.g. the powers of tau of a trusted
+    /// setup ceremony. `()` for schemes with a transparent setup.
+    type SRS: Sync + Send;
+    type Setup: Clone + Sync + Send + CanonicalSerialize + CanonicalDeserialize;
+    /// The part of `Setup` needed to verify opening proofs.
+    type VerifierSetup: Clone + Sync + Send + CanonicalSerialize + CanonicalDeserialize;
     type Commitment: Default
+        + Clone
         + Debug
         + Sync
         + Send
@@ -45,8 +56,38 @@ pub trait CommitmentScheme<ProofTranscript: Transcript>: Clone + Sync + Send + '
         + AppendToTranscript;
     type Proof: Sync + Send + CanonicalSerialize + CanonicalDeserialize;
     type BatchedProof: Sync + Send + CanonicalSerialize + CanonicalDeserialize;
+    /// Data computed by the prover when committing to a polynomial that proofs of its
+    /// openings can reuse, e.g. the encoded rows of a code-based scheme. It is held by the
+    /// prover for the current proof only. `()` for schemes with nothing to reuse.
+    type OpeningHint: Default + Sync + Send + 'static;
+    /// How the openings accumulated over a proof are proven. [`AdditivelyHomomorphic`]
+    /// schemes should use [`OpeningReduction::sumcheck`].
+    const OPENING_REDUCTION: OpeningReduction<Self, ProofTranscript>;
 
-    fn setup(shapes: &[CommitShape]) -> Self::Setup;
+    /// Derives an SRS supporting `shapes` from a fixed, public seed. Anyone can recover its
+    /// trapdoor and forge proofs, so it is only available in tests and with the
+    /// `insecure-setup` feature.
+    #[cfg(any(test, feature = "insecure-setup"))]
+    fn insecure_srs(shapes: &[CommitShape]) -> Self::SRS;
+    /// Derives a setup supporting `shapes` from [`CommitmentScheme::insecure_srs`]. Use
+    /// [`CommitmentScheme::setup_from_srs`] for production proofs.
+    #[cfg(any(test, feature = "insecure-setup"))]
+    fn setup(shapes: &[CommitShape]) -> Self::Setup {
+        Self::setup_from_srs(&Self::insecure_srs(shapes), shapes)
+            .expect("the insecure SRS supports the shapes it was derived for")
+    }
+    /// Derives a setup supporting `shapes` from an externally provided SRS, trimmed to the
+    /// largest shape.
+    fn setup_from_srs(srs: &Self::SRS, shapes: &[CommitShape]) -> Result<Self::Setup, SRSError>;
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup;
+    /// Precomputes [`FixedBaseMSMTable`](crate::msm::FixedBaseMSMTable)s with `num_windows`
+    /// windows of the bases used for committing. They speed up commitments to polynomials with
+    /// large coefficients, but take `num_windows` times the memory of the bases, so setups
+    /// don't include them unless this is called. Schemes without such tables return `setup`
+    /// unchanged.
+    fn with_msm_tables(setup: Self::Setup, _num_windows: usize) -> Self::Setup {
+        setup
+    }
     fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment;
     fn batch_commit(
         evals: &[&[Self::Field]],
@@ -70,14 +111,61 @@ pub trait CommitmentScheme<ProofTranscript: Transcript>: Clone + Sync + Send + '
         let slices: Vec<&[Self::Field]> = polys.iter().map(|poly| poly.evals_ref()).collect();
         Self::batch_commit(&slices, setup, batch_type)
     }
-
-    /// Homomorphically combines multiple commitments into a single commitment, computed as a
-    /// linear combination with the given coefficients.
-    fn combine_commitments(
-        _commitments: &[&Self::Commitment],
-        _coeffs: &[Self::Field],
-    ) -> Self::Commitment {
-        todo!("`combine_commitments` should be on a separate `AdditivelyHomomorphic` trait")
+    /// Commits to evaluations given as small integers or bits, e.g. flags, chunk indices or
+    /// timestamps. The commitment is the same as to the evaluations as field elements, which
+    /// the default implementation converts them to.
+    fn commit_small<S: SmallScalar>(evals: &[S], setup: &Self::Setup) -> Self::Commitment {
+        Self::commit_slice(&small_scalars_to_field(evals), setup)
+    }
+    /// Like [`CommitmentScheme::batch_commit`], for evaluations given as small integers or
+    /// bits.
+    fn batch_commit_small<S: SmallScalar>(
+        evals: &[&[S]],
+        gens: &Self::Setup,
+        batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        let evals: Vec<Vec<Self::Field>> = evals
+            .iter()
+            .map(|evals| small_scalars_to_field(evals))
+            .collect();
+        let slices: Vec<&[Self::Field]> = evals.iter().map(|evals| evals.as_slice()).collect();
+        Self::batch_commit(&slices, gens, batch_type)
+    }
+    /// Like [`CommitmentScheme::commit`], also returning the polynomial's
+    /// [`CommitmentScheme::OpeningHint`].
+    fn commit_with_hint(
+        poly: &DensePolynomial<Self::Field>,
+        setup: &Self::Setup,
+    ) -> (Self::Commitment, Self::OpeningHint) {
+        (Self::commit(poly, setup), Self::OpeningHint::default())
+    }
+    /// Like [`CommitmentScheme::batch_commit`], also returning each polynomial's
+    /// [`CommitmentScheme::OpeningHint`].
+    fn batch_commit_with_hints(
+        evals: &[&[Self::Field]],
+        gens: &Self::Setup,
+        batch_type: BatchType,
+    ) -> (Vec<Self::Commitment>, Vec<Self::OpeningHint>) {
+        let commitments = Self::batch_commit(evals, gens, batch_type);
+        let hints = commitments
+            .iter()
+            .map(|_| Self::OpeningHint::default())
+            .collect();
+        (commitments, hints)
+    }
+    /// Like [`CommitmentScheme::batch_commit_small`], also returning each polynomial's
+    /// [`CommitmentScheme::OpeningHint`].
+    fn batch_commit_small_with_hints<S: SmallScalar>(
+        evals: &[&[S]],
+        gens: &Self::Setup,
+        batch_type: BatchType,
+    ) -> (Vec<Self::Commitment>, Vec<Self::OpeningHint>) {
+        let commitments = Self::batch_commit_small(evals, gens, batch_type);
+        let hints = commitments
+            .iter()
+            .map(|_| Self::OpeningHint::default())
+            .collect();
+        (commitments, hints)
     }
 
     fn prove(
@@ -94,10 +182,30 @@ pub trait CommitmentScheme<ProofTranscript: Transcript>: Clone + Sync + Send + '
         batch_type: BatchType,
         transcript: &mut ProofTranscript,
     ) -> Self::BatchedProof;
+    /// Like [`CommitmentScheme::batch_prove`], reusing the `hints` returned when committing
+    /// to `polynomials`. A default hint means there is nothing to reuse.
+    fn batch_prove_with_hints(
+        setup: &Self::Setup,
+        polynomials: &[&DensePolynomial<Self::Field>],
+        _hints: &[&Self::OpeningHint],
+        opening_point: &[Self::Field],
+        openings: &[Self::Field],
+        batch_type: BatchType,
+        transcript: &mut ProofTranscript,
+    ) -> Self::BatchedProof {
+        Self::batch_prove(
+            setup,
+            polynomials,
+            opening_point,
+            openings,
+            batch_type,
+            transcript,
+        )
+    }
 
     fn verify(
         proof: &Self::Proof,
-        setup: &Self::Setup,
+        setup: &Self::VerifierSetup,
         transcript: &mut ProofTranscript,
         opening_point: &[Self::Field], // point at which the polynomial is evaluated
         opening: &Self::Field,         // evaluation \widetilde{Z}(r)
@@ -106,7 +214,7 @@ pub trait CommitmentScheme<ProofTranscript: Transcript>: Clone + Sync + Send + '
 
     fn batch_verify(
         batch_proof: &Self::BatchedProof,
-        setup: &Self::Setup,
+        setup: &Self::VerifierSetup,
         opening_point: &[Self::Field],
         openings: &[Self::Field],
         commitments: &[&Self::Commitment],
@@ -115,3 +223,59 @@ pub trait CommitmentScheme<ProofTranscript: Transcript>: Clone + Sync + Send + '
 
     fn protocol_name() -> &'static [u8];
 }
+
+pub(crate) fn small_scalars_to_field<F: JoltField, S: SmallScalar>(evals: &[S]) -> Vec<F> {
+    evals
+        .par_iter()
+        .map(|eval| F::from_u64(eval.to_u64()).unwrap())
+        .collect()
+}
+
+/// A commitment scheme whose commitments can be combined into a commitment to the same
+/// linear combination of the committed polynomials.
+pub trait AdditivelyHomomorphic<ProofTranscript: Transcript>:
+    CommitmentScheme<ProofTranscript>
+{
+    /// Homomorphically combines multiple commitments into a single commitment, computed as a
+    /// linear combination with the given coefficients.
+    fn combine_commitments(
+        commitments: &[&Self::Commitment],
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment;
+}
+
+/// How the openings accumulated over a proof (see [`crate::poly::opening_proof`]) are
+/// proven.
+pub enum OpeningReduction<PCS, ProofTranscript>
+where
+    PCS: CommitmentScheme<ProofTranscript>,
+    ProofTranscript: Transcript,
+{
+    /// Each batch of polynomials opened at the same point is proven separately with
+    /// [`CommitmentScheme::batch_prove`]. Works with any scheme, at the cost of one opening
+    /// proof per opening point.
+    BatchOpenings,
+    /// A sumcheck reduces all openings to a single opening of a random linear combination
+    /// of the polynomials, whose commitment the verifier computes with
+    /// `combine_commitments`.
+    Sumcheck {
+        combine_commitments: fn(&[&PCS::Commitment], &[PCS::Field]) -> PCS::Commitment,
+    },
+}
+
+impl<PCS, ProofTranscript> OpeningReduction<PCS, ProofTranscript>
+where
+    PCS: CommitmentScheme<ProofTranscript>,
+    ProofTranscript: Transcript,
+{
+    /// [`OpeningReduction::Sumcheck`], combining commitments with
+    /// [`AdditivelyHomomorphic::combine_commitments`].
+    pub const fn sumcheck() -> Self
+    where
+        PCS: AdditivelyHomomorphic<ProofTranscript>,
+    {
+        Self::Sumcheck {
+            combine_commitments: PCS::combine_commitments,
+        }
+    }
+}
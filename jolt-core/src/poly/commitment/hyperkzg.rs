@@ -8,7 +8,7 @@
 //! (2) HyperKZG is specialized to use KZG as the univariate commitment scheme, so it includes several optimizations (both during the transformation of multilinear-to-univariate claims
 //! and within the KZG commitment scheme implementation itself).
 use super::{
-    commitment_scheme::{BatchType, CommitmentScheme},
+    commitment_scheme::{AdditivelyHomomorphic, BatchType, CommitmentScheme, OpeningReduction},
     kzg,
     kzg::{KZGProverKey, KZGVerifierKey, UnivariateKZG},
 };
@@ -18,15 +18,21 @@ use crate::utils::mul_0_1_optimized;
 use crate::utils::thread::unsafe_allocate_zero_vec;
 use crate::utils::transcript::Transcript;
 use crate::{
-    msm::VariableBaseMSM,
+    msm::{SmallScalar, VariableBaseMSM},
     poly::{commitment::kzg::SRS, dense_mlpoly::DensePolynomial, unipoly::UniPoly},
-    utils::{errors::ProofVerifyError, transcript::AppendToTranscript},
+    utils::{
+        errors::{ProofVerifyError, SRSError},
+        transcript::AppendToTranscript,
+    },
 };
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{One, Zero};
+#[cfg(any(test, feature = "insecure-setup"))]
 use rand_chacha::ChaCha20Rng;
-use rand_core::{CryptoRng, RngCore, SeedableRng};
+#[cfg(any(test, feature = "insecure-setup"))]
+use rand_core::SeedableRng;
+use rand_core::{CryptoRng, RngCore};
 use rayon::iter::{
     IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
     IntoParallelRefMutIterator, ParallelIterator,
@@ -41,23 +47,50 @@ impl<P: Pairing> HyperKZGSRS<P> {
         Self(Arc::new(SRS::setup(rng, max_degree, 2)))
     }
 
+    /// Derives the SRS from a fixed, public seed. Anyone can recover its trapdoor and forge
+    /// proofs, so this is only available in tests and with the `insecure-setup` feature.
+    #[cfg(any(test, feature = "insecure-setup"))]
+    pub fn insecure_setup(max_degree: usize) -> Self {
+        Self::setup(&mut Self::insecure_rng(), max_degree)
+    }
+
+    /// The verifier key of [`HyperKZGSRS::insecure_setup`], which is cheap to derive since
+    /// it does not depend on the maximum degree.
+    #[cfg(any(test, feature = "insecure-setup"))]
+    pub fn insecure_verifier_key() -> HyperKZGVerifierKey<P> {
+        HyperKZGVerifierKey {
+            kzg_vk: SRS::setup_verifier_key(&mut Self::insecure_rng()),
+        }
+    }
+
+    #[cfg(any(test, feature = "insecure-setup"))]
+    fn insecure_rng() -> ChaCha20Rng {
+        ChaCha20Rng::from_seed(*b"HyperKZG_POLY_COMMITMENT_SCHEMEE")
+    }
+
+    /// Uses the powers of an externally generated SRS, e.g. one loaded with
+    /// [`SRS::from_ptau_file`], supporting polynomials of degree up to `max_degree`.
+    pub fn from_srs(srs: &SRS<P>, max_degree: usize) -> Result<Self, SRSError> {
+        Ok(Self(Arc::new(srs.truncate(max_degree + 1, 2)?)))
+    }
+
     pub fn trim(self, max_degree: usize) -> (HyperKZGProverKey<P>, HyperKZGVerifierKey<P>) {
         let (kzg_pk, kzg_vk) = SRS::trim(self.0, max_degree);
         (HyperKZGProverKey { kzg_pk }, HyperKZGVerifierKey { kzg_vk })
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct HyperKZGProverKey<P: Pairing> {
     pub kzg_pk: KZGProverKey<P>,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct HyperKZGVerifierKey<P: Pairing> {
     pub kzg_vk: KZGVerifierKey<P>,
 }
 
-#[derive(Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct HyperKZGCommitment<P: Pairing>(pub P::G1Affine);
 
 impl<P: Pairing> Default for HyperKZGCommitment<P> {
@@ -290,6 +323,22 @@ where
         b"HyperKZG"
     }
 
+    /// The number of G1 and G2 powers of tau an SRS needs for
+    /// [`CommitmentScheme::setup_from_srs`] to support `shapes`.
+    pub fn srs_size(shapes: &[CommitShape]) -> (usize, usize) {
+        // Polynomials of up to `max_len` coefficients are committed to, and only the first
+        // two G2 powers are used
+        let max_len = shapes.iter().map(|shape| shape.input_length).max().unwrap();
+        (max_len + 1, 2)
+    }
+
+    /// The verifier setup derived by [`CommitmentScheme::setup`], computed without the
+    /// prover's powers.
+    #[cfg(any(test, feature = "insecure-setup"))]
+    pub fn insecure_verifier_setup() -> HyperKZGVerifierKey<P> {
+        HyperKZGSRS::insecure_verifier_key()
+    }
+
     pub fn commit(
         pp: &HyperKZGProverKey<P>,
         poly: &DensePolynomial<P::ScalarField>,
@@ -446,7 +495,8 @@ where
 
         let span = trace_span!("f_batched");
         let enter = span.enter();
-        let num_chunks = rayon::current_num_threads().next_power_of_two();
+        // Only use as many chunks as there are threads, or the total number of evaluations
+        let num_chunks = rayon::current_num_threads().next_power_of_two().min(n);
         let chunk_size = n / num_chunks;
         let f_batched = (0..num_chunks)
             .into_par_iter()
@@ -510,20 +560,33 @@ where
     <P as Pairing>::ScalarField: field::JoltField,
 {
     type Field = P::ScalarField;
+    type SRS = SRS<P>;
     type Setup = (HyperKZGProverKey<P>, HyperKZGVerifierKey<P>);
+    type VerifierSetup = HyperKZGVerifierKey<P>;
     type Commitment = HyperKZGCommitment<P>;
     type Proof = HyperKZGProof<P>;
     type BatchedProof = HyperKZGProof<P>;
+    type OpeningHint = ();
+    const OPENING_REDUCTION: OpeningReduction<Self, ProofTranscript> = OpeningReduction::sumcheck();
 
-    fn setup(shapes: &[CommitShape]) -> Self::Setup {
+    #[cfg(any(test, feature = "insecure-setup"))]
+    fn insecure_srs(shapes: &[CommitShape]) -> Self::SRS {
         let max_len = shapes.iter().map(|shape| shape.input_length).max().unwrap();
+        SRS::setup(&mut HyperKZGSRS::<P>::insecure_rng(), max_len, 2)
+    }
 
-        HyperKZGSRS(Arc::new(SRS::setup(
-            &mut ChaCha20Rng::from_seed(*b"HyperKZG_POLY_COMMITMENT_SCHEMEE"),
-            max_len,
-            2,
-        )))
-        .trim(max_len)
+    fn setup_from_srs(srs: &Self::SRS, shapes: &[CommitShape]) -> Result<Self::Setup, SRSError> {
+        let max_len = shapes.iter().map(|shape| shape.input_length).max().unwrap();
+        Ok(HyperKZGSRS::from_srs(srs, max_len)?.trim(max_len))
+    }
+
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup {
+        setup.1
+    }
+    fn with_msm_tables(setup: Self::Setup, num_windows: usize) -> Self::Setup {
+        let (pk, vk) = setup;
+        let kzg_pk = pk.kzg_pk.with_msm_table(num_windows);
+        (HyperKZGProverKey { kzg_pk }, vk)
     }
 
     fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
@@ -572,6 +635,23 @@ where
         HyperKZGCommitment(UnivariateKZG::commit_slice(&setup.0.kzg_pk, evals).unwrap())
     }
 
+    fn commit_small<S: SmallScalar>(evals: &[S], setup: &Self::Setup) -> Self::Commitment {
+        HyperKZGCommitment(UnivariateKZG::commit_small(&setup.0.kzg_pk, evals).unwrap())
+    }
+
+    fn batch_commit_small<S: SmallScalar>(
+        evals: &[&[S]],
+        gens: &Self::Setup,
+        _batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        evals
+            .par_iter()
+            .map(|evals| {
+                HyperKZGCommitment(UnivariateKZG::commit_small(&gens.0.kzg_pk, evals).unwrap())
+            })
+            .collect()
+    }
+
     fn prove(
         setup: &Self::Setup,
         poly: &DensePolynomial<Self::Field>,
@@ -600,28 +680,16 @@ where
         )
     }
 
-    fn combine_commitments(
-        commitments: &[&Self::Commitment],
-        coeffs: &[Self::Field],
-    ) -> Self::Commitment {
-        let combined_commitment: P::G1 = commitments
-            .iter()
-            .zip(coeffs.iter())
-            .map(|(commitment, coeff)| commitment.0 * coeff)
-            .sum();
-        HyperKZGCommitment(combined_commitment.into_affine())
-    }
-
     fn verify(
         proof: &Self::Proof,
-        setup: &Self::Setup,
+        setup: &Self::VerifierSetup,
         transcript: &mut ProofTranscript,
         opening_point: &[Self::Field], // point at which the polynomial is evaluated
         opening: &Self::Field,         // evaluation \widetilde{Z}(r)
         commitment: &Self::Commitment,
     ) -> Result<(), ProofVerifyError> {
         HyperKZG::<P, ProofTranscript>::verify(
-            &setup.1,
+            setup,
             commitment,
             opening_point,
             opening,
@@ -632,14 +700,14 @@ where
 
     fn batch_verify(
         batch_proof: &Self::BatchedProof,
-        setup: &Self::Setup,
+        setup: &Self::VerifierSetup,
         opening_point: &[Self::Field],
         openings: &[Self::Field],
         commitments: &[&Self::Commitment],
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         HyperKZG::<P, ProofTranscript>::batch_verify(
-            &setup.1,
+            setup,
             commitments,
             opening_point,
             openings,
@@ -653,11 +721,32 @@ where
     }
 }
 
+impl<P: Pairing, ProofTranscript: Transcript> AdditivelyHomomorphic<ProofTranscript>
+    for HyperKZG<P, ProofTranscript>
+where
+    <P as Pairing>::ScalarField: field::JoltField,
+{
+    fn combine_commitments(
+        commitments: &[&Self::Commitment],
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment {
+        let combined_commitment: P::G1 = commitments
+            .iter()
+            .zip(coeffs.iter())
+            .map(|(commitment, coeff)| commitment.0 * coeff)
+            .sum();
+        HyperKZGCommitment(combined_commitment.into_affine())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::msm::FixedBaseMSMTable;
     use crate::utils::transcript::{KeccakTranscript, Transcript};
-    use ark_bn254::{Bn254, Fr};
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::{Bn254, Fr, G1Projective};
+    use ark_serialize::SerializationError;
     use ark_std::UniformRand;
     use rand_core::SeedableRng;
 
@@ -712,6 +801,130 @@ mod tests {
         assert!(test_inner(point, eval).is_err());
     }
 
+    #[test]
+    fn test_hyperkzg_setup_from_srs() {
+        type PCS = HyperKZG<Bn254, KeccakTranscript>;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let srs = SRS::<Bn254>::setup(&mut rng, 63, 2);
+        let srs =
+            SRS::<Bn254>::from_powers(srs.g1_powers.to_vec(), srs.g2_powers.to_vec()).unwrap();
+
+        let shapes = [CommitShape::new(16, BatchType::Big)];
+        let setup = PCS::setup_from_srs(&srs, &shapes).unwrap();
+        assert_eq!(setup.0.kzg_pk.g1_powers().len(), 17);
+
+        let poly = DensePolynomial::new((0..16).map(|_| Fr::rand(&mut rng)).collect());
+        let point: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+        let eval = poly.evaluate(&point);
+        let commitment = <PCS as CommitmentScheme<KeccakTranscript>>::commit(&poly, &setup);
+        let mut transcript = KeccakTranscript::new(b"TestEval");
+        let proof = PCS::prove(&setup, &poly, &point, &mut transcript);
+        let mut transcript = KeccakTranscript::new(b"TestEval");
+        <PCS as CommitmentScheme<KeccakTranscript>>::verify(
+            &proof,
+            &PCS::verifier_setup(&setup),
+            &mut transcript,
+            &point,
+            &eval,
+            &commitment,
+        )
+        .unwrap();
+
+        let shapes = [CommitShape::new(64, BatchType::Big)];
+        assert!(matches!(
+            PCS::setup_from_srs(&srs, &shapes),
+            Err(SRSError::TooFewPowers {
+                group: "G1",
+                required: 65,
+                available: 64
+            })
+        ));
+    }
+
+    #[test]
+    fn insecure_verifier_key_matches_setup() {
+        let (_, vk) = HyperKZGSRS::<Bn254>::insecure_setup(16).trim(16);
+        let expected = HyperKZGSRS::<Bn254>::insecure_verifier_key();
+        let mut vk_bytes = vec![];
+        vk.serialize_compressed(&mut vk_bytes).unwrap();
+        let mut expected_bytes = vec![];
+        expected.serialize_compressed(&mut expected_bytes).unwrap();
+        assert_eq!(vk_bytes, expected_bytes);
+    }
+
+    #[test]
+    fn test_hyperkzg_msm_table() {
+        type PCS = HyperKZG<Bn254, KeccakTranscript>;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let (plain_pk, vk) = HyperKZGSRS::<Bn254>::insecure_setup(64).trim(64);
+        // Tables are opt-in
+        assert!(plain_pk.kzg_pk.msm_table().is_none());
+        let num_windows = FixedBaseMSMTable::<G1Projective>::DEFAULT_NUM_WINDOWS;
+        let (pk, vk) = PCS::with_msm_tables((plain_pk.clone(), vk), num_windows);
+        assert_eq!(pk.kzg_pk.msm_table().unwrap().num_windows(), num_windows);
+
+        // The table is rebuilt, not serialized
+        let mut buf = vec![];
+        pk.serialize_compressed(&mut buf).unwrap();
+        let mut plain_buf = vec![];
+        plain_pk.serialize_compressed(&mut plain_buf).unwrap();
+        // Only the number of windows is stored
+        assert_eq!(buf.len(), plain_buf.len() + 8);
+        let deserialized_pk = HyperKZGProverKey::<Bn254>::deserialize_compressed(&*buf).unwrap();
+        assert!(deserialized_pk.kzg_pk.msm_table().is_some());
+
+        // A number of windows the table can't be built with is rejected rather than panicking
+        // or exhausting memory
+        let num_windows_offset = buf.len() - 8;
+        for num_windows in [0, u64::MAX] {
+            buf[num_windows_offset..].copy_from_slice(&num_windows.to_le_bytes());
+            assert!(matches!(
+                HyperKZGProverKey::<Bn254>::deserialize_compressed(&*buf),
+                Err(SerializationError::InvalidData)
+            ));
+        }
+
+        let polys: Vec<Vec<Fr>> = [64, 48, 3]
+            .iter()
+            .map(|&len| (0..len).map(|_| Fr::rand(&mut rng)).collect())
+            .collect();
+        let poly_refs: Vec<&[Fr]> = polys.iter().map(|poly| poly.as_slice()).collect();
+        let expected: Vec<_> = poly_refs
+            .iter()
+            .map(|poly| UnivariateKZG::commit_slice(&plain_pk.kzg_pk, poly).unwrap())
+            .collect();
+        for pk in [pk, deserialized_pk] {
+            let commitments = PCS::batch_commit(&poly_refs, &(pk, vk), BatchType::Big);
+            let commitments: Vec<_> = commitments.iter().map(|c| c.0).collect();
+            assert_eq!(commitments, expected);
+        }
+    }
+
+    #[test]
+    fn test_hyperkzg_commit_small() {
+        type PCS = HyperKZG<Bn254, KeccakTranscript>;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let setup = PCS::setup(&[CommitShape::new(64, BatchType::Big)]);
+        let flags: Vec<bool> = (0..64).map(|_| rng.next_u32() % 5 == 0).collect();
+        let timestamps: Vec<u32> = (0..48).map(|_| rng.next_u32()).collect();
+        let to_field = |evals: Vec<u64>| evals.into_iter().map(Fr::from).collect::<Vec<_>>();
+        let flag_evals = to_field(flags.iter().map(|&flag| flag as u64).collect());
+        let timestamp_evals = to_field(timestamps.iter().map(|&t| t as u64).collect());
+
+        assert_eq!(
+            PCS::commit_small(&flags, &setup),
+            PCS::commit_slice(&flag_evals, &setup)
+        );
+        assert_eq!(
+            PCS::batch_commit_small(&[&flags, &flags], &setup, BatchType::Big),
+            PCS::batch_commit(&[&flag_evals, &flag_evals], &setup, BatchType::Big)
+        );
+        assert_eq!(
+            PCS::batch_commit_small(&[timestamps.as_slice()], &setup, BatchType::Big),
+            PCS::batch_commit(&[timestamp_evals.as_slice()], &setup, BatchType::Big)
+        );
+    }
+
     #[test]
     fn test_hyperkzg_small() {
         let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
@@ -809,4 +1022,40 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_hyperkzg_bls12_381() {
+        type Fr = <Bls12_381 as Pairing>::ScalarField;
+
+        for ell in [2, 4, 6] {
+            let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(ell as u64);
+            let n = 1 << ell;
+
+            let poly = DensePolynomial::new((0..n).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>());
+            let point = (0..ell).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let eval = poly.evaluate(&point);
+
+            let srs = HyperKZGSRS::setup(&mut rng, n);
+            let (pk, vk): (HyperKZGProverKey<Bls12_381>, HyperKZGVerifierKey<Bls12_381>) =
+                srs.trim(n);
+            let C = HyperKZG::<_, KeccakTranscript>::commit(&pk, &poly).unwrap();
+
+            let mut prover_transcript = KeccakTranscript::new(b"TestEval");
+            let proof = HyperKZG::open(&pk, &poly, &point, &eval, &mut prover_transcript).unwrap();
+
+            let mut verifier_tr = KeccakTranscript::new(b"TestEval");
+            assert!(HyperKZG::verify(&vk, &C, &point, &eval, &proof, &mut verifier_tr).is_ok());
+
+            let mut verifier_tr = KeccakTranscript::new(b"TestEval");
+            assert!(HyperKZG::verify(
+                &vk,
+                &C,
+                &point,
+                &(eval + Fr::from(1)),
+                &proof,
+                &mut verifier_tr
+            )
+            .is_err());
+        }
+    }
 }
@@ -1,11 +1,13 @@
 use std::marker::PhantomData;
 
-use super::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
+use super::commitment_scheme::{
+    AdditivelyHomomorphic, BatchType, CommitShape, CommitmentScheme, OpeningReduction,
+};
 use super::pedersen::{PedersenCommitment, PedersenGenerators};
 use crate::field::JoltField;
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::eq_poly::EqPolynomial;
-use crate::utils::errors::ProofVerifyError;
+use crate::utils::errors::{ProofVerifyError, SRSError};
 use crate::utils::math::Math;
 use crate::utils::transcript::{AppendToTranscript, Transcript};
 use crate::utils::{compute_dotproduct, mul_0_1_optimized};
@@ -15,7 +17,7 @@ use num_integer::Roots;
 use rayon::prelude::*;
 use tracing::trace_span;
 
-use crate::msm::VariableBaseMSM;
+use crate::msm::{SmallScalar, VariableBaseMSM};
 
 #[derive(Clone)]
 pub struct HyraxScheme<G: CurveGroup, ProofTranscript: Transcript> {
@@ -52,12 +54,18 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>, ProofTranscript: Transcript>
     CommitmentScheme<ProofTranscript> for HyraxScheme<G, ProofTranscript>
 {
     type Field = G::ScalarField;
+    type SRS = ();
     type Setup = PedersenGenerators<G>;
+    type VerifierSetup = PedersenGenerators<G>;
     type Commitment = HyraxCommitment<G>;
     type Proof = HyraxOpeningProof<G, ProofTranscript>;
     type BatchedProof = BatchedHyraxOpeningProof<G, ProofTranscript>;
+    type OpeningHint = ();
+    const OPENING_REDUCTION: OpeningReduction<Self, ProofTranscript> = OpeningReduction::sumcheck();
 
-    fn setup(shapes: &[CommitShape]) -> Self::Setup {
+    #[cfg(any(test, feature = "insecure-setup"))]
+    fn insecure_srs(_shapes: &[CommitShape]) -> Self::SRS {}
+    fn setup_from_srs(_srs: &Self::SRS, shapes: &[CommitShape]) -> Result<Self::Setup, SRSError> {
         let mut max_len: usize = 0;
         for shape in shapes {
             let len = matrix_dimensions(
@@ -69,7 +77,16 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>, ProofTranscript: Transcript>
                 max_len = len;
             }
         }
-        PedersenGenerators::new(max_len, b"Jolt v1 Hyrax generators")
+        Ok(PedersenGenerators::new(
+            max_len,
+            b"Jolt v1 Hyrax generators",
+        ))
+    }
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup {
+        setup.clone().without_msm_table()
+    }
+    fn with_msm_tables(setup: Self::Setup, num_windows: usize) -> Self::Setup {
+        setup.with_msm_table(num_windows)
     }
     fn commit(poly: &DensePolynomial<Self::Field>, gens: &Self::Setup) -> Self::Commitment {
         HyraxCommitment::commit(poly, gens)
@@ -84,6 +101,19 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>, ProofTranscript: Transcript>
     fn commit_slice(eval_slice: &[Self::Field], generators: &Self::Setup) -> Self::Commitment {
         HyraxCommitment::commit_slice(eval_slice, generators)
     }
+    fn commit_small<S: SmallScalar>(evals: &[S], generators: &Self::Setup) -> Self::Commitment {
+        // `commit_slice` lays out the evaluations as with `BatchType::Small`
+        HyraxCommitment::batch_commit_small(&[evals], generators, BatchType::Small)
+            .pop()
+            .unwrap()
+    }
+    fn batch_commit_small<S: SmallScalar>(
+        evals: &[&[S]],
+        gens: &Self::Setup,
+        batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        HyraxCommitment::batch_commit_small(evals, gens, batch_type)
+    }
     fn prove(
         _setup: &Self::Setup,
         poly: &DensePolynomial<Self::Field>,
@@ -109,42 +139,10 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>, ProofTranscript: Transcript>
             transcript,
         )
     }
-    fn combine_commitments(
-        commitments: &[&Self::Commitment],
-        coeffs: &[Self::Field],
-    ) -> Self::Commitment {
-        let max_size = commitments
-            .iter()
-            .map(|commitment| commitment.row_commitments.len())
-            .max()
-            .unwrap();
-
-        let row_commitments = coeffs
-            .par_iter()
-            .zip(commitments.par_iter())
-            .map(|(coeff, commitment)| {
-                commitment
-                    .row_commitments
-                    .iter()
-                    .map(|row_commitment| *row_commitment * coeff)
-                    .collect()
-            })
-            .reduce(
-                || vec![G::zero(); max_size],
-                |running, new| {
-                    running
-                        .iter()
-                        .zip(new.iter())
-                        .map(|(r, n)| *r + n)
-                        .collect()
-                },
-            );
-        HyraxCommitment { row_commitments }
-    }
 
     fn verify(
         proof: &Self::Proof,
-        generators: &Self::Setup,
+        generators: &Self::VerifierSetup,
         transcript: &mut ProofTranscript,
         opening_point: &[Self::Field],
         opening: &Self::Field,
@@ -164,7 +162,7 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>, ProofTranscript: Transcript>
     #[tracing::instrument(skip_all, name = "HyraxScheme::batch_verify")]
     fn batch_verify(
         batch_proof: &Self::BatchedProof,
-        generators: &Self::Setup,
+        generators: &Self::VerifierSetup,
         opening_point: &[Self::Field],
         openings: &[Self::Field],
         commitments: &[&Self::Commitment],
@@ -184,6 +182,55 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>, ProofTranscript: Transcript>
     }
 }
 
+impl<F: JoltField, G: CurveGroup<ScalarField = F>, ProofTranscript: Transcript>
+    AdditivelyHomomorphic<ProofTranscript> for HyraxScheme<G, ProofTranscript>
+{
+    fn combine_commitments(
+        commitments: &[&Self::Commitment],
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment {
+        let max_size = commitments
+            .iter()
+            .map(|commitment| commitment.row_commitments.len())
+            .max()
+            .unwrap();
+
+        let row_commitments = coeffs
+            .par_iter()
+            .zip(commitments.par_iter())
+            .map(|(coeff, commitment)| {
+                commitment
+                    .row_commitments
+                    .iter()
+                    .map(|row_commitment| *row_commitment * coeff)
+                    .collect()
+            })
+            .reduce(
+                || vec![G::zero(); max_size],
+                |running, new| {
+                    running
+                        .iter()
+                        .zip(new.iter())
+                        .map(|(r, n)| *r + n)
+                        .collect()
+                },
+            );
+        HyraxCommitment { row_commitments }
+    }
+}
+
+/// Commits to `row` against `bases`, the first `row.len()` of `generators` in affine form.
+fn commit_row<G: CurveGroup>(
+    row: &[G::ScalarField],
+    bases: &[G::Affine],
+    generators: &PedersenGenerators<G>,
+) -> G {
+    match generators.msm_table() {
+        Some(table) => G::msm_precomputed(bases, row, table, 0).unwrap(),
+        None => PedersenCommitment::commit_vector(row, bases),
+    }
+}
+
 #[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct HyraxGenerators<G: CurveGroup> {
     pub gens: PedersenGenerators<G>,
@@ -214,7 +261,7 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxCommitment<G> {
         let gens = CurveGroup::normalize_batch(&generators.generators[..R_size]);
         let row_commitments = eval_slice
             .par_chunks(R_size)
-            .map(|row| PedersenCommitment::commit_vector(row, &gens))
+            .map(|row| commit_row(row, &gens, generators))
             .collect();
         Self { row_commitments }
     }
@@ -236,9 +283,38 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxCommitment<G> {
 
         let gens = CurveGroup::normalize_batch(&generators.generators[..R_size]);
 
+        let rows = batch.par_iter().flat_map(|poly| poly.par_chunks(R_size));
+        let row_commitments: Vec<G> = rows.map(|row| commit_row(row, &gens, generators)).collect();
+
+        row_commitments
+            .par_chunks(L_size)
+            .map(|chunk| Self {
+                row_commitments: chunk.to_vec(),
+            })
+            .collect()
+    }
+
+    /// Like [`HyraxCommitment::batch_commit`], for evaluations given as small integers or bits.
+    #[tracing::instrument(skip_all, name = "HyraxCommitment::batch_commit_small")]
+    pub fn batch_commit_small<S: SmallScalar>(
+        batch: &[&[S]],
+        generators: &PedersenGenerators<G>,
+        batch_type: BatchType,
+    ) -> Vec<Self> {
+        let n = batch[0].len();
+        batch.iter().for_each(|poly| assert_eq!(poly.len(), n));
+        let ell = n.log_2();
+
+        let ratio = batch_type_to_ratio(&batch_type);
+
+        let (L_size, R_size) = matrix_dimensions(ell, ratio);
+        assert_eq!(L_size * R_size, n);
+
+        let gens = CurveGroup::normalize_batch(&generators.generators[..R_size]);
+
         let rows = batch.par_iter().flat_map(|poly| poly.par_chunks(R_size));
         let row_commitments: Vec<G> = rows
-            .map(|row| PedersenCommitment::commit_vector(row, &gens))
+            .map(|row| G::msm_small_scalars(&gens, row).unwrap())
             .collect();
 
         row_commitments
@@ -535,6 +611,30 @@ mod tests {
         check_polynomial_commit_helper::<Fr, G1Projective, 4>();
     }
 
+    #[test]
+    fn check_commit_small() {
+        type PCS = HyraxScheme<G1Projective, KeccakTranscript>;
+        let setup = PCS::setup(&[
+            CommitShape::new(1 << 8, BatchType::Big),
+            CommitShape::new(1 << 8, BatchType::SurgeReadWrite),
+        ]);
+        let chunks: Vec<u16> = (0..1 << 8).map(|i| (i * 251) as u16).collect();
+        let chunk_evals: Vec<Fr> = chunks.iter().map(|&chunk| Fr::from(chunk)).collect();
+        let flags: Vec<bool> = (0..1 << 8).map(|i| i % 3 == 0).collect();
+        let flag_evals: Vec<Fr> = flags.iter().map(|&flag| Fr::from(flag)).collect();
+
+        assert_eq!(
+            PCS::commit_small(&chunks, &setup),
+            PCS::commit_slice(&chunk_evals, &setup)
+        );
+        for batch_type in [BatchType::Big, BatchType::SurgeReadWrite] {
+            assert_eq!(
+                PCS::batch_commit_small(&[&flags, &flags], &setup, batch_type.clone()),
+                PCS::batch_commit(&[&flag_evals, &flag_evals], &setup, batch_type)
+            );
+        }
+    }
+
     fn check_polynomial_commit_helper<
         F: JoltField,
         G: CurveGroup<ScalarField = F>,
@@ -1,21 +1,30 @@
 use crate::field::JoltField;
-use crate::msm::VariableBaseMSM;
+use crate::msm::{FixedBaseMSMTable, SmallScalar, VariableBaseMSM};
+use crate::poly::commitment::ptau;
+use crate::poly::commitment::srs_file::Points;
 use crate::poly::unipoly::UniPoly;
-use crate::utils::errors::ProofVerifyError;
+use crate::utils::errors::{ProofVerifyError, SRSError};
 use ark_ec::scalar_mul::fixed_base::FixedBase;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use ark_std::{One, UniformRand, Zero};
 use rand_core::{CryptoRng, RngCore};
 use rayon::prelude::*;
+use sha3::{Digest, Keccak256};
 use std::marker::PhantomData;
+use std::path::Path;
 use std::sync::Arc;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct SRS<P: Pairing> {
-    pub g1_powers: Vec<P::G1Affine>,
-    pub g2_powers: Vec<P::G2Affine>,
-    pub g_products: Vec<P::G1Affine>,
+    pub g1_powers: Points<P::G1Affine>,
+    pub g2_powers: Points<P::G2Affine>,
+    pub g_products: Points<P::G1Affine>,
 }
 
 impl<P: Pairing> SRS<P> {
@@ -24,9 +33,7 @@ impl<P: Pairing> SRS<P> {
         num_g1_powers: usize,
         num_g2_powers: usize,
     ) -> Self {
-        let beta = P::ScalarField::rand(&mut rng);
-        let g1 = P::G1::rand(&mut rng);
-        let g2 = P::G2::rand(&mut rng);
+        let (beta, g1, g2) = Self::sample_trapdoor(&mut rng);
 
         let scalar_bits = P::ScalarField::MODULUS_BIT_SIZE as usize;
 
@@ -63,11 +70,170 @@ impl<P: Pairing> SRS<P> {
             || P::G2::normalize_batch(&g2_powers_projective),
         );
 
+        Self::from_powers_unchecked(g1_powers, g2_powers)
+    }
+
+    /// Returns the verifier key of the SRS that [`SRS::setup`] would derive from `rng`,
+    /// without computing any of the powers.
+    pub fn setup_verifier_key<R: RngCore + CryptoRng>(rng: &mut R) -> KZGVerifierKey<P> {
+        let (beta, g1, g2) = Self::sample_trapdoor(rng);
+        KZGVerifierKey {
+            g1: (g1 * beta).into_affine(),
+            g2: (g2 * beta).into_affine(),
+            beta_g2: (g2 * beta * beta).into_affine(),
+        }
+    }
+
+    fn sample_trapdoor<R: RngCore + CryptoRng>(mut rng: &mut R) -> (P::ScalarField, P::G1, P::G2) {
+        let beta = P::ScalarField::rand(&mut rng);
+        let g1 = P::G1::rand(&mut rng);
+        let g2 = P::G2::rand(&mut rng);
+        (beta, g1, g2)
+    }
+
+    /// Loads the first `num_g1_powers` G1 and `num_g2_powers` G2 powers of tau from a
+    /// snarkjs `.ptau` file, checking them as in [`SRS::from_powers`].
+    pub fn from_ptau_file<G1, G2>(
+        path: impl AsRef<Path>,
+        num_g1_powers: usize,
+        num_g2_powers: usize,
+    ) -> Result<Self, SRSError>
+    where
+        P: Pairing<G1Affine = Affine<G1>, G2Affine = Affine<G2>>,
+        G1: SWCurveConfig,
+        G2: SWCurveConfig,
+    {
+        let (g1_powers, g2_powers) =
+            ptau::read_powers::<G1, G2>(path.as_ref(), num_g1_powers, num_g2_powers)?;
+        Self::from_powers(g1_powers, g2_powers)
+    }
+
+    /// Loads the first `num_g1_powers` G1 and `num_g2_powers` G2 powers of tau from a
+    /// snarkjs `.ptau` file if `path` has the `ptau` extension, and otherwise from an SRS
+    /// file written by [`SRS::save_to_file`].
+    ///
+    /// # Safety
+    /// As for [`SRS::load_from_file`], which memory-maps SRS files: the file must not be
+    /// modified until the returned SRS and everything derived from it are dropped.
+    pub unsafe fn from_file<G1, G2>(
+        path: impl AsRef<Path>,
+        num_g1_powers: usize,
+        num_g2_powers: usize,
+    ) -> Result<Self, SRSError>
+    where
+        P: Pairing<G1Affine = Affine<G1>, G2Affine = Affine<G2>>,
+        G1: SWCurveConfig,
+        G2: SWCurveConfig,
+    {
+        let path = path.as_ref();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "ptau")
+        {
+            Self::from_ptau_file(path, num_g1_powers, num_g2_powers)
+        } else {
+            // SAFETY: guaranteed by the caller
+            unsafe { Self::load_from_file(path) }?.truncate(num_g1_powers, num_g2_powers)
+        }
+    }
+
+    /// Builds an SRS from externally generated powers `[τ^i]G` and `[τ^i]H`. Checks that
+    /// every point is in the prime-order subgroup and that both sequences are powers of
+    /// the same τ.
+    pub fn from_powers<G1, G2>(
+        g1_powers: Vec<Affine<G1>>,
+        g2_powers: Vec<Affine<G2>>,
+    ) -> Result<Self, SRSError>
+    where
+        P: Pairing<G1Affine = Affine<G1>, G2Affine = Affine<G2>>,
+        G1: SWCurveConfig,
+        G2: SWCurveConfig,
+    {
+        check_points(&g1_powers, "G1")?;
+        check_points(&g2_powers, "G2")?;
+        Self::check_powers(&g1_powers, &g2_powers)?;
+        Ok(Self::from_powers_unchecked(g1_powers, g2_powers))
+    }
+
+    /// Checks the pairing equations `e([τ^{i+1}]G, H) = e([τ^i]G, [τ]H)` and
+    /// `e(G, [τ^{j+1}]H) = e([τ]G, [τ^j]H)` for all `i` and `j`, batched with powers of a
+    /// challenge derived by hashing the powers.
+    fn check_powers(g1_powers: &[P::G1Affine], g2_powers: &[P::G2Affine]) -> Result<(), SRSError> {
+        let mut hasher = Keccak256::new();
+        g1_powers
+            .serialize_uncompressed(&mut hasher)
+            .expect("hashing cannot fail");
+        g2_powers
+            .serialize_uncompressed(&mut hasher)
+            .expect("hashing cannot fail");
+        let rho = P::ScalarField::from_le_bytes_mod_order(&hasher.finalize());
+
+        let num_g1_equations = g1_powers.len() - 1;
+        let num_g2_equations = g2_powers.len() - 1;
+        let rho_powers: Vec<P::ScalarField> = (0..num_g1_equations + num_g2_equations)
+            .scan(P::ScalarField::one(), |acc, _| {
+                let val = *acc;
+                *acc *= rho;
+                Some(val)
+            })
+            .collect();
+        let (g1_rho_powers, g2_rho_powers) = rho_powers.split_at(num_g1_equations);
+
+        let ((g1_shifted, g1_unshifted), (g2_shifted, g2_unshifted)) = rayon::join(
+            || {
+                rayon::join(
+                    || <P::G1 as VariableBaseMSM>::msm(&g1_powers[1..], g1_rho_powers).unwrap(),
+                    || {
+                        <P::G1 as VariableBaseMSM>::msm(
+                            &g1_powers[..num_g1_equations],
+                            g1_rho_powers,
+                        )
+                        .unwrap()
+                    },
+                )
+            },
+            || {
+                rayon::join(
+                    || <P::G2 as VariableBaseMSM>::msm(&g2_powers[1..], g2_rho_powers).unwrap(),
+                    || {
+                        <P::G2 as VariableBaseMSM>::msm(
+                            &g2_powers[..num_g2_equations],
+                            g2_rho_powers,
+                        )
+                        .unwrap()
+                    },
+                )
+            },
+        );
+
+        let consistent = P::multi_pairing(
+            [
+                g1_shifted,
+                -g1_unshifted,
+                g1_powers[0].into_group(),
+                -g1_powers[1].into_group(),
+            ],
+            [
+                g2_powers[0].into_group(),
+                g2_powers[1].into_group(),
+                g2_shifted,
+                g2_unshifted,
+            ],
+        )
+        .is_zero();
+        if consistent {
+            Ok(())
+        } else {
+            Err(SRSError::InconsistentPowers)
+        }
+    }
+
+    fn from_powers_unchecked(g1_powers: Vec<P::G1Affine>, g2_powers: Vec<P::G2Affine>) -> Self {
         // Precompute a commitment to each power-of-two length vector of ones, which is just the sum of each power-of-two length prefix of the SRS
         let num_powers = (g1_powers.len() as f64).log2().floor() as usize + 1;
-        let all_ones_coeffs: Vec<P::ScalarField> = vec![P::ScalarField::one(); num_g1_powers + 1];
+        let all_ones_coeffs: Vec<P::ScalarField> = vec![P::ScalarField::one(); g1_powers.len()];
         let powers_of_2 = (0..num_powers).into_par_iter().map(|i| 1usize << i);
-        let g_products = powers_of_2
+        let g_products: Vec<_> = powers_of_2
             .map(|power| {
                 <P::G1 as VariableBaseMSM>::msm(&g1_powers[..power], &all_ones_coeffs[..power])
                     .unwrap()
@@ -76,10 +242,36 @@ impl<P: Pairing> SRS<P> {
             .collect();
 
         Self {
-            g1_powers,
-            g2_powers,
-            g_products,
+            g1_powers: g1_powers.into(),
+            g2_powers: g2_powers.into(),
+            g_products: g_products.into(),
+        }
+    }
+
+    /// Returns this SRS restricted to its first `num_g1_powers` G1 and `num_g2_powers`
+    /// G2 powers, without copying them if they are memory-mapped.
+    pub fn truncate(&self, num_g1_powers: usize, num_g2_powers: usize) -> Result<Self, SRSError> {
+        for (group, required, available) in [
+            ("G1", num_g1_powers, self.g1_powers.len()),
+            ("G2", num_g2_powers, self.g2_powers.len()),
+        ] {
+            if available < required {
+                return Err(SRSError::TooFewPowers {
+                    group,
+                    required,
+                    available,
+                });
+            }
         }
+        // `g_products[i]` is the sum of the first 2^i G1 powers
+        let num_g_products = num_g1_powers
+            .checked_ilog2()
+            .map_or(0, |log| log as usize + 1);
+        Ok(Self {
+            g1_powers: self.g1_powers.prefix(num_g1_powers),
+            g2_powers: self.g2_powers.prefix(num_g2_powers),
+            g_products: self.g_products.prefix(num_g_products),
+        })
     }
 
     pub fn trim(params: Arc<Self>, max_degree: usize) -> (KZGProverKey<P>, KZGVerifierKey<P>) {
@@ -97,6 +289,27 @@ impl<P: Pairing> SRS<P> {
     }
 }
 
+fn check_points<C: SWCurveConfig>(
+    points: &[Affine<C>],
+    group: &'static str,
+) -> Result<(), SRSError> {
+    // The pairing checks need at least G, [τ]G, H and [τ]H
+    if points.len() < 2 {
+        return Err(SRSError::TooFewPowers {
+            group,
+            required: 2,
+            available: points.len(),
+        });
+    }
+    // No power of a nonzero τ is the identity
+    match points.par_iter().position_any(|point| {
+        point.is_zero() || !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve()
+    }) {
+        Some(index) => Err(SRSError::InvalidPoint { group, index }),
+        None => Ok(()),
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct KZGProverKey<P: Pairing> {
     srs: Arc<SRS<P>>,
@@ -104,6 +317,8 @@ pub struct KZGProverKey<P: Pairing> {
     offset: usize,
     // max size of srs
     supported_size: usize,
+    // fixed-base MSM table of `g1_powers()`, rebuilt rather than serialized
+    msm_table: Option<Arc<FixedBaseMSMTable<P::G1>>>,
 }
 
 impl<P: Pairing> KZGProverKey<P> {
@@ -119,15 +334,92 @@ impl<P: Pairing> KZGProverKey<P> {
             srs,
             offset,
             supported_size,
+            msm_table: None,
         }
     }
 
+    /// Precomputes a [`FixedBaseMSMTable`] of the G1 powers with `num_windows` windows, which
+    /// commitments then use for large coefficients. The table takes `num_windows` times the
+    /// memory of the powers, so keys don't have one unless this is called.
+    pub fn with_msm_table(mut self, num_windows: usize) -> Self {
+        self.msm_table = Some(Arc::new(FixedBaseMSMTable::new(
+            self.g1_powers(),
+            num_windows,
+        )));
+        self
+    }
+
+    pub fn without_msm_table(mut self) -> Self {
+        self.msm_table = None;
+        self
+    }
+
+    pub fn msm_table(&self) -> Option<&FixedBaseMSMTable<P::G1>> {
+        self.msm_table.as_deref()
+    }
+
     pub fn g1_powers(&self) -> &[P::G1Affine] {
         &self.srs.g1_powers[self.offset..self.offset + self.supported_size]
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+impl<P: Pairing> CanonicalSerialize for KZGProverKey<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.srs.serialize_with_mode(&mut writer, compress)?;
+        self.offset.serialize_with_mode(&mut writer, compress)?;
+        self.supported_size
+            .serialize_with_mode(&mut writer, compress)?;
+        self.msm_table
+            .as_ref()
+            .map(|table| table.num_windows())
+            .serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.srs.serialized_size(compress)
+            + self.offset.serialized_size(compress)
+            + self.supported_size.serialized_size(compress)
+            + self
+                .msm_table
+                .as_ref()
+                .map(|table| table.num_windows())
+                .serialized_size(compress)
+    }
+}
+
+impl<P: Pairing> Valid for KZGProverKey<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.srs.check()
+    }
+}
+
+impl<P: Pairing> CanonicalDeserialize for KZGProverKey<P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let srs = Arc::<SRS<P>>::deserialize_with_mode(&mut reader, compress, validate)?;
+        let offset = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let supported_size = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let num_windows =
+            FixedBaseMSMTable::<P::G1>::deserialize_num_windows(&mut reader, compress, validate)?;
+        if srs.g1_powers.len() < offset + supported_size {
+            return Err(SerializationError::InvalidData);
+        }
+        let pk = Self::new(srs, offset, supported_size);
+        Ok(match num_windows {
+            Some(num_windows) => pk.with_msm_table(num_windows),
+            None => pk,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct KZGVerifierKey<P: Pairing> {
     pub g1: P::G1Affine,
     pub g2: P::G2Affine,
@@ -195,6 +487,26 @@ where
         Self::commit_inner(pk, coeffs, 0, mode)
     }
 
+    /// Commits to coefficients given as small integers or bits. The commitment is computed as
+    /// by [`UnivariateKZG::commit_slice`] on the coefficients as field elements: `pk`'s
+    /// fixed-base table only speeds up scalars of more than 64 bits, so neither uses it here.
+    #[tracing::instrument(skip_all, name = "KZG::commit_small")]
+    pub fn commit_small<S: SmallScalar>(
+        pk: &KZGProverKey<P>,
+        coeffs: &[S],
+    ) -> Result<P::G1Affine, ProofVerifyError> {
+        if pk.g1_powers().len() < coeffs.len() {
+            return Err(ProofVerifyError::KeyLengthError(
+                pk.g1_powers().len(),
+                coeffs.len(),
+            ));
+        }
+        let c =
+            <P::G1 as VariableBaseMSM>::msm_small_scalars(&pk.g1_powers()[..coeffs.len()], coeffs)
+                .unwrap();
+        Ok(c.into_affine())
+    }
+
     #[inline]
     #[tracing::instrument(skip_all, name = "KZG::commit_inner")]
     fn commit_inner(
@@ -212,10 +524,16 @@ where
 
         match mode {
             CommitMode::Default => {
-                let c = <P::G1 as VariableBaseMSM>::msm(
-                    &pk.g1_powers()[offset..coeffs.len()],
-                    &coeffs[offset..],
-                )
+                let bases = &pk.g1_powers()[offset..coeffs.len()];
+                let c = match pk.msm_table() {
+                    Some(table) => <P::G1 as VariableBaseMSM>::msm_precomputed(
+                        bases,
+                        &coeffs[offset..],
+                        table,
+                        offset,
+                    ),
+                    None => <P::G1 as VariableBaseMSM>::msm(bases, &coeffs[offset..]),
+                }
                 .unwrap();
                 Ok(c.into_affine())
             }
@@ -298,7 +616,8 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use ark_bn254::{Bn254, Fr};
+    use ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine};
+    use ark_ff::{BigInteger, Field};
     use ark_std::{rand::Rng, UniformRand};
     use rand_chacha::ChaCha20Rng;
     use rand_core::SeedableRng;
@@ -338,4 +657,154 @@ mod test {
         // This test uses the grand product optimization and ensures only powers of 2 are used for degree generation
         run_kzg_test(|rng| 1 << rng.gen_range(1..8), CommitMode::GrandProduct)
     }
+
+    fn test_srs(num_powers: usize) -> SRS<Bn254> {
+        SRS::<Bn254>::setup(&mut ChaCha20Rng::seed_from_u64(0), num_powers - 1, 2)
+    }
+
+    /// Writes a snarkjs `.ptau` file containing only the sections read by `ptau::read_powers`.
+    fn write_ptau(path: &Path, g1_powers: &[G1Affine], g2_powers: &[G2Affine]) {
+        // The Montgomery form of `a` is `a * 2^256`
+        let montgomery = |a: Fq| (a * Fq::from(2u64).pow([256])).into_bigint().to_bytes_le();
+
+        let mut header = 32u32.to_le_bytes().to_vec();
+        header.extend(Fq::MODULUS.to_bytes_le());
+        header.extend(10u32.to_le_bytes());
+        header.extend(10u32.to_le_bytes());
+        let tau_g1: Vec<u8> = g1_powers
+            .iter()
+            .flat_map(|point| [montgomery(point.x), montgomery(point.y)].concat())
+            .collect();
+        let tau_g2: Vec<u8> = g2_powers
+            .iter()
+            .flat_map(|point| {
+                [
+                    montgomery(point.x.c0),
+                    montgomery(point.x.c1),
+                    montgomery(point.y.c0),
+                    montgomery(point.y.c1),
+                ]
+                .concat()
+            })
+            .collect();
+
+        let mut file = b"ptau".to_vec();
+        file.extend(1u32.to_le_bytes());
+        file.extend(3u32.to_le_bytes());
+        // Sections need not be in order
+        for (section_type, section) in [(3u32, tau_g2), (1, header), (2, tau_g1)] {
+            file.extend(section_type.to_le_bytes());
+            file.extend((section.len() as u64).to_le_bytes());
+            file.extend(section);
+        }
+        std::fs::write(path, file).unwrap();
+    }
+
+    #[test]
+    fn setup_verifier_key_matches_setup() {
+        let (_, vk) = SRS::trim(Arc::new(test_srs(8)), 4);
+        let expected = SRS::<Bn254>::setup_verifier_key(&mut ChaCha20Rng::seed_from_u64(0));
+        assert_eq!(vk.g1, expected.g1);
+        assert_eq!(vk.g2, expected.g2);
+        assert_eq!(vk.beta_g2, expected.beta_g2);
+    }
+
+    #[test]
+    fn srs_from_powers() {
+        let srs = test_srs(16);
+        let loaded =
+            SRS::<Bn254>::from_powers(srs.g1_powers.to_vec(), srs.g2_powers.to_vec()).unwrap();
+        assert_eq!(loaded.g_products[..], srs.g_products[..]);
+
+        let truncated = loaded.truncate(5, 2).unwrap();
+        assert_eq!(truncated.g1_powers[..], srs.g1_powers[..5]);
+        assert_eq!(truncated.g_products[..], srs.g_products[..3]);
+        assert!(matches!(
+            loaded.truncate(17, 2),
+            Err(SRSError::TooFewPowers {
+                group: "G1",
+                required: 17,
+                available: 16
+            })
+        ));
+    }
+
+    #[test]
+    fn srs_from_powers_rejects_inconsistent_powers() {
+        let srs = test_srs(16);
+
+        let mut g1_powers = srs.g1_powers.to_vec();
+        g1_powers[7] = (g1_powers[7] + g1_powers[0]).into_affine();
+        assert!(matches!(
+            SRS::<Bn254>::from_powers(g1_powers, srs.g2_powers.to_vec()),
+            Err(SRSError::InconsistentPowers)
+        ));
+
+        let mut g2_powers = srs.g2_powers.to_vec();
+        g2_powers[2] = (g2_powers[2] + g2_powers[0]).into_affine();
+        assert!(matches!(
+            SRS::<Bn254>::from_powers(srs.g1_powers.to_vec(), g2_powers),
+            Err(SRSError::InconsistentPowers)
+        ));
+    }
+
+    #[test]
+    fn srs_from_powers_rejects_invalid_points() {
+        let srs = test_srs(16);
+
+        let mut g1_powers = srs.g1_powers.to_vec();
+        g1_powers[3] = G1Affine::new_unchecked(g1_powers[3].x, g1_powers[3].y + Fq::one());
+        assert!(matches!(
+            SRS::<Bn254>::from_powers(g1_powers, srs.g2_powers.to_vec()),
+            Err(SRSError::InvalidPoint {
+                group: "G1",
+                index: 3
+            })
+        ));
+
+        let mut g2_powers = srs.g2_powers.to_vec();
+        g2_powers[1] = G2Affine::zero();
+        assert!(matches!(
+            SRS::<Bn254>::from_powers(srs.g1_powers.to_vec(), g2_powers),
+            Err(SRSError::InvalidPoint {
+                group: "G2",
+                index: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn srs_from_ptau_file() {
+        let srs = test_srs(16);
+        let path = std::env::temp_dir().join(format!("jolt-kzg-{}.ptau", std::process::id()));
+        write_ptau(&path, &srs.g1_powers, &srs.g2_powers);
+
+        let loaded = SRS::<Bn254>::from_ptau_file(&path, 8, 2).unwrap();
+        assert_eq!(loaded.g1_powers[..], srs.g1_powers[..8]);
+        assert_eq!(loaded.g2_powers[..], srs.g2_powers[..2]);
+        assert!(matches!(
+            SRS::<Bn254>::from_ptau_file(&path, 8, 4),
+            Err(SRSError::TooFewPowers {
+                group: "G2",
+                required: 4,
+                available: 3
+            })
+        ));
+
+        // A huge field element size is rejected before anything is allocated for it
+        let mut bytes = std::fs::read(&path).unwrap();
+        let modulus = Fq::MODULUS.to_bytes_le();
+        let n8_offset = bytes
+            .windows(modulus.len())
+            .position(|window| window == modulus)
+            .unwrap()
+            - 4;
+        bytes[n8_offset..n8_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            SRS::<Bn254>::from_ptau_file(&path, 8, 2),
+            Err(SRSError::Format(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
 }
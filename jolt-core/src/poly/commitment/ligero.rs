@@ -0,0 +1,700 @@
+use std::marker::PhantomData;
+
+use ark_ff::FftField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use itertools::Itertools;
+use rayon::prelude::*;
+use sha3::{Digest, Keccak256};
+
+use super::commitment_scheme::{
+    small_scalars_to_field, BatchType, CommitShape, CommitmentScheme, OpeningReduction,
+};
+use crate::field::JoltField;
+use crate::msm::SmallScalar;
+use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::eq_poly::EqPolynomial;
+use crate::utils::errors::{ProofVerifyError, SRSError};
+use crate::utils::math::Math;
+use crate::utils::transcript::{AppendToTranscript, Transcript};
+
+/// log2 of the inverse rate of the Reed–Solomon code the rows are encoded with.
+const LOG_BLOWUP: usize = 2;
+/// Number of columns opened per proof. A row combination more than 3/8 (half the relative
+/// distance of the rate-1/4 code) away from the code passes each query with probability
+/// at most 5/8, so 148 queries give 100 bits of security.
+const NUM_QUERIES: usize = 148;
+
+/// A field with a Reed–Solomon encoder, over which [`Ligero`] commitments are computed.
+pub trait ReedSolomonField: JoltField {
+    /// Data precomputed for encoding messages of a fixed length, e.g. twiddle factors.
+    type Encoder: Sync;
+
+    /// Returns an encoder for messages of length `2^log_message_len` into codewords
+    /// `2^log_blowup` times as long.
+    fn encoder(log_message_len: usize, log_blowup: usize) -> Self::Encoder;
+    /// Encodes `message` as the evaluations over a fixed domain of a polynomial of degree
+    /// less than `message.len()`, which depends linearly on `message`.
+    fn encode(encoder: &Self::Encoder, message: &[Self]) -> Vec<Self>;
+}
+
+/// Reed–Solomon encoding over a multiplicative subgroup of two-power order, by a radix-2 FFT.
+///
+/// The message holds the coefficients of a polynomial, which is evaluated at the powers
+/// `omega^0, omega^1, ...` of a root of unity of the codeword's order.
+pub struct Radix2Fft<F: JoltField> {
+    log_codeword_len: usize,
+    /// `omega^i` for `i` in `0..codeword_len / 2`
+    twiddles: Vec<F>,
+}
+
+impl<F: JoltField + FftField> Radix2Fft<F> {
+    pub fn new(log_len: usize, log_blowup: usize) -> Self {
+        let log_codeword_len = log_len + log_blowup;
+        let omega = F::get_root_of_unity(1 << log_codeword_len).unwrap();
+        let mut twiddles = Vec::with_capacity(log_codeword_len.pow2() / 2);
+        let mut power = F::one();
+        for _ in 0..log_codeword_len.pow2() / 2 {
+            twiddles.push(power);
+            power *= omega;
+        }
+        Self {
+            log_codeword_len,
+            twiddles,
+        }
+    }
+
+    pub fn encode(&self, message: &[F]) -> Vec<F> {
+        let n = self.log_codeword_len.pow2();
+        assert!(message.len() <= n);
+        let mut evals = message.to_vec();
+        evals.resize(n, F::zero());
+
+        // Iterative Cooley-Tukey on the bit-reversed coefficients
+        for i in 0..n {
+            let j = i.reverse_bits() >> (usize::BITS as usize - self.log_codeword_len);
+            if i < j {
+                evals.swap(i, j);
+            }
+        }
+        let mut half = 1;
+        while half < n {
+            let twiddle_stride = n / (2 * half);
+            for block in evals.chunks_mut(2 * half) {
+                let (low, high) = block.split_at_mut(half);
+                for (k, (u, v)) in low.iter_mut().zip(high.iter_mut()).enumerate() {
+                    let t = *v * self.twiddles[k * twiddle_stride];
+                    *v = *u - t;
+                    *u += t;
+                }
+            }
+            half *= 2;
+        }
+        evals
+    }
+}
+
+impl ReedSolomonField for ark_bn254::Fr {
+    type Encoder = Radix2Fft<Self>;
+
+    fn encoder(log_message_len: usize, log_blowup: usize) -> Self::Encoder {
+        Radix2Fft::new(log_message_len, log_blowup)
+    }
+
+    fn encode(encoder: &Self::Encoder, message: &[Self]) -> Vec<Self> {
+        encoder.encode(message)
+    }
+}
+
+/// A transparent, hash-based multilinear PCS in the style of Ligero (as in Brakedown,
+/// https://eprint.iacr.org/2021/1043). The evaluations are arranged in a matrix, each row
+/// is Reed–Solomon encoded, and the commitment is a Merkle root over the columns of the
+/// encoded matrix.
+#[derive(Clone)]
+pub struct Ligero<F: ReedSolomonField, ProofTranscript: Transcript> {
+    _marker: PhantomData<(F, ProofTranscript)>,
+}
+
+/// Ligero's [`CommitmentScheme::OpeningHint`]: the encoding computed when committing to a
+/// polynomial, so that proofs of its openings don't encode it again.
+pub struct LigeroOpeningHint<F: JoltField>(Option<EncodedMatrix<F>>);
+
+impl<F: JoltField> Default for LigeroOpeningHint<F> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+#[derive(Clone, Default, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LigeroCommitment {
+    root: [u8; 32],
+}
+
+impl AppendToTranscript for LigeroCommitment {
+    fn append_to_transcript<ProofTranscript: Transcript>(&self, transcript: &mut ProofTranscript) {
+        transcript.append_bytes(&self.root);
+    }
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LigeroProof<F: JoltField> {
+    /// A random combination of the rows of the evaluation matrix, for the proximity test.
+    combined_row: Vec<F>,
+    /// The rows of the evaluation matrix combined with the `eq` evaluations of the row
+    /// variables of the opening point.
+    eval_row: Vec<F>,
+    /// The opened columns of the encoded matrix, one per distinct query.
+    columns: Vec<Vec<F>>,
+    /// Merkle authentication paths of the opened columns, from the leaf upwards.
+    merkle_paths: Vec<Vec<[u8; 32]>>,
+}
+
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LigeroBatchedProof<F: JoltField> {
+    proofs: Vec<LigeroProof<F>>,
+}
+
+/// Splits `num_vars` variables into (row, column) variables of the evaluation matrix.
+fn matrix_num_vars(num_vars: usize) -> (usize, usize) {
+    let num_row_vars = num_vars / 2;
+    (num_row_vars, num_vars - num_row_vars)
+}
+
+/// A polynomial's evaluation matrix with its rows Reed–Solomon encoded, and the Merkle tree
+/// over its columns.
+struct EncodedMatrix<F: JoltField> {
+    /// Row-major, each row of length `codeword_len`.
+    encoded_rows: Vec<F>,
+    codeword_len: usize,
+    merkle_tree: MerkleTree,
+}
+
+impl<F: ReedSolomonField> EncodedMatrix<F> {
+    #[tracing::instrument(skip_all, name = "Ligero::EncodedMatrix::new")]
+    fn new(evals: &[F]) -> Self {
+        assert!(evals.len().is_power_of_two());
+        let (_, num_col_vars) = matrix_num_vars(evals.len().log_2());
+        let num_cols = num_col_vars.pow2();
+        let encoder = F::encoder(num_col_vars, LOG_BLOWUP);
+
+        let encoded_rows: Vec<F> = evals
+            .par_chunks(num_cols)
+            .flat_map_iter(|row| F::encode(&encoder, row))
+            .collect();
+        let codeword_len = num_cols << LOG_BLOWUP;
+        let leaves = (0..codeword_len)
+            .into_par_iter()
+            .map(|index| hash_column(encoded_rows.iter().skip(index).step_by(codeword_len)))
+            .collect();
+
+        Self {
+            encoded_rows,
+            codeword_len,
+            merkle_tree: MerkleTree::new(leaves),
+        }
+    }
+
+    fn column(&self, index: usize) -> Vec<F> {
+        self.encoded_rows
+            .iter()
+            .skip(index)
+            .step_by(self.codeword_len)
+            .copied()
+            .collect()
+    }
+}
+
+fn hash_column<'a, F: JoltField>(column: impl Iterator<Item = &'a F>) -> [u8; 32] {
+    let mut bytes = vec![];
+    for value in column {
+        value.serialize_uncompressed(&mut bytes).unwrap();
+    }
+    Keccak256::digest(&bytes).into()
+}
+
+/// A binary Merkle tree of Keccak256 hashes over a power-of-two number of leaves.
+struct MerkleTree {
+    /// `layers[0]` are the leaves and the last layer is the root.
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    fn new(leaves: Vec<[u8; 32]>) -> Self {
+        assert!(leaves.len().is_power_of_two());
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next_layer = layers
+                .last()
+                .unwrap()
+                .par_chunks(2)
+                .map(|pair| Self::hash_pair(&pair[0], &pair[1]))
+                .collect();
+            layers.push(next_layer);
+        }
+        Self { layers }
+    }
+
+    fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        Keccak256::new()
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+            .into()
+    }
+
+    fn root(&self) -> [u8; 32] {
+        self.layers.last().unwrap()[0]
+    }
+
+    fn path(&self, mut index: usize) -> Vec<[u8; 32]> {
+        let num_layers = self.layers.len();
+        self.layers[..num_layers - 1]
+            .iter()
+            .map(|layer| {
+                let sibling = layer[index ^ 1];
+                index >>= 1;
+                sibling
+            })
+            .collect()
+    }
+
+    fn verify_path(root: &[u8; 32], leaf: [u8; 32], mut index: usize, path: &[[u8; 32]]) -> bool {
+        let computed_root = path.iter().fold(leaf, |node, sibling| {
+            let parent = if index % 2 == 0 {
+                Self::hash_pair(&node, sibling)
+            } else {
+                Self::hash_pair(sibling, &node)
+            };
+            index >>= 1;
+            parent
+        });
+        computed_root == *root
+    }
+}
+
+/// Samples the indices of the columns to open, sorted and deduplicated.
+fn query_indices<F: JoltField, ProofTranscript: Transcript>(
+    transcript: &mut ProofTranscript,
+    codeword_len: usize,
+) -> Vec<usize> {
+    let seed: F = transcript.challenge_scalar();
+    let mut seed_bytes = vec![];
+    seed.serialize_uncompressed(&mut seed_bytes).unwrap();
+    (0..NUM_QUERIES as u64)
+        .map(|i| {
+            let hash = Keccak256::new()
+                .chain_update(&seed_bytes)
+                .chain_update(i.to_le_bytes())
+                .finalize();
+            u64::from_le_bytes(hash[..8].try_into().unwrap()) as usize % codeword_len
+        })
+        .sorted_unstable()
+        .dedup()
+        .collect()
+}
+
+/// Computes `coeffs^T M` for the `rows x cols` row-major matrix `M`.
+fn combine_rows<F: JoltField>(coeffs: &[F], matrix: &[F], num_cols: usize) -> Vec<F> {
+    (0..num_cols)
+        .into_par_iter()
+        .map(|col| {
+            coeffs
+                .iter()
+                .zip(matrix.iter().skip(col).step_by(num_cols))
+                .map(|(coeff, value)| *coeff * value)
+                .sum()
+        })
+        .collect()
+}
+
+fn inner_product<F: JoltField>(a: &[F], b: &[F]) -> F {
+    a.iter().zip_eq(b.iter()).map(|(a_i, b_i)| *a_i * b_i).sum()
+}
+
+impl<F: ReedSolomonField, ProofTranscript: Transcript> Ligero<F, ProofTranscript> {
+    fn commit_evals(evals: &[F]) -> (LigeroCommitment, LigeroOpeningHint<F>) {
+        let matrix = EncodedMatrix::new(evals);
+        let commitment = LigeroCommitment {
+            root: matrix.merkle_tree.root(),
+        };
+        (commitment, LigeroOpeningHint(Some(matrix)))
+    }
+
+    /// Proves the opening of `evals` at `opening_point`, opening columns of the encoding in
+    /// `hint` if it was kept when committing to `evals`.
+    #[tracing::instrument(skip_all, name = "Ligero::prove")]
+    fn prove_evals(
+        evals: &[F],
+        hint: &LigeroOpeningHint<F>,
+        opening_point: &[F],
+        transcript: &mut ProofTranscript,
+    ) -> LigeroProof<F> {
+        assert_eq!(evals.len(), opening_point.len().pow2());
+        let (num_row_vars, num_col_vars) = matrix_num_vars(opening_point.len());
+        let num_cols = num_col_vars.pow2();
+
+        let encoded;
+        let matrix = match &hint.0 {
+            Some(matrix) => matrix,
+            None => {
+                encoded = EncodedMatrix::new(evals);
+                &encoded
+            }
+        };
+        debug_assert_eq!(matrix.encoded_rows.len(), evals.len() << LOG_BLOWUP);
+        transcript.append_bytes(&matrix.merkle_tree.root());
+
+        let gamma: Vec<F> = transcript.challenge_vector(num_row_vars.pow2());
+        let combined_row = combine_rows(&gamma, evals, num_cols);
+        let eq_rows = EqPolynomial::evals(&opening_point[..num_row_vars]);
+        let eval_row = combine_rows(&eq_rows, evals, num_cols);
+        transcript.append_scalars(&combined_row);
+        transcript.append_scalars(&eval_row);
+
+        let queries = query_indices::<F, _>(transcript, matrix.codeword_len);
+        let columns = queries.iter().map(|&index| matrix.column(index)).collect();
+        let merkle_paths = queries
+            .iter()
+            .map(|&index| matrix.merkle_tree.path(index))
+            .collect();
+
+        LigeroProof {
+            combined_row,
+            eval_row,
+            columns,
+            merkle_paths,
+        }
+    }
+
+    #[tracing::instrument(skip_all, name = "Ligero::verify")]
+    fn verify_proof(
+        proof: &LigeroProof<F>,
+        transcript: &mut ProofTranscript,
+        opening_point: &[F],
+        opening: &F,
+        commitment: &LigeroCommitment,
+    ) -> Result<(), ProofVerifyError> {
+        let (num_row_vars, num_col_vars) = matrix_num_vars(opening_point.len());
+        let (num_rows, num_cols) = (num_row_vars.pow2(), num_col_vars.pow2());
+        for row in [&proof.combined_row, &proof.eval_row] {
+            if row.len() != num_cols {
+                return Err(ProofVerifyError::InvalidInputLength(num_cols, row.len()));
+            }
+        }
+
+        transcript.append_bytes(&commitment.root);
+        let gamma: Vec<F> = transcript.challenge_vector(num_rows);
+        transcript.append_scalars(&proof.combined_row);
+        transcript.append_scalars(&proof.eval_row);
+
+        let eq_cols = EqPolynomial::evals(&opening_point[num_row_vars..]);
+        if inner_product(&proof.eval_row, &eq_cols) != *opening {
+            return Err(ProofVerifyError::InternalError);
+        }
+
+        let codeword_len = num_cols << LOG_BLOWUP;
+        let queries = query_indices::<F, _>(transcript, codeword_len);
+        if proof.columns.len() != queries.len() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                queries.len(),
+                proof.columns.len(),
+            ));
+        }
+        if proof.merkle_paths.len() != queries.len() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                queries.len(),
+                proof.merkle_paths.len(),
+            ));
+        }
+
+        let encoder = F::encoder(num_col_vars, LOG_BLOWUP);
+        let encoded_combined_row = F::encode(&encoder, &proof.combined_row);
+        let encoded_eval_row = F::encode(&encoder, &proof.eval_row);
+        let eq_rows = EqPolynomial::evals(&opening_point[..num_row_vars]);
+
+        for ((&index, column), path) in queries
+            .iter()
+            .zip(proof.columns.iter())
+            .zip(proof.merkle_paths.iter())
+        {
+            if column.len() != num_rows {
+                return Err(ProofVerifyError::InvalidInputLength(num_rows, column.len()));
+            }
+            if path.len() != codeword_len.log_2() {
+                return Err(ProofVerifyError::InvalidInputLength(
+                    codeword_len.log_2(),
+                    path.len(),
+                ));
+            }
+            if !MerkleTree::verify_path(&commitment.root, hash_column(column.iter()), index, path) {
+                return Err(ProofVerifyError::InternalError);
+            }
+            if inner_product(&gamma, column) != encoded_combined_row[index]
+                || inner_product(&eq_rows, column) != encoded_eval_row[index]
+            {
+                return Err(ProofVerifyError::InternalError);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<F: ReedSolomonField, ProofTranscript: Transcript> CommitmentScheme<ProofTranscript>
+    for Ligero<F, ProofTranscript>
+{
+    type Field = F;
+    type SRS = ();
+    type Setup = ();
+    type VerifierSetup = ();
+    type Commitment = LigeroCommitment;
+    type Proof = LigeroProof<F>;
+    type BatchedProof = LigeroBatchedProof<F>;
+    type OpeningHint = LigeroOpeningHint<F>;
+    const OPENING_REDUCTION: OpeningReduction<Self, ProofTranscript> =
+        OpeningReduction::BatchOpenings;
+
+    #[cfg(any(test, feature = "insecure-setup"))]
+    fn insecure_srs(_shapes: &[CommitShape]) -> Self::SRS {}
+    fn setup_from_srs(_srs: &Self::SRS, _shapes: &[CommitShape]) -> Result<Self::Setup, SRSError> {
+        Ok(())
+    }
+    fn verifier_setup(_setup: &Self::Setup) -> Self::VerifierSetup {}
+    fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
+        Self::commit_slice(poly.evals_ref(), setup)
+    }
+    #[tracing::instrument(skip_all, name = "Ligero::batch_commit")]
+    fn batch_commit(
+        evals: &[&[Self::Field]],
+        setup: &Self::Setup,
+        _batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        evals
+            .iter()
+            .map(|evals| Self::commit_slice(evals, setup))
+            .collect()
+    }
+    fn commit_slice(evals: &[Self::Field], _setup: &Self::Setup) -> Self::Commitment {
+        Self::commit_evals(evals).0
+    }
+    fn commit_with_hint(
+        poly: &DensePolynomial<Self::Field>,
+        _setup: &Self::Setup,
+    ) -> (Self::Commitment, Self::OpeningHint) {
+        Self::commit_evals(poly.evals_ref())
+    }
+    #[tracing::instrument(skip_all, name = "Ligero::batch_commit_with_hints")]
+    fn batch_commit_with_hints(
+        evals: &[&[Self::Field]],
+        _setup: &Self::Setup,
+        _batch_type: BatchType,
+    ) -> (Vec<Self::Commitment>, Vec<Self::OpeningHint>) {
+        evals.iter().map(|evals| Self::commit_evals(evals)).unzip()
+    }
+    #[tracing::instrument(skip_all, name = "Ligero::batch_commit_small_with_hints")]
+    fn batch_commit_small_with_hints<S: SmallScalar>(
+        evals: &[&[S]],
+        _setup: &Self::Setup,
+        _batch_type: BatchType,
+    ) -> (Vec<Self::Commitment>, Vec<Self::OpeningHint>) {
+        evals
+            .iter()
+            .map(|evals| Self::commit_evals(&small_scalars_to_field(evals)))
+            .unzip()
+    }
+    fn prove(
+        _setup: &Self::Setup,
+        poly: &DensePolynomial<Self::Field>,
+        opening_point: &[Self::Field],
+        transcript: &mut ProofTranscript,
+    ) -> Self::Proof {
+        Self::prove_evals(
+            poly.evals_ref(),
+            &LigeroOpeningHint::default(),
+            opening_point,
+            transcript,
+        )
+    }
+    fn batch_prove(
+        setup: &Self::Setup,
+        polynomials: &[&DensePolynomial<Self::Field>],
+        opening_point: &[Self::Field],
+        openings: &[Self::Field],
+        batch_type: BatchType,
+        transcript: &mut ProofTranscript,
+    ) -> Self::BatchedProof {
+        let no_hint = LigeroOpeningHint::default();
+        Self::batch_prove_with_hints(
+            setup,
+            polynomials,
+            &vec![&no_hint; polynomials.len()],
+            opening_point,
+            openings,
+            batch_type,
+            transcript,
+        )
+    }
+    fn batch_prove_with_hints(
+        _setup: &Self::Setup,
+        polynomials: &[&DensePolynomial<Self::Field>],
+        hints: &[&Self::OpeningHint],
+        opening_point: &[Self::Field],
+        _openings: &[Self::Field],
+        _batch_type: BatchType,
+        transcript: &mut ProofTranscript,
+    ) -> Self::BatchedProof {
+        assert_eq!(polynomials.len(), hints.len());
+        LigeroBatchedProof {
+            proofs: polynomials
+                .iter()
+                .zip(hints.iter())
+                .map(|(poly, hint)| {
+                    Self::prove_evals(poly.evals_ref(), hint, opening_point, transcript)
+                })
+                .collect(),
+        }
+    }
+
+    fn verify(
+        proof: &Self::Proof,
+        _setup: &Self::VerifierSetup,
+        transcript: &mut ProofTranscript,
+        opening_point: &[Self::Field],
+        opening: &Self::Field,
+        commitment: &Self::Commitment,
+    ) -> Result<(), ProofVerifyError> {
+        Self::verify_proof(proof, transcript, opening_point, opening, commitment)
+    }
+
+    fn batch_verify(
+        batch_proof: &Self::BatchedProof,
+        _setup: &Self::VerifierSetup,
+        opening_point: &[Self::Field],
+        openings: &[Self::Field],
+        commitments: &[&Self::Commitment],
+        transcript: &mut ProofTranscript,
+    ) -> Result<(), ProofVerifyError> {
+        if openings.len() != commitments.len() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                commitments.len(),
+                openings.len(),
+            ));
+        }
+        if batch_proof.proofs.len() != commitments.len() {
+            return Err(ProofVerifyError::NumOpeningProofs {
+                expected: commitments.len(),
+                actual: batch_proof.proofs.len(),
+            });
+        }
+        for ((proof, opening), commitment) in batch_proof
+            .proofs
+            .iter()
+            .zip(openings.iter())
+            .zip(commitments.iter())
+        {
+            Self::verify_proof(proof, transcript, opening_point, opening, commitment)?;
+        }
+        Ok(())
+    }
+
+    fn protocol_name() -> &'static [u8] {
+        b"ligero_commit"
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::utils::transcript::KeccakTranscript;
+    use ark_bn254::Fr;
+    use ark_std::{test_rng, One, Zero};
+
+    #[test]
+    fn radix2_fft_matches_evaluation() {
+        let mut rng = test_rng();
+        for log_len in 0..6 {
+            let message: Vec<Fr> = (0..1 << log_len).map(|_| Fr::random(&mut rng)).collect();
+            let codeword = Radix2Fft::new(log_len, LOG_BLOWUP).encode(&message);
+            assert_eq!(codeword.len(), message.len() << LOG_BLOWUP);
+
+            let omega = Fr::get_root_of_unity(codeword.len() as u64).unwrap();
+            let mut x = Fr::one();
+            for eval in codeword.iter() {
+                let expected = message
+                    .iter()
+                    .rev()
+                    .fold(Fr::zero(), |acc, coeff| acc * x + coeff);
+                assert_eq!(*eval, expected);
+                x *= omega;
+            }
+        }
+    }
+
+    /// Commits to, opens and verifies random polynomials of up to 8 variables, checking that
+    /// proofs reusing the encodings kept at commit time are the same as without them.
+    pub(crate) fn commit_prove_verify<F: ReedSolomonField>() {
+        let mut rng = test_rng();
+        for num_vars in 0..9 {
+            let poly = DensePolynomial::new(
+                (0..1 << num_vars)
+                    .map(|_| F::random(&mut rng))
+                    .collect::<Vec<_>>(),
+            );
+            let point: Vec<F> = (0..num_vars).map(|_| F::random(&mut rng)).collect();
+            let eval = poly.evaluate(&point);
+            let (commitment, hint) = Ligero::<F, KeccakTranscript>::commit_with_hint(&poly, &());
+            assert_eq!(
+                commitment,
+                Ligero::<F, KeccakTranscript>::commit(&poly, &())
+            );
+
+            let mut prover_transcript = KeccakTranscript::new(b"test_transcript");
+            let proof =
+                Ligero::<F, KeccakTranscript>::prove(&(), &poly, &point, &mut prover_transcript);
+            let mut hinted_transcript = KeccakTranscript::new(b"test_transcript");
+            let hinted_proof = Ligero::<F, KeccakTranscript>::batch_prove_with_hints(
+                &(),
+                &[&poly],
+                &[&hint],
+                &point,
+                &[eval],
+                BatchType::Big,
+                &mut hinted_transcript,
+            );
+            let mut proof_bytes = vec![];
+            proof.serialize_compressed(&mut proof_bytes).unwrap();
+            let mut hinted_proof_bytes = vec![];
+            hinted_proof.proofs[0]
+                .serialize_compressed(&mut hinted_proof_bytes)
+                .unwrap();
+            assert_eq!(proof_bytes, hinted_proof_bytes);
+
+            let mut verifier_transcript = KeccakTranscript::new(b"test_transcript");
+            verifier_transcript.compare_to(prover_transcript);
+            Ligero::<F, KeccakTranscript>::verify(
+                &proof,
+                &(),
+                &mut verifier_transcript,
+                &point,
+                &eval,
+                &commitment,
+            )
+            .unwrap();
+
+            let mut verifier_transcript = KeccakTranscript::new(b"test_transcript");
+            assert!(Ligero::<F, KeccakTranscript>::verify(
+                &proof,
+                &(),
+                &mut verifier_transcript,
+                &point,
+                &(eval + F::one()),
+                &commitment,
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn commit_prove_verify_bn254() {
+        commit_prove_verify::<Fr>();
+    }
+}
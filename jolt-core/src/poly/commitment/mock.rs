@@ -6,19 +6,23 @@ use crate::{
     field::JoltField,
     poly::dense_mlpoly::DensePolynomial,
     utils::{
-        errors::ProofVerifyError,
+        errors::{ProofVerifyError, SRSError},
         transcript::{AppendToTranscript, Transcript},
     },
 };
 
-use super::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
+use super::commitment_scheme::{
+    AdditivelyHomomorphic, BatchType, CommitShape, CommitmentScheme, OpeningReduction,
+};
+#[cfg(test)]
+use crate::msm::SmallScalar;
 
 #[derive(Clone)]
 pub struct MockCommitScheme<F: JoltField, ProofTranscript: Transcript> {
     _marker: PhantomData<(F, ProofTranscript)>,
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize, Default, Debug, PartialEq)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Default, Debug, PartialEq)]
 pub struct MockCommitment<F: JoltField> {
     poly: DensePolynomial<F>,
 }
@@ -40,12 +44,21 @@ where
     ProofTranscript: Transcript,
 {
     type Field = F;
+    type SRS = ();
     type Setup = ();
+    type VerifierSetup = ();
     type Commitment = MockCommitment<F>;
     type Proof = MockProof<F>;
     type BatchedProof = MockProof<F>;
+    type OpeningHint = ();
+    const OPENING_REDUCTION: OpeningReduction<Self, ProofTranscript> = OpeningReduction::sumcheck();
 
-    fn setup(_shapes: &[CommitShape]) -> Self::Setup {}
+    #[cfg(any(test, feature = "insecure-setup"))]
+    fn insecure_srs(_shapes: &[CommitShape]) -> Self::SRS {}
+    fn setup_from_srs(_srs: &Self::SRS, _shapes: &[CommitShape]) -> Result<Self::Setup, SRSError> {
+        Ok(())
+    }
+    fn verifier_setup(_setup: &Self::Setup) -> Self::VerifierSetup {}
     fn commit(poly: &DensePolynomial<Self::Field>, _setup: &Self::Setup) -> Self::Commitment {
         MockCommitment {
             poly: poly.to_owned(),
@@ -94,6 +107,52 @@ where
         }
     }
 
+    fn verify(
+        proof: &Self::Proof,
+        _setup: &Self::VerifierSetup,
+        _transcript: &mut ProofTranscript,
+        opening_point: &[Self::Field],
+        opening: &Self::Field,
+        commitment: &Self::Commitment,
+    ) -> Result<(), ProofVerifyError> {
+        let evaluation = commitment.poly.evaluate(opening_point);
+        if evaluation != *opening || proof.opening_point != opening_point {
+            return Err(ProofVerifyError::InternalError);
+        }
+        Ok(())
+    }
+
+    fn batch_verify(
+        batch_proof: &Self::BatchedProof,
+        _setup: &Self::VerifierSetup,
+        opening_point: &[Self::Field],
+        openings: &[Self::Field],
+        commitments: &[&Self::Commitment],
+        _transcript: &mut ProofTranscript,
+    ) -> Result<(), ProofVerifyError> {
+        if batch_proof.opening_point != opening_point || openings.len() != commitments.len() {
+            return Err(ProofVerifyError::InternalError);
+        }
+        for i in 0..openings.len() {
+            let evaluation = commitments[i].poly.evaluate(opening_point);
+            if evaluation != openings[i] {
+                return Err(ProofVerifyError::InternalError);
+            }
+        }
+        Ok(())
+    }
+
+    fn protocol_name() -> &'static [u8] {
+        b"mock_commit"
+    }
+}
+
+impl<F, ProofTranscript> AdditivelyHomomorphic<ProofTranscript>
+    for MockCommitScheme<F, ProofTranscript>
+where
+    F: JoltField,
+    ProofTranscript: Transcript,
+{
     fn combine_commitments(
         commitments: &[&Self::Commitment],
         coeffs: &[Self::Field],
@@ -114,39 +173,165 @@ where
         }
         MockCommitment { poly }
     }
+}
+
+/// Wraps a commitment scheme so that its openings are proven with
+/// [`OpeningReduction::BatchOpenings`], even if it is additively homomorphic. Lets the
+/// batch-opening path be tested with the existing schemes.
+#[cfg(test)]
+#[derive(Clone)]
+pub struct BatchOpeningScheme<PCS> {
+    _marker: PhantomData<PCS>,
+}
+
+#[cfg(test)]
+impl<PCS, ProofTranscript> CommitmentScheme<ProofTranscript> for BatchOpeningScheme<PCS>
+where
+    PCS: CommitmentScheme<ProofTranscript>,
+    ProofTranscript: Transcript,
+{
+    type Field = PCS::Field;
+    type SRS = PCS::SRS;
+    type Setup = PCS::Setup;
+    type VerifierSetup = PCS::VerifierSetup;
+    type Commitment = PCS::Commitment;
+    type Proof = PCS::Proof;
+    type BatchedProof = PCS::BatchedProof;
+    type OpeningHint = PCS::OpeningHint;
+    const OPENING_REDUCTION: OpeningReduction<Self, ProofTranscript> =
+        OpeningReduction::BatchOpenings;
+
+    #[cfg(any(test, feature = "insecure-setup"))]
+    fn insecure_srs(shapes: &[CommitShape]) -> Self::SRS {
+        PCS::insecure_srs(shapes)
+    }
+    fn setup_from_srs(srs: &Self::SRS, shapes: &[CommitShape]) -> Result<Self::Setup, SRSError> {
+        PCS::setup_from_srs(srs, shapes)
+    }
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup {
+        PCS::verifier_setup(setup)
+    }
+    fn with_msm_tables(setup: Self::Setup, num_windows: usize) -> Self::Setup {
+        PCS::with_msm_tables(setup, num_windows)
+    }
+    fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
+        PCS::commit(poly, setup)
+    }
+    fn batch_commit(
+        evals: &[&[Self::Field]],
+        gens: &Self::Setup,
+        batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        PCS::batch_commit(evals, gens, batch_type)
+    }
+    fn commit_slice(evals: &[Self::Field], setup: &Self::Setup) -> Self::Commitment {
+        PCS::commit_slice(evals, setup)
+    }
+    fn commit_small<S: SmallScalar>(evals: &[S], setup: &Self::Setup) -> Self::Commitment {
+        PCS::commit_small(evals, setup)
+    }
+    fn batch_commit_small<S: SmallScalar>(
+        evals: &[&[S]],
+        gens: &Self::Setup,
+        batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        PCS::batch_commit_small(evals, gens, batch_type)
+    }
+    fn commit_with_hint(
+        poly: &DensePolynomial<Self::Field>,
+        setup: &Self::Setup,
+    ) -> (Self::Commitment, Self::OpeningHint) {
+        PCS::commit_with_hint(poly, setup)
+    }
+    fn batch_commit_with_hints(
+        evals: &[&[Self::Field]],
+        gens: &Self::Setup,
+        batch_type: BatchType,
+    ) -> (Vec<Self::Commitment>, Vec<Self::OpeningHint>) {
+        PCS::batch_commit_with_hints(evals, gens, batch_type)
+    }
+    fn batch_commit_small_with_hints<S: SmallScalar>(
+        evals: &[&[S]],
+        gens: &Self::Setup,
+        batch_type: BatchType,
+    ) -> (Vec<Self::Commitment>, Vec<Self::OpeningHint>) {
+        PCS::batch_commit_small_with_hints(evals, gens, batch_type)
+    }
+    fn prove(
+        setup: &Self::Setup,
+        poly: &DensePolynomial<Self::Field>,
+        opening_point: &[Self::Field],
+        transcript: &mut ProofTranscript,
+    ) -> Self::Proof {
+        PCS::prove(setup, poly, opening_point, transcript)
+    }
+    fn batch_prove(
+        setup: &Self::Setup,
+        polynomials: &[&DensePolynomial<Self::Field>],
+        opening_point: &[Self::Field],
+        openings: &[Self::Field],
+        batch_type: BatchType,
+        transcript: &mut ProofTranscript,
+    ) -> Self::BatchedProof {
+        PCS::batch_prove(
+            setup,
+            polynomials,
+            opening_point,
+            openings,
+            batch_type,
+            transcript,
+        )
+    }
+    fn batch_prove_with_hints(
+        setup: &Self::Setup,
+        polynomials: &[&DensePolynomial<Self::Field>],
+        hints: &[&Self::OpeningHint],
+        opening_point: &[Self::Field],
+        openings: &[Self::Field],
+        batch_type: BatchType,
+        transcript: &mut ProofTranscript,
+    ) -> Self::BatchedProof {
+        PCS::batch_prove_with_hints(
+            setup,
+            polynomials,
+            hints,
+            opening_point,
+            openings,
+            batch_type,
+            transcript,
+        )
+    }
 
     fn verify(
         proof: &Self::Proof,
-        _setup: &Self::Setup,
-        _transcript: &mut ProofTranscript,
+        setup: &Self::VerifierSetup,
+        transcript: &mut ProofTranscript,
         opening_point: &[Self::Field],
         opening: &Self::Field,
         commitment: &Self::Commitment,
     ) -> Result<(), ProofVerifyError> {
-        let evaluation = commitment.poly.evaluate(opening_point);
-        assert_eq!(evaluation, *opening);
-        assert_eq!(proof.opening_point, opening_point);
-        Ok(())
+        PCS::verify(proof, setup, transcript, opening_point, opening, commitment)
     }
 
     fn batch_verify(
         batch_proof: &Self::BatchedProof,
-        _setup: &Self::Setup,
+        setup: &Self::VerifierSetup,
         opening_point: &[Self::Field],
         openings: &[Self::Field],
         commitments: &[&Self::Commitment],
-        _transcript: &mut ProofTranscript,
+        transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
-        assert_eq!(batch_proof.opening_point, opening_point);
-        assert_eq!(openings.len(), commitments.len());
-        for i in 0..openings.len() {
-            let evaluation = commitments[i].poly.evaluate(opening_point);
-            assert_eq!(evaluation, openings[i]);
-        }
-        Ok(())
+        PCS::batch_verify(
+            batch_proof,
+            setup,
+            opening_point,
+            openings,
+            commitments,
+            transcript,
+        )
     }
 
     fn protocol_name() -> &'static [u8] {
-        b"mock_commit"
+        PCS::protocol_name()
     }
 }
@@ -3,8 +3,11 @@ pub mod commitment_scheme;
 pub mod hyperkzg;
 pub mod hyrax;
 pub mod kzg;
+pub mod ligero;
 pub mod pedersen;
+mod ptau;
+pub mod srs_file;
 pub mod zeromorph;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "test-utils"))]
 pub mod mock;
@@ -1,16 +1,21 @@
 use ark_ec::CurveGroup;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate, Write,
+};
 use ark_std::rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
 use sha3::digest::{ExtendableOutput, Update};
 use sha3::Shake256;
 use std::io::Read;
+use std::sync::Arc;
 
-use crate::msm::VariableBaseMSM;
+use crate::msm::{FixedBaseMSMTable, VariableBaseMSM};
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone)]
 pub struct PedersenGenerators<G: CurveGroup> {
     pub generators: Vec<G>,
+    // fixed-base MSM table of the generators, rebuilt rather than serialized
+    msm_table: Option<Arc<FixedBaseMSMTable<G>>>,
 }
 
 impl<G: CurveGroup> PedersenGenerators<G> {
@@ -32,7 +37,30 @@ impl<G: CurveGroup> PedersenGenerators<G> {
             generators.push(G::rand(&mut rng));
         }
 
-        Self { generators }
+        Self {
+            generators,
+            msm_table: None,
+        }
+    }
+
+    /// Precomputes a [`FixedBaseMSMTable`] of the generators with `num_windows` windows, which
+    /// commitments then use for large inputs. The table takes `num_windows` times the memory
+    /// of the generators, so generators don't have one unless this is called.
+    pub fn with_msm_table(mut self, num_windows: usize) -> Self {
+        self.msm_table = Some(Arc::new(FixedBaseMSMTable::new(
+            &G::normalize_batch(&self.generators),
+            num_windows,
+        )));
+        self
+    }
+
+    pub fn without_msm_table(mut self) -> Self {
+        self.msm_table = None;
+        self
+    }
+
+    pub fn msm_table(&self) -> Option<&FixedBaseMSMTable<G>> {
+        self.msm_table.as_deref()
     }
 
     pub fn clone_n(&self, n: usize) -> PedersenGenerators<G> {
@@ -45,10 +73,60 @@ impl<G: CurveGroup> PedersenGenerators<G> {
         let slice = &self.generators[..n];
         PedersenGenerators {
             generators: slice.into(),
+            msm_table: None,
         }
     }
 }
 
+impl<G: CurveGroup> CanonicalSerialize for PedersenGenerators<G> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.generators.serialize_with_mode(&mut writer, compress)?;
+        self.msm_table
+            .as_ref()
+            .map(|table| table.num_windows())
+            .serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.generators.serialized_size(compress)
+            + self
+                .msm_table
+                .as_ref()
+                .map(|table| table.num_windows())
+                .serialized_size(compress)
+    }
+}
+
+impl<G: CurveGroup> Valid for PedersenGenerators<G> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.generators.check()
+    }
+}
+
+impl<G: CurveGroup> CanonicalDeserialize for PedersenGenerators<G> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let generators = Vec::<G>::deserialize_with_mode(&mut reader, compress, validate)?;
+        let num_windows =
+            FixedBaseMSMTable::<G>::deserialize_num_windows(&mut reader, compress, validate)?;
+        let generators = Self {
+            generators,
+            msm_table: None,
+        };
+        Ok(match num_windows {
+            Some(num_windows) => generators.with_msm_table(num_windows),
+            None => generators,
+        })
+    }
+}
+
 pub trait PedersenCommitment<G: CurveGroup>: Sized {
     fn commit(&self, gens: &PedersenGenerators<G>) -> G;
     fn commit_vector(inputs: &[Self], bases: &[G::Affine]) -> G;
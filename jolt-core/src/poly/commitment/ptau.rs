@@ -0,0 +1,177 @@
+//! Reader for the `.ptau` powers-of-tau files produced by snarkjs ceremonies, e.g. the
+//! Perpetual Powers of Tau transcripts.
+//!
+//! A `.ptau` file starts with the magic `ptau`, a `u32` version and a `u32` section count,
+//! followed by sections each prefixed with a `u32` type and a `u64` byte length. Field
+//! elements are `n8`-byte little-endian integers in Montgomery form; an extension field
+//! element is its base prime field coefficients in order, and a point is its affine `x`
+//! followed by `y`.
+use crate::utils::errors::SRSError;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ff::{BigInteger, Field, PrimeField};
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"ptau";
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+
+/// Reads the first `num_g1_powers` points of the `tauG1` section and the first
+/// `num_g2_powers` points of the `tauG2` section. The points are checked to have canonical
+/// coordinates, but not to be on the curve or in the prime-order subgroup.
+pub(crate) fn read_powers<G1: SWCurveConfig, G2: SWCurveConfig>(
+    path: &Path,
+    num_g1_powers: usize,
+    num_g2_powers: usize,
+) -> Result<(Vec<Affine<G1>>, Vec<Affine<G2>>), SRSError> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(SRSError::Format("not a .ptau file".to_string()));
+    }
+    let _version = read_u32(&mut reader)?;
+    let num_sections = read_u32(&mut reader)?;
+
+    let mut sections = HashMap::new();
+    for _ in 0..num_sections {
+        let section_type = read_u32(&mut reader)?;
+        let size = read_u64(&mut reader)?;
+        sections.insert(section_type, (reader.stream_position()?, size));
+        let size = i64::try_from(size)
+            .map_err(|_| SRSError::Format(format!("section {section_type} is too large")))?;
+        reader.seek_relative(size)?;
+    }
+    let section = |section_type: u32| {
+        sections
+            .get(&section_type)
+            .copied()
+            .ok_or_else(|| SRSError::Format(format!("missing section {section_type}")))
+    };
+
+    // The header holds the byte size of a base field element followed by the base field
+    // modulus, which identifies the curve.
+    let (header_offset, header_size) = section(HEADER_SECTION)?;
+    reader.seek(SeekFrom::Start(header_offset))?;
+    let n8 = read_u32(&mut reader)? as usize;
+    // Checked before allocating, as `n8` comes from the file
+    let expected_modulus = <G1::BaseField as Field>::BasePrimeField::MODULUS.to_bytes_le();
+    if n8 != expected_modulus.len() || header_size < 4 + n8 as u64 {
+        return Err(SRSError::Format(format!("invalid field element size {n8}")));
+    }
+    let mut modulus = vec![0u8; n8];
+    reader.read_exact(&mut modulus)?;
+    if modulus != expected_modulus {
+        return Err(SRSError::Format(
+            "file is for a different curve".to_string(),
+        ));
+    }
+
+    let g1_powers = read_points(
+        &mut reader,
+        section(TAU_G1_SECTION)?,
+        n8,
+        num_g1_powers,
+        "G1",
+    )?;
+    let g2_powers = read_points(
+        &mut reader,
+        section(TAU_G2_SECTION)?,
+        n8,
+        num_g2_powers,
+        "G2",
+    )?;
+    Ok((g1_powers, g2_powers))
+}
+
+fn read_points<C: SWCurveConfig>(
+    reader: &mut BufReader<File>,
+    (offset, size): (u64, u64),
+    n8: usize,
+    num_points: usize,
+    group: &'static str,
+) -> Result<Vec<Affine<C>>, SRSError> {
+    let point_size = 2 * C::BaseField::extension_degree() as usize * n8;
+    let coordinate_size = point_size / 2;
+    let available = usize::try_from(size / point_size as u64).unwrap_or(usize::MAX);
+    if available < num_points {
+        return Err(SRSError::TooFewPowers {
+            group,
+            required: num_points,
+            available,
+        });
+    }
+    // At most the section size, which may not fit in a `usize` on 32-bit targets
+    let num_bytes = num_points
+        .checked_mul(point_size)
+        .ok_or_else(|| SRSError::Format(format!("too many {group} powers requested")))?;
+
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut bytes = vec![0u8; num_bytes];
+    reader.read_exact(&mut bytes)?;
+
+    let r_inv = montgomery_r::<<C::BaseField as Field>::BasePrimeField>(n8)
+        .inverse()
+        .unwrap();
+    bytes
+        .par_chunks(2 * coordinate_size)
+        .enumerate()
+        .map(|(index, point)| {
+            let (x, y) = point.split_at(coordinate_size);
+            match (read_coordinate(x, n8, r_inv), read_coordinate(y, n8, r_inv)) {
+                (Some(x), Some(y)) => Ok(Affine::new_unchecked(x, y)),
+                _ => Err(SRSError::Format(format!(
+                    "{group} power {index} has a non-canonical coordinate"
+                ))),
+            }
+        })
+        .collect()
+}
+
+/// Montgomery form stores `a` as `a * R`, with `R = 2^(8 * n8)`.
+pub(super) fn montgomery_r<F: PrimeField>(n8: usize) -> F {
+    F::from(2u64).pow([8 * n8 as u64])
+}
+
+/// Decodes a coordinate stored as `n8`-byte little-endian Montgomery form base prime field
+/// coefficients, given the inverse of [`montgomery_r`].
+pub(super) fn read_coordinate<F: Field>(
+    bytes: &[u8],
+    n8: usize,
+    r_inv: F::BasePrimeField,
+) -> Option<F> {
+    let modulus = F::BasePrimeField::MODULUS.to_bytes_le();
+    let coefficients = bytes
+        .chunks(n8)
+        .map(|coefficient| {
+            // `from_le_bytes_mod_order` would silently reduce a non-canonical encoding
+            is_canonical(coefficient, &modulus)
+                .then(|| F::BasePrimeField::from_le_bytes_mod_order(coefficient) * r_inv)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    F::from_base_prime_field_elems(&coefficients)
+}
+
+/// Whether the little-endian integer `bytes` is less than the little-endian `modulus` of
+/// the same length.
+pub(super) fn is_canonical(bytes: &[u8], modulus: &[u8]) -> bool {
+    bytes.iter().rev().cmp(modulus.iter().rev()) == Ordering::Less
+}
+
+pub(super) fn read_u32(reader: &mut impl Read) -> Result<u32, SRSError> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+pub(super) fn read_u64(reader: &mut impl Read) -> Result<u64, SRSError> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
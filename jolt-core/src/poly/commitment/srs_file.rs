@@ -0,0 +1,518 @@
+//! A compact on-disk format for KZG SRSs, which can be memory-mapped so that large SRSs are
+//! loaded without copying them.
+//!
+//! All integers are little-endian. The header consists of
+//!
+//! | field          | bytes | contents                                              |
+//! |----------------|-------|-------------------------------------------------------|
+//! | magic          | 8     | `JOLTSRS\0`                                           |
+//! | version        | 4     | 1                                                     |
+//! | `n8`           | 4     | size of a base prime field element                    |
+//! | modulus        | `n8`  | base prime field modulus, which identifies the curve  |
+//! | G1 powers      | 8     | number of G1 powers                                   |
+//! | G2 powers      | 8     | number of G2 powers                                   |
+//! | G1 products    | 8     | length of `SRS::g_products`                           |
+//! | hash           | 32    | Keccak-256 of everything after the header             |
+//!
+//! and is followed by the G1 powers, the G2 powers and `g_products`. A point is stored as
+//! its affine `x` and `y` coordinates, each as `n8`-byte Montgomery form base prime field
+//! coefficients (as in `.ptau` files), then an infinity byte, zero-padded to a multiple of
+//! 8 bytes. On little-endian targets this is arkworks' in-memory representation of an
+//! affine point, so mapped points are used in place; elsewhere, they are decoded.
+use super::kzg::SRS;
+use super::ptau::{is_canonical, montgomery_r, read_coordinate, read_u32, read_u64};
+use crate::utils::errors::SRSError;
+use ark_ec::pairing::Pairing;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use memmap2::Mmap;
+use rayon::prelude::*;
+use sha3::{Digest, Keccak256};
+use std::fmt::{self, Debug};
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::ops::Deref;
+use std::path::Path;
+use std::ptr::addr_of_mut;
+use std::sync::Arc;
+
+const MAGIC: &[u8; 8] = b"JOLTSRS\0";
+const VERSION: u32 = 1;
+const HASH_SIZE: usize = 32;
+
+/// The points of an SRS, either owned or used in place from a memory-mapped SRS file.
+#[derive(Clone)]
+pub struct Points<T>(Storage<T>);
+
+#[derive(Clone)]
+enum Storage<T> {
+    Owned(Vec<T>),
+    Mapped {
+        mmap: Arc<Mmap>,
+        offset: usize,
+        len: usize,
+    },
+}
+
+impl<T: Clone> Points<T> {
+    /// The first `len` points, which are not copied if they are memory-mapped.
+    pub fn prefix(&self, len: usize) -> Self {
+        assert!(len <= self.len());
+        match &self.0 {
+            Storage::Owned(points) => Self(Storage::Owned(points[..len].to_vec())),
+            Storage::Mapped { mmap, offset, .. } => Self(Storage::Mapped {
+                mmap: mmap.clone(),
+                offset: *offset,
+                len,
+            }),
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for Points<T> {
+    fn from(points: Vec<T>) -> Self {
+        Self(Storage::Owned(points))
+    }
+}
+
+impl<T> Deref for Points<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match &self.0 {
+            Storage::Owned(points) => points,
+            // SAFETY: `SRS::load_from_file` only maps points whose encoding it has checked to
+            // be a valid in-memory representation of `T`, at offsets aligned for `T`, and its
+            // caller guarantees that the file is not modified afterwards.
+            Storage::Mapped { mmap, offset, len } => unsafe {
+                std::slice::from_raw_parts(mmap.as_ptr().add(*offset).cast(), *len)
+            },
+        }
+    }
+}
+
+impl<T: Debug> Debug for Points<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T: CanonicalSerialize> CanonicalSerialize for Points<T> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.deref().serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.deref().serialized_size(compress)
+    }
+}
+
+impl<T: Valid> Valid for Points<T> {
+    fn check(&self) -> Result<(), SerializationError> {
+        T::batch_check(self.iter())
+    }
+}
+
+impl<T: CanonicalDeserialize> CanonicalDeserialize for Points<T> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Vec::<T>::deserialize_with_mode(reader, compress, validate).map(Self::from)
+    }
+}
+
+impl<P: Pairing> SRS<P> {
+    /// Writes the SRS in the format described in the [module documentation](self).
+    pub fn save_to_file<G1, G2>(&self, path: impl AsRef<Path>) -> Result<(), SRSError>
+    where
+        P: Pairing<G1Affine = Affine<G1>, G2Affine = Affine<G2>>,
+        G1: SWCurveConfig,
+        G2: SWCurveConfig,
+    {
+        let modulus = modulus::<G1>();
+        let n8 = modulus.len();
+        let mut header = MAGIC.to_vec();
+        header.extend(VERSION.to_le_bytes());
+        header.extend((n8 as u32).to_le_bytes());
+        header.extend(&modulus);
+        for len in [
+            self.g1_powers.len(),
+            self.g2_powers.len(),
+            self.g_products.len(),
+        ] {
+            header.extend((len as u64).to_le_bytes());
+        }
+        // The hash is filled in once the points are written
+        header.extend([0u8; HASH_SIZE]);
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&header)?;
+        let mut hasher = Keccak256::new();
+        write_points(&mut writer, &mut hasher, &self.g1_powers, n8)?;
+        write_points(&mut writer, &mut hasher, &self.g2_powers, n8)?;
+        write_points(&mut writer, &mut hasher, &self.g_products, n8)?;
+
+        writer.seek(SeekFrom::Start((header.len() - HASH_SIZE) as u64))?;
+        writer.write_all(&hasher.finalize())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Loads an SRS written by [`SRS::save_to_file`], checking its header and hash. If the
+    /// file's point encoding is the in-memory representation of points, it is memory-mapped
+    /// and its points are used without copying them.
+    ///
+    /// The points are not checked to be valid powers of tau, so the file must have been
+    /// written from a checked SRS, e.g. one loaded with [`SRS::from_ptau_file`].
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated, by this or any other process, until the
+    /// returned SRS and everything derived from it are dropped. The points are checked when
+    /// the file is loaded, but mapped points are read from the file in place afterwards,
+    /// so a modified file is undefined behavior.
+    pub unsafe fn load_from_file<G1, G2>(path: impl AsRef<Path>) -> Result<Self, SRSError>
+    where
+        P: Pairing<G1Affine = Affine<G1>, G2Affine = Affine<G2>>,
+        G1: SWCurveConfig,
+        G2: SWCurveConfig,
+    {
+        let file = File::open(path)?;
+        // SAFETY: the caller guarantees that the file is not modified while it is mapped
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        let mut reader = &mmap[..];
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(SRSError::Format("not an SRS file".to_string()));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != VERSION {
+            return Err(SRSError::Format(format!(
+                "unsupported SRS file version {version}"
+            )));
+        }
+        let n8 = read_u32(&mut reader)? as usize;
+        let expected_modulus = self::modulus::<G1>();
+        // Checked before allocating, as `n8` comes from the file
+        if n8 != expected_modulus.len() {
+            return Err(SRSError::Format(format!("invalid field element size {n8}")));
+        }
+        let mut modulus = vec![0u8; n8];
+        reader.read_exact(&mut modulus)?;
+        if modulus != expected_modulus {
+            return Err(SRSError::Format(
+                "file is for a different curve".to_string(),
+            ));
+        }
+        let num_g1_powers = read_u64(&mut reader)? as usize;
+        let num_g2_powers = read_u64(&mut reader)? as usize;
+        let num_g_products = read_u64(&mut reader)? as usize;
+        let mut hash = [0u8; HASH_SIZE];
+        reader.read_exact(&mut hash)?;
+
+        let header_size = mmap.len() - reader.len();
+        let (g1_size, g2_size) = (point_size::<G1>(n8), point_size::<G2>(n8));
+        let g1_powers_offset = header_size;
+        let g2_powers_offset = section_end(g1_powers_offset, num_g1_powers, g1_size)?;
+        let g_products_offset = section_end(g2_powers_offset, num_g2_powers, g2_size)?;
+        if section_end(g_products_offset, num_g_products, g1_size)? != mmap.len() {
+            return Err(SRSError::Format(
+                "file size does not match its header".to_string(),
+            ));
+        }
+        if Keccak256::digest(&mmap[header_size..]).as_slice() != hash {
+            return Err(SRSError::Format("file does not match its hash".to_string()));
+        }
+
+        // The map is page-aligned, and points are a multiple of 8 bytes long, so every point
+        // is aligned if the first one is.
+        if header_size % 8 == 0
+            && encoding_is_in_memory::<G1>(n8)
+            && encoding_is_in_memory::<G2>(n8)
+        {
+            check_mapped_points::<G1>(&mmap, g1_powers_offset, num_g1_powers, n8)?;
+            check_mapped_points::<G2>(&mmap, g2_powers_offset, num_g2_powers, n8)?;
+            check_mapped_points::<G1>(&mmap, g_products_offset, num_g_products, n8)?;
+            let mmap = Arc::new(mmap);
+            let mapped = |offset, len| Storage::Mapped {
+                mmap: mmap.clone(),
+                offset,
+                len,
+            };
+            return Ok(Self {
+                g1_powers: Points(mapped(g1_powers_offset, num_g1_powers)),
+                g2_powers: Points(mapped(g2_powers_offset, num_g2_powers)),
+                g_products: Points(mapped(g_products_offset, num_g_products)),
+            });
+        }
+
+        Ok(Self {
+            g1_powers: read_points(&mmap[g1_powers_offset..g2_powers_offset], n8)?.into(),
+            g2_powers: read_points(&mmap[g2_powers_offset..g_products_offset], n8)?.into(),
+            g_products: read_points(&mmap[g_products_offset..], n8)?.into(),
+        })
+    }
+}
+
+fn modulus<C: SWCurveConfig>() -> Vec<u8> {
+    <C::BaseField as Field>::BasePrimeField::MODULUS.to_bytes_le()
+}
+
+fn coordinate_size<C: SWCurveConfig>(n8: usize) -> usize {
+    C::BaseField::extension_degree() as usize * n8
+}
+
+fn point_size<C: SWCurveConfig>(n8: usize) -> usize {
+    (2 * coordinate_size::<C>(n8) + 1).next_multiple_of(8)
+}
+
+fn section_end(offset: usize, num_points: usize, point_size: usize) -> Result<usize, SRSError> {
+    num_points
+        .checked_mul(point_size)
+        .and_then(|size| size.checked_add(offset))
+        .ok_or_else(|| SRSError::Format("file size does not match its header".to_string()))
+}
+
+fn encode_point<C: SWCurveConfig>(
+    point: &Affine<C>,
+    n8: usize,
+    r: <C::BaseField as Field>::BasePrimeField,
+    out: &mut Vec<u8>,
+) {
+    let start = out.len();
+    for coefficient in point
+        .x
+        .to_base_prime_field_elements()
+        .chain(point.y.to_base_prime_field_elements())
+    {
+        out.extend((coefficient * r).into_bigint().to_bytes_le());
+    }
+    out.push(point.infinity as u8);
+    out.resize(start + point_size::<C>(n8), 0);
+}
+
+fn write_points<C: SWCurveConfig>(
+    writer: &mut impl Write,
+    hasher: &mut Keccak256,
+    points: &[Affine<C>],
+    n8: usize,
+) -> Result<(), SRSError> {
+    let r = montgomery_r(n8);
+    for chunk in points.chunks(1 << 16) {
+        let bytes: Vec<u8> = chunk
+            .par_iter()
+            .flat_map_iter(|point| {
+                let mut bytes = Vec::with_capacity(point_size::<C>(n8));
+                encode_point(point, n8, r, &mut bytes);
+                bytes
+            })
+            .collect();
+        hasher.update(&bytes);
+        writer.write_all(&bytes)?;
+    }
+    Ok(())
+}
+
+fn read_points<C: SWCurveConfig>(bytes: &[u8], n8: usize) -> Result<Vec<Affine<C>>, SRSError> {
+    let coordinate_size = coordinate_size::<C>(n8);
+    let r_inv = montgomery_r::<<C::BaseField as Field>::BasePrimeField>(n8)
+        .inverse()
+        .unwrap();
+    bytes
+        .par_chunks(point_size::<C>(n8))
+        .enumerate()
+        .map(|(index, point)| {
+            let x = read_coordinate(&point[..coordinate_size], n8, r_inv);
+            let y = read_coordinate(&point[coordinate_size..2 * coordinate_size], n8, r_inv);
+            match (x, y, point[2 * coordinate_size]) {
+                (Some(x), Some(y), 0) => Ok(Affine::new_unchecked(x, y)),
+                (Some(_), Some(_), 1) => Ok(Affine::identity()),
+                _ => Err(SRSError::Format(format!("point {index} is malformed"))),
+            }
+        })
+        .collect()
+}
+
+/// Whether encoded points are exactly the in-memory representation of `Affine<C>`, so
+/// that they can be used in place.
+fn encoding_is_in_memory<C: SWCurveConfig>(n8: usize) -> bool {
+    let size = point_size::<C>(n8);
+    if size != std::mem::size_of::<Affine<C>>() || std::mem::align_of::<Affine<C>>() > 8 {
+        return false;
+    }
+
+    let r = montgomery_r(n8);
+    [Affine::<C>::generator(), Affine::<C>::identity()]
+        .iter()
+        .all(|point| {
+            let mut encoding = vec![];
+            encode_point(point, n8, r, &mut encoding);
+
+            // Write the fields into zeroed memory so that the padding bytes are initialized.
+            // Base field elements are arrays of limbs, so they have no padding of their own.
+            let mut memory = vec![0u64; size / 8];
+            let ptr = memory.as_mut_ptr().cast::<Affine<C>>();
+            unsafe {
+                addr_of_mut!((*ptr).x).write(point.x);
+                addr_of_mut!((*ptr).y).write(point.y);
+                addr_of_mut!((*ptr).infinity).write(point.infinity);
+            }
+            bytemuck::cast_slice::<u64, u8>(&memory) == encoding
+        })
+}
+
+/// Checks that each mapped point has canonical coordinates, i.e. that every Montgomery
+/// form limb array is less than the modulus as arkworks assumes, and that its infinity
+/// flag is a valid `bool`.
+fn check_mapped_points<C: SWCurveConfig>(
+    mmap: &[u8],
+    offset: usize,
+    num_points: usize,
+    n8: usize,
+) -> Result<(), SRSError> {
+    let modulus = modulus::<C>();
+    let (size, flag) = (point_size::<C>(n8), 2 * coordinate_size::<C>(n8));
+    match (0..num_points).into_par_iter().find_first(|index| {
+        let point = &mmap[offset + index * size..][..size];
+        point[flag] > 1
+            || !point[..flag]
+                .chunks(n8)
+                .all(|coefficient| is_canonical(coefficient, &modulus))
+    }) {
+        Some(index) => Err(SRSError::Format(format!("point {index} is malformed"))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, G1Affine, G2Affine};
+    use ark_ec::CurveGroup;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    fn test_srs() -> SRS<Bn254> {
+        SRS::<Bn254>::setup(&mut ChaCha20Rng::seed_from_u64(0), 15, 2)
+    }
+
+    fn test_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("jolt-{name}-{}.srs", std::process::id()))
+    }
+
+    #[test]
+    fn save_and_load() {
+        let srs = test_srs();
+        let path = test_path("save-and-load");
+        srs.save_to_file(&path).unwrap();
+
+        let loaded = unsafe { SRS::<Bn254>::load_from_file(&path) }.unwrap();
+        assert_eq!(loaded.g1_powers[..], srs.g1_powers[..]);
+        assert_eq!(loaded.g2_powers[..], srs.g2_powers[..]);
+        assert_eq!(loaded.g_products[..], srs.g_products[..]);
+        if cfg!(target_endian = "little") {
+            assert!(matches!(loaded.g1_powers.0, Storage::Mapped { .. }));
+            assert!(matches!(
+                loaded.truncate(8, 2).unwrap().g1_powers.0,
+                Storage::Mapped { len: 8, .. }
+            ));
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn load_rejects_corrupted_file() {
+        let srs = test_srs();
+        let path = test_path("corrupted");
+        srs.save_to_file(&path).unwrap();
+
+        let mut bytes = std::fs::read(&path).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            unsafe { SRS::<Bn254>::load_from_file(&path) },
+            Err(SRSError::Format(_))
+        ));
+
+        bytes.truncate(bytes.len() - 1);
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            unsafe { SRS::<Bn254>::load_from_file(&path) },
+            Err(SRSError::Format(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn load_rejects_non_canonical_points() {
+        let srs = test_srs();
+        let path = test_path("non-canonical");
+        srs.save_to_file(&path).unwrap();
+
+        // Replace the `x` coordinate of the first G1 power with the modulus, and update the
+        // hash so that only the point itself is invalid
+        let mut bytes = std::fs::read(&path).unwrap();
+        let header_size = bytes.len()
+            - [
+                (srs.g1_powers.len(), point_size::<ark_bn254::g1::Config>(32)),
+                (srs.g2_powers.len(), point_size::<ark_bn254::g2::Config>(32)),
+                (
+                    srs.g_products.len(),
+                    point_size::<ark_bn254::g1::Config>(32),
+                ),
+            ]
+            .iter()
+            .map(|(len, size)| len * size)
+            .sum::<usize>();
+        bytes[header_size..header_size + 32].copy_from_slice(&modulus::<ark_bn254::g1::Config>());
+        let hash = Keccak256::digest(&bytes[header_size..]);
+        bytes[header_size - HASH_SIZE..header_size].copy_from_slice(&hash);
+        std::fs::write(&path, &bytes).unwrap();
+
+        assert!(matches!(
+            unsafe { SRS::<Bn254>::load_from_file(&path) },
+            Err(SRSError::Format(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn encode_and_decode_points() {
+        let srs = test_srs();
+        let g1_points = [
+            srs.g1_powers[3],
+            G1Affine::identity(),
+            (srs.g1_powers[1] + srs.g1_powers[2]).into_affine(),
+        ];
+        let g2_points = [srs.g2_powers[1], G2Affine::identity()];
+
+        let mut bytes = vec![];
+        for point in g1_points.iter() {
+            encode_point(point, 32, montgomery_r(32), &mut bytes);
+        }
+        assert_eq!(
+            read_points::<ark_bn254::g1::Config>(&bytes, 32).unwrap(),
+            g1_points
+        );
+
+        let mut bytes = vec![];
+        for point in g2_points.iter() {
+            encode_point(point, 32, montgomery_r(32), &mut bytes);
+        }
+        assert_eq!(
+            read_points::<ark_bn254::g2::Config>(&bytes, 32).unwrap(),
+            g2_points
+        );
+    }
+}
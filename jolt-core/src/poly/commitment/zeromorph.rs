@@ -4,12 +4,12 @@
 use std::{iter, marker::PhantomData};
 
 use crate::field;
-use crate::msm::VariableBaseMSM;
+use crate::msm::{SmallScalar, VariableBaseMSM};
 use crate::poly::{dense_mlpoly::DensePolynomial, unipoly::UniPoly};
 use crate::utils::mul_0_1_optimized;
 use crate::utils::thread::unsafe_allocate_zero_vec;
 use crate::utils::{
-    errors::ProofVerifyError,
+    errors::{ProofVerifyError, SRSError},
     transcript::{AppendToTranscript, Transcript},
 };
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
@@ -17,6 +17,7 @@ use ark_ff::{batch_inversion, Field};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{One, Zero};
 use itertools::izip;
+#[cfg(any(test, feature = "insecure-setup"))]
 use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
 use rand_core::{CryptoRng, RngCore};
 use std::sync::Arc;
@@ -25,7 +26,9 @@ use tracing::trace_span;
 use rayon::prelude::*;
 
 use super::{
-    commitment_scheme::{BatchType, CommitShape, CommitmentScheme},
+    commitment_scheme::{
+        AdditivelyHomomorphic, BatchType, CommitShape, CommitmentScheme, OpeningReduction,
+    },
     kzg::{KZGProverKey, KZGVerifierKey, UnivariateKZG, SRS},
 };
 
@@ -36,6 +39,26 @@ impl<P: Pairing> ZeromorphSRS<P> {
         Self(Arc::new(SRS::setup(rng, max_degree, max_degree)))
     }
 
+    /// Derives the SRS from a fixed, public seed. Anyone can recover its trapdoor and forge
+    /// proofs, so this is only available in tests and with the `insecure-setup` feature.
+    #[cfg(any(test, feature = "insecure-setup"))]
+    pub fn insecure_setup(max_degree: usize) -> Self {
+        Self::setup(&mut Self::insecure_rng(), max_degree)
+    }
+
+    #[cfg(any(test, feature = "insecure-setup"))]
+    fn insecure_rng() -> ChaCha20Rng {
+        ChaCha20Rng::from_seed(*b"ZEROMORPH_POLY_COMMITMENT_SCHEME")
+    }
+
+    /// Uses the powers of an externally generated SRS, e.g. one loaded with
+    /// [`SRS::from_ptau_file`], supporting polynomials of degree up to `max_degree`.
+    pub fn from_srs(srs: &SRS<P>, max_degree: usize) -> Result<Self, SRSError> {
+        // `trim` opens against the G2 power at offset `g1_powers.len() - max_degree`, which
+        // is 1 once the G1 powers are truncated to `max_degree + 1`
+        Ok(Self(Arc::new(srs.truncate(max_degree + 1, 2)?)))
+    }
+
     pub fn trim(self, max_degree: usize) -> (ZeromorphProverKey<P>, ZeromorphVerifierKey<P>) {
         let (commit_pp, kzg_vk) = SRS::trim(self.0.clone(), max_degree);
         let offset = self.0.g1_powers.len() - max_degree;
@@ -52,19 +75,19 @@ impl<P: Pairing> ZeromorphSRS<P> {
 }
 
 //TODO: adapt interface to have prover and verifier key
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ZeromorphProverKey<P: Pairing> {
     pub commit_pp: KZGProverKey<P>,
     pub open_pp: KZGProverKey<P>,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ZeromorphVerifierKey<P: Pairing> {
     pub kzg_vk: KZGVerifierKey<P>,
     pub tau_N_max_sub_2_N: P::G2Affine,
 }
 
-#[derive(Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ZeromorphCommitment<P: Pairing>(P::G1Affine);
 
 impl<P: Pairing> Default for ZeromorphCommitment<P> {
@@ -357,7 +380,8 @@ where
 
         let span = trace_span!("f_batched");
         let enter = span.enter();
-        let num_chunks = rayon::current_num_threads().next_power_of_two();
+        // Only use as many chunks as there are threads, or the total number of evaluations
+        let num_chunks = rayon::current_num_threads().next_power_of_two().min(n);
         let chunk_size = n / num_chunks;
         let f_batched = (0..num_chunks)
             .into_par_iter()
@@ -485,20 +509,35 @@ where
     <P as Pairing>::ScalarField: field::JoltField,
 {
     type Field = P::ScalarField;
+    type SRS = SRS<P>;
     type Setup = (ZeromorphProverKey<P>, ZeromorphVerifierKey<P>);
+    type VerifierSetup = ZeromorphVerifierKey<P>;
     type Commitment = ZeromorphCommitment<P>;
     type Proof = ZeromorphProof<P>;
     type BatchedProof = ZeromorphProof<P>;
+    type OpeningHint = ();
+    const OPENING_REDUCTION: OpeningReduction<Self, ProofTranscript> = OpeningReduction::sumcheck();
+
+    #[cfg(any(test, feature = "insecure-setup"))]
+    fn insecure_srs(shapes: &[CommitShape]) -> Self::SRS {
+        let max_len = shapes.iter().map(|shape| shape.input_length).max().unwrap();
+        // Only the first two G2 powers are used, see `ZeromorphSRS::from_srs`
+        SRS::setup(&mut ZeromorphSRS::<P>::insecure_rng(), max_len, 2)
+    }
 
-    fn setup(shapes: &[CommitShape]) -> Self::Setup {
+    fn setup_from_srs(srs: &Self::SRS, shapes: &[CommitShape]) -> Result<Self::Setup, SRSError> {
         let max_len = shapes.iter().map(|shape| shape.input_length).max().unwrap();
+        Ok(ZeromorphSRS::from_srs(srs, max_len)?.trim(max_len))
+    }
 
-        ZeromorphSRS(Arc::new(SRS::setup(
-            &mut ChaCha20Rng::from_seed(*b"ZEROMORPH_POLY_COMMITMENT_SCHEME"),
-            max_len,
-            max_len,
-        )))
-        .trim(max_len)
+    fn verifier_setup(setup: &Self::Setup) -> Self::VerifierSetup {
+        setup.1
+    }
+    fn with_msm_tables(setup: Self::Setup, num_windows: usize) -> Self::Setup {
+        let (pk, vk) = setup;
+        let commit_pp = pk.commit_pp.with_msm_table(num_windows);
+        let open_pp = pk.open_pp;
+        (ZeromorphProverKey { commit_pp, open_pp }, vk)
     }
 
     fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
@@ -544,6 +583,23 @@ where
         )
     }
 
+    fn commit_small<S: SmallScalar>(evals: &[S], setup: &Self::Setup) -> Self::Commitment {
+        ZeromorphCommitment(UnivariateKZG::commit_small(&setup.0.commit_pp, evals).unwrap())
+    }
+
+    fn batch_commit_small<S: SmallScalar>(
+        evals: &[&[S]],
+        gens: &Self::Setup,
+        _batch_type: BatchType,
+    ) -> Vec<Self::Commitment> {
+        evals
+            .par_iter()
+            .map(|evals| {
+                ZeromorphCommitment(UnivariateKZG::commit_small(&gens.0.commit_pp, evals).unwrap())
+            })
+            .collect()
+    }
+
     fn prove(
         setup: &Self::Setup,
         poly: &DensePolynomial<Self::Field>,
@@ -572,28 +628,16 @@ where
         )
     }
 
-    fn combine_commitments(
-        commitments: &[&Self::Commitment],
-        coeffs: &[Self::Field],
-    ) -> Self::Commitment {
-        let combined_commitment: P::G1 = commitments
-            .iter()
-            .zip(coeffs.iter())
-            .map(|(commitment, coeff)| commitment.0 * coeff)
-            .sum();
-        ZeromorphCommitment(combined_commitment.into_affine())
-    }
-
     fn verify(
         proof: &Self::Proof,
-        setup: &Self::Setup,
+        setup: &Self::VerifierSetup,
         transcript: &mut ProofTranscript,
         opening_point: &[Self::Field], // point at which the polynomial is evaluated
         opening: &Self::Field,         // evaluation \widetilde{Z}(r)
         commitment: &Self::Commitment,
     ) -> Result<(), ProofVerifyError> {
         Zeromorph::<P, ProofTranscript>::verify(
-            &setup.1,
+            setup,
             commitment,
             opening_point,
             opening,
@@ -604,14 +648,14 @@ where
 
     fn batch_verify(
         batch_proof: &Self::BatchedProof,
-        setup: &Self::Setup,
+        setup: &Self::VerifierSetup,
         opening_point: &[Self::Field],
         openings: &[Self::Field],
         commitments: &[&Self::Commitment],
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
         Zeromorph::<P, ProofTranscript>::batch_verify(
-            &setup.1,
+            setup,
             commitments,
             opening_point,
             openings,
@@ -625,6 +669,24 @@ where
     }
 }
 
+impl<P: Pairing, ProofTranscript: Transcript> AdditivelyHomomorphic<ProofTranscript>
+    for Zeromorph<P, ProofTranscript>
+where
+    <P as Pairing>::ScalarField: field::JoltField,
+{
+    fn combine_commitments(
+        commitments: &[&Self::Commitment],
+        coeffs: &[Self::Field],
+    ) -> Self::Commitment {
+        let combined_commitment: P::G1 = commitments
+            .iter()
+            .zip(coeffs.iter())
+            .map(|(commitment, coeff)| commitment.0 * coeff)
+            .sum();
+        ZeromorphCommitment(combined_commitment.into_affine())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -697,6 +759,24 @@ mod test {
         assert!(res.is_zero());
     }
 
+    #[test]
+    fn commit_small() {
+        type PCS = Zeromorph<Bn254, KeccakTranscript>;
+        let mut rng = test_rng();
+        let setup = PCS::setup(&[CommitShape::new(64, BatchType::Big)]);
+        let chunks: Vec<u8> = (0..64).map(|_| u8::rand(&mut rng)).collect();
+        let chunk_evals: Vec<Fr> = chunks.iter().map(|&chunk| Fr::from(chunk)).collect();
+
+        assert_eq!(
+            PCS::commit_small(&chunks, &setup),
+            PCS::commit_slice(&chunk_evals, &setup)
+        );
+        assert_eq!(
+            PCS::batch_commit_small(&[&chunks], &setup, BatchType::Big),
+            PCS::batch_commit(&[&chunk_evals], &setup, BatchType::Big)
+        );
+    }
+
     /// Test for construction of batched lifted degree quotient:
     ///  ̂q = ∑ₖ₌₀ⁿ⁻¹ yᵏ Xᵐ⁻ᵈᵏ⁻¹ ̂qₖ, 𝑑ₖ = deg(̂q), 𝑚 = 𝑁
     #[test]
@@ -4,6 +4,7 @@ use crate::utils::thread::{drop_in_background_thread, unsafe_allocate_zero_vec};
 use crate::utils::{self, compute_dotproduct, compute_dotproduct_low_optimized};
 
 use crate::field::JoltField;
+use crate::msm::SmallScalar;
 use crate::utils::math::Math;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use core::ops::Index;
@@ -265,6 +266,17 @@ impl<F: JoltField> DensePolynomial<F> {
         )
     }
 
+    /// Builds the polynomial from the compact integer evaluations produced by witness
+    /// generation, e.g. flags or timestamps.
+    #[tracing::instrument(skip_all, name = "DensePolynomial::from")]
+    pub fn from_small_scalars<S: SmallScalar>(Z: &[S]) -> Self {
+        DensePolynomial::new(
+            Z.par_iter()
+                .map(|z| F::from_u64(z.to_u64()).unwrap())
+                .collect::<Vec<F>>(),
+        )
+    }
+
     pub fn random<R: RngCore + CryptoRng>(num_vars: usize, mut rng: &mut R) -> Self {
         Self::new(
             std::iter::from_fn(|| Some(F::random(&mut rng)))
@@ -3,13 +3,15 @@
 //! For additively homomorphic commitment schemes (including Zeromorph, HyperKZG) we
 //! can use a sumcheck to reduce multiple opening proofs (multiple polynomials, not
 //! necessarily of the same size, each opened at a different point) into a single opening.
+//! Other schemes prove each batch of polynomials opened at the same point separately; see
+//! [`OpeningReduction`].
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rayon::prelude::*;
 use std::marker::PhantomData;
 
 use super::{
-    commitment::commitment_scheme::CommitmentScheme,
+    commitment::commitment_scheme::{BatchType, CommitmentScheme, OpeningReduction},
     dense_mlpoly::DensePolynomial,
     eq_poly::EqPolynomial,
     unipoly::{CompressedUniPoly, UniPoly},
@@ -18,7 +20,7 @@ use crate::{
     field::{JoltField, OptimizedMul},
     subprotocols::sumcheck::SumcheckInstanceProof,
     utils::{
-        errors::ProofVerifyError,
+        errors::{Mismatch, ProofVerifyError, SumcheckError},
         thread::unsafe_allocate_zero_vec,
         transcript::{AppendToTranscript, Transcript},
     },
@@ -72,6 +74,35 @@ where
     pub claim: F,
 }
 
+/// Polynomials opened at the same point, proven together with
+/// [`CommitmentScheme::batch_prove`] under [`OpeningReduction::BatchOpenings`].
+struct ProverBatchOpening<F: JoltField> {
+    polynomials: Vec<BatchPolynomial<F>>,
+    opening_point: Vec<F>,
+    claims: Vec<F>,
+}
+
+/// A polynomial of a [`ProverBatchOpening`]. Committed polynomials registered with
+/// [`ProverOpeningAccumulator::register_committed_polynomials`] are referred to by their
+/// index among the registered ones, so that they are not copied; any other polynomial may not
+/// outlive the call to `append`.
+enum BatchPolynomial<F: JoltField> {
+    Committed(usize),
+    Owned(DensePolynomial<F>),
+}
+
+/// The verifier's side of a [`ProverBatchOpening`].
+struct VerifierBatchOpening<F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    commitments: Vec<PCS::Commitment>,
+    opening_point: Vec<F>,
+    claims: Vec<F>,
+}
+
 impl<F: JoltField> ProverOpening<F> {
     fn new(
         polynomial: DensePolynomial<F>,
@@ -107,8 +138,14 @@ where
 
 /// Accumulates openings computed by the prover over the course of Jolt,
 /// so that they can all be reduced to a single opening proof using sumcheck.
-pub struct ProverOpeningAccumulator<F: JoltField, ProofTranscript: Transcript> {
+pub struct ProverOpeningAccumulator<'a, F: JoltField, ProofTranscript: Transcript> {
     openings: Vec<ProverOpening<F>>,
+    /// `Some` if the commitment scheme uses [`OpeningReduction::BatchOpenings`], in which
+    /// case openings are accumulated here instead of in `openings`.
+    batch_openings: Option<Vec<ProverBatchOpening<F>>>,
+    /// The polynomials registered with
+    /// [`ProverOpeningAccumulator::register_committed_polynomials`].
+    committed_polynomials: Vec<&'a DensePolynomial<F>>,
     _marker: PhantomData<ProofTranscript>,
 }
 
@@ -121,6 +158,8 @@ where
     ProofTranscript: Transcript,
 {
     openings: Vec<VerifierOpening<F, PCS, ProofTranscript>>,
+    /// Openings accumulated under [`OpeningReduction::BatchOpenings`].
+    batch_openings: Vec<VerifierBatchOpening<F, PCS, ProofTranscript>>,
     #[cfg(test)]
     /// In testing, the Jolt verifier may be provided the prover's openings so that we
     /// can detect any places where the openings don't match up.
@@ -135,29 +174,87 @@ pub struct ReducedOpeningProof<
     PCS: CommitmentScheme<ProofTranscript, Field = F>,
     ProofTranscript: Transcript,
 > {
-    sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
-    sumcheck_claims: Vec<F>,
-    joint_opening_proof: PCS::Proof,
+    pub(crate) sumcheck_proof: SumcheckInstanceProof<F, ProofTranscript>,
+    pub(crate) sumcheck_claims: Vec<F>,
+    /// Set under [`OpeningReduction::Sumcheck`].
+    joint_opening_proof: Option<PCS::Proof>,
+    /// One proof per opening point under [`OpeningReduction::BatchOpenings`].
+    batch_opening_proofs: Vec<PCS::BatchedProof>,
 }
 
-impl<F: JoltField, ProofTranscript: Transcript> Default
-    for ProverOpeningAccumulator<F, ProofTranscript>
+impl<F, PCS, ProofTranscript> ReducedOpeningProof<F, PCS, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
 {
-    fn default() -> Self {
-        Self::new()
+    fn num_opening_proofs(&self) -> usize {
+        self.joint_opening_proof.is_some() as usize + self.batch_opening_proofs.len()
     }
 }
 
-impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, ProofTranscript> {
-    pub fn new() -> Self {
+impl<'a, F: JoltField, ProofTranscript: Transcript>
+    ProverOpeningAccumulator<'a, F, ProofTranscript>
+{
+    /// Creates an accumulator for openings of polynomials committed with `PCS`, which
+    /// determines how they are batched (see [`OpeningReduction`]).
+    pub fn new<PCS: CommitmentScheme<ProofTranscript, Field = F>>() -> Self {
+        let batch_openings = match PCS::OPENING_REDUCTION {
+            OpeningReduction::BatchOpenings => Some(vec![]),
+            OpeningReduction::Sumcheck { .. } => None,
+        };
         Self {
             openings: vec![],
+            batch_openings,
+            committed_polynomials: vec![],
+            _marker: PhantomData,
+        }
+    }
+
+    /// Registers the committed polynomials whose openings are accumulated. Under
+    /// [`OpeningReduction::BatchOpenings`], openings of these polynomials are proven from
+    /// the polynomials themselves, which stay borrowed until the accumulator is dropped,
+    /// instead of from a copy made by `append`.
+    pub fn register_committed_polynomials(&mut self, polynomials: &[&'a DensePolynomial<F>]) {
+        self.committed_polynomials = polynomials.to_vec();
+    }
+
+    /// Copies the registered polynomials referred to by batch openings, so that the
+    /// accumulator no longer borrows them. Used to keep the prover's openings for debugging
+    /// after the polynomials are dropped.
+    pub fn into_owned(self) -> ProverOpeningAccumulator<'static, F, ProofTranscript> {
+        let committed_polynomials = self.committed_polynomials;
+        ProverOpeningAccumulator {
+            openings: self.openings,
+            batch_openings: self.batch_openings.map(|batch_openings| {
+                batch_openings
+                    .into_iter()
+                    .map(|opening| ProverBatchOpening {
+                        polynomials: opening
+                            .polynomials
+                            .into_iter()
+                            .map(|poly| match poly {
+                                BatchPolynomial::Committed(index) => BatchPolynomial::Owned(
+                                    DensePolynomial::clone(committed_polynomials[index]),
+                                ),
+                                BatchPolynomial::Owned(poly) => BatchPolynomial::Owned(poly),
+                            })
+                            .collect(),
+                        opening_point: opening.opening_point,
+                        claims: opening.claims,
+                    })
+                    .collect()
+            }),
+            committed_polynomials: vec![],
             _marker: PhantomData,
         }
     }
 
     pub fn len(&self) -> usize {
-        self.openings.len()
+        match &self.batch_openings {
+            Some(batch_openings) => batch_openings.len(),
+            None => self.openings.len(),
+        }
     }
 
     /// Adds openings to the accumulator. The given `polynomials` are opened at
@@ -165,7 +262,8 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
     /// the multilinear extension EQ(x, opening_point), which is typically an
     /// intermediate value in computing `claims`. Multiple polynomials opened at
     /// a single point can be batched into a single polynomial opened at the same
-    /// point. This function performs this batching before appending to `self.openings`.
+    /// point. This function performs this batching before appending to `self.openings`,
+    /// unless the commitment scheme uses [`OpeningReduction::BatchOpenings`].
     #[tracing::instrument(skip_all, name = "ProverOpeningAccumulator::append")]
     pub fn append(
         &mut self,
@@ -193,6 +291,29 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
             }
         }
 
+        if let Some(batch_openings) = &mut self.batch_openings {
+            let committed_polynomials = &self.committed_polynomials;
+            batch_openings.push(ProverBatchOpening {
+                polynomials: polynomials
+                    .iter()
+                    .map(|poly| {
+                        // A registered polynomial is borrowed for `'a`, so it cannot have
+                        // moved and a polynomial at the same address is that polynomial.
+                        match committed_polynomials
+                            .iter()
+                            .position(|committed| std::ptr::eq(*committed, *poly))
+                        {
+                            Some(index) => BatchPolynomial::Committed(index),
+                            None => BatchPolynomial::Owned(DensePolynomial::clone(poly)),
+                        }
+                    })
+                    .collect(),
+                opening_point,
+                claims: claims.iter().map(|claim| **claim).collect(),
+            });
+            return;
+        }
+
         // Generate batching challenge \rho and powers 1,...,\rho^{m-1}
         let rho: F = transcript.challenge_scalar();
         let mut rho_powers = vec![F::one()];
@@ -252,13 +373,58 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
     }
 
     /// Reduces the multiple openings accumulated into a single opening proof,
-    /// using a single sumcheck.
+    /// using a single sumcheck. Under [`OpeningReduction::BatchOpenings`], instead
+    /// proves each batch of polynomials opened at the same point separately, reusing
+    /// `opening_hints`: the [`CommitmentScheme::OpeningHint`]s returned when committing to the
+    /// polynomials registered with
+    /// [`ProverOpeningAccumulator::register_committed_polynomials`], in the same order, or
+    /// none.
     #[tracing::instrument(skip_all, name = "ProverOpeningAccumulator::reduce_and_prove")]
     pub fn reduce_and_prove<PCS: CommitmentScheme<ProofTranscript, Field = F>>(
         &mut self,
         pcs_setup: &PCS::Setup,
+        opening_hints: &[PCS::OpeningHint],
         transcript: &mut ProofTranscript,
     ) -> ReducedOpeningProof<F, PCS, ProofTranscript> {
+        if let Some(batch_openings) = &self.batch_openings {
+            assert!(
+                opening_hints.is_empty() || opening_hints.len() == self.committed_polynomials.len()
+            );
+            let no_hint = PCS::OpeningHint::default();
+            let batch_opening_proofs = batch_openings
+                .iter()
+                .map(|opening| {
+                    let (polynomials, hints): (Vec<&DensePolynomial<F>>, Vec<&PCS::OpeningHint>) =
+                        opening
+                            .polynomials
+                            .iter()
+                            .map(|poly| match poly {
+                                BatchPolynomial::Committed(index) => (
+                                    self.committed_polynomials[*index],
+                                    opening_hints.get(*index).unwrap_or(&no_hint),
+                                ),
+                                BatchPolynomial::Owned(poly) => (poly, &no_hint),
+                            })
+                            .unzip();
+                    PCS::batch_prove_with_hints(
+                        pcs_setup,
+                        &polynomials,
+                        &hints,
+                        &opening.opening_point,
+                        &opening.claims,
+                        BatchType::Big,
+                        transcript,
+                    )
+                })
+                .collect();
+            return ReducedOpeningProof {
+                sumcheck_proof: SumcheckInstanceProof::new(vec![]),
+                sumcheck_claims: vec![],
+                joint_opening_proof: None,
+                batch_opening_proofs,
+            };
+        }
+
         // Generate coefficients for random linear combination
         let rho: F = transcript.challenge_scalar();
         let mut rho_powers = vec![F::one()];
@@ -320,7 +486,8 @@ impl<F: JoltField, ProofTranscript: Transcript> ProverOpeningAccumulator<F, Proo
         ReducedOpeningProof {
             sumcheck_proof,
             sumcheck_claims,
-            joint_opening_proof,
+            joint_opening_proof: Some(joint_opening_proof),
+            batch_opening_proofs: vec![],
         }
     }
 
@@ -496,6 +663,7 @@ where
     pub fn new() -> Self {
         Self {
             openings: vec![],
+            batch_openings: vec![],
             #[cfg(test)]
             prover_openings: None,
             #[cfg(test)]
@@ -516,7 +684,7 @@ where
     }
 
     pub fn len(&self) -> usize {
-        self.openings.len()
+        self.openings.len() + self.batch_openings.len()
     }
 
     /// Adds openings to the accumulator. The polynomials underlying the given
@@ -525,7 +693,8 @@ where
     /// Multiple polynomials opened at a single point can be batched into a single
     /// polynomial opened at the same point. This function performs the verifier side
     /// of this batching by homomorphically combining the commitments before appending
-    /// to `self.openings`.
+    /// to `self.openings`, unless the commitment scheme uses
+    /// [`OpeningReduction::BatchOpenings`].
     pub fn append(
         &mut self,
         commitments: &[&PCS::Commitment],
@@ -534,6 +703,23 @@ where
         transcript: &mut ProofTranscript,
     ) {
         assert_eq!(commitments.len(), claims.len());
+        let combine_commitments = match PCS::OPENING_REDUCTION {
+            OpeningReduction::Sumcheck {
+                combine_commitments,
+            } => combine_commitments,
+            OpeningReduction::BatchOpenings => {
+                self.batch_openings.push(VerifierBatchOpening {
+                    commitments: commitments
+                        .iter()
+                        .map(|commitment| PCS::Commitment::clone(commitment))
+                        .collect(),
+                    opening_point,
+                    claims: claims.iter().map(|claim| **claim).collect(),
+                });
+                return;
+            }
+        };
+
         let rho: F = transcript.challenge_scalar();
         let mut rho_powers = vec![F::one()];
         for i in 1..commitments.len() {
@@ -546,7 +732,7 @@ where
             .map(|(scalar, eval)| *scalar * *eval)
             .sum();
 
-        let joint_commitment = PCS::combine_commitments(commitments, &rho_powers);
+        let joint_commitment = combine_commitments(commitments, &rho_powers);
 
         #[cfg(test)]
         'test: {
@@ -596,13 +782,32 @@ where
     }
 
     /// Verifies that the given `reduced_opening_proof` (consisting of a sumcheck proof
-    /// and a single opening proof) indeed proves the openings accumulated.
+    /// and a single opening proof, or of a proof per opening point under
+    /// [`OpeningReduction::BatchOpenings`]) indeed proves the openings accumulated.
     pub fn reduce_and_verify(
         &self,
-        pcs_setup: &PCS::Setup,
+        pcs_setup: &PCS::VerifierSetup,
         reduced_opening_proof: &ReducedOpeningProof<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
+        let combine_commitments = match PCS::OPENING_REDUCTION {
+            OpeningReduction::Sumcheck {
+                combine_commitments,
+            } => combine_commitments,
+            OpeningReduction::BatchOpenings => {
+                return self.verify_batch_openings(pcs_setup, reduced_opening_proof, transcript)
+            }
+        };
+        let (Some(joint_opening_proof), []) = (
+            &reduced_opening_proof.joint_opening_proof,
+            &reduced_opening_proof.batch_opening_proofs[..],
+        ) else {
+            return Err(ProofVerifyError::NumOpeningProofs {
+                expected: 1,
+                actual: reduced_opening_proof.num_opening_proofs(),
+            });
+        };
+
         let num_sumcheck_rounds = self
             .openings
             .iter()
@@ -618,12 +823,14 @@ where
         }
 
         // Verify the sumcheck
-        let (sumcheck_claim, r_sumcheck) = self.verify_batch_opening_reduction(
-            &rho_powers,
-            num_sumcheck_rounds,
-            &reduced_opening_proof.sumcheck_proof,
-            transcript,
-        )?;
+        let (sumcheck_claim, r_sumcheck) = self
+            .verify_batch_opening_reduction(
+                &rho_powers,
+                num_sumcheck_rounds,
+                &reduced_opening_proof.sumcheck_proof,
+                transcript,
+            )
+            .map_err(ProofVerifyError::BatchedOpeningSumcheck)?;
 
         // Compute random linear combination of the claims, accounting for the fact that the
         // polynomials may be of different sizes
@@ -640,9 +847,9 @@ where
             })
             .sum();
 
-        if sumcheck_claim != expected_sumcheck_claim {
-            return Err(ProofVerifyError::InternalError);
-        }
+        Mismatch::check(sumcheck_claim, expected_sumcheck_claim).map_err(|mismatch| {
+            ProofVerifyError::BatchedOpeningSumcheck(SumcheckError::FinalClaim(mismatch))
+        })?;
 
         transcript.append_scalars(&reduced_opening_proof.sumcheck_claims);
 
@@ -653,7 +860,7 @@ where
         }
 
         // Compute joint commitment = ∑ᵢ γⁱ⋅ commitmentᵢ
-        let joint_commitment = PCS::combine_commitments(
+        let joint_commitment = combine_commitments(
             &self
                 .openings
                 .iter()
@@ -677,13 +884,64 @@ where
 
         // Verify the reduced opening proof
         PCS::verify(
-            &reduced_opening_proof.joint_opening_proof,
+            joint_opening_proof,
             pcs_setup,
             transcript,
             &r_sumcheck,
             &joint_claim,
             &joint_commitment,
         )
+        .map_err(|e| ProofVerifyError::BatchedOpening(Box::new(e)))
+    }
+
+    /// Verifies a proof of each batch of polynomials opened at the same point, made under
+    /// [`OpeningReduction::BatchOpenings`].
+    fn verify_batch_openings(
+        &self,
+        pcs_setup: &PCS::VerifierSetup,
+        reduced_opening_proof: &ReducedOpeningProof<F, PCS, ProofTranscript>,
+        transcript: &mut ProofTranscript,
+    ) -> Result<(), ProofVerifyError> {
+        let num_sumcheck_rounds = reduced_opening_proof.sumcheck_proof.compressed_polys.len();
+        if num_sumcheck_rounds != 0 {
+            return Err(ProofVerifyError::BatchedOpeningSumcheck(
+                SumcheckError::NumRounds {
+                    expected: 0,
+                    actual: num_sumcheck_rounds,
+                },
+            ));
+        }
+        if !reduced_opening_proof.sumcheck_claims.is_empty() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                0,
+                reduced_opening_proof.sumcheck_claims.len(),
+            ));
+        }
+        if reduced_opening_proof.joint_opening_proof.is_some()
+            || reduced_opening_proof.batch_opening_proofs.len() != self.batch_openings.len()
+        {
+            return Err(ProofVerifyError::NumOpeningProofs {
+                expected: self.batch_openings.len(),
+                actual: reduced_opening_proof.num_opening_proofs(),
+            });
+        }
+
+        for (opening, proof) in self
+            .batch_openings
+            .iter()
+            .zip(reduced_opening_proof.batch_opening_proofs.iter())
+        {
+            PCS::batch_verify(
+                proof,
+                pcs_setup,
+                &opening.opening_point,
+                &opening.claims,
+                &opening.commitments.iter().collect::<Vec<_>>(),
+                transcript,
+            )
+            .map_err(|e| ProofVerifyError::BatchedOpening(Box::new(e)))?;
+        }
+        Ok(())
     }
 
     /// Verifies the sumcheck proven in `ProverOpeningAccumulator::prove_batch_opening_reduction`.
@@ -693,7 +951,7 @@ where
         num_sumcheck_rounds: usize,
         sumcheck_proof: &SumcheckInstanceProof<F, ProofTranscript>,
         transcript: &mut ProofTranscript,
-    ) -> Result<(F, Vec<F>), ProofVerifyError> {
+    ) -> Result<(F, Vec<F>), SumcheckError> {
         let combined_claim: F = coeffs
             .par_iter()
             .zip(self.openings.par_iter())
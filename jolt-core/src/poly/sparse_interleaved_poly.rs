@@ -471,7 +471,7 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                 .map(|eq_chunk| {
                     let eval_point_0 = eq_chunk[0];
                     let m_eq = eq_chunk[1] - eq_chunk[0];
-                    let eval_point_2 = eq_chunk[1] + m_eq;
+                    let eval_point_2 = F::step_linear_eval(eq_chunk[1], m_eq, 1);
                     let eval_point_3 = eval_point_2 + m_eq;
                     (eval_point_0, eval_point_2, eval_point_3)
                 })
@@ -509,10 +509,10 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                             let m_left = left.1 - left.0;
                             let m_right = right.1 - right.0;
 
-                            let left_eval_2 = left.1 + m_left;
+                            let left_eval_2 = F::step_linear_eval(left.1, m_left, 1);
                             let left_eval_3 = left_eval_2 + m_left;
 
-                            let right_eval_2 = right.1 + m_right;
+                            let right_eval_2 = F::step_linear_eval(right.1, m_right, 1);
                             let right_eval_3 = right_eval_2 + m_right;
 
                             let eq_evals = eq_evals[block_index];
@@ -546,7 +546,7 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                 .map(|E1_chunk| {
                     let eval_point_0 = E1_chunk[0];
                     let m_eq = E1_chunk[1] - E1_chunk[0];
-                    let eval_point_2 = E1_chunk[1] + m_eq;
+                    let eval_point_2 = F::step_linear_eval(E1_chunk[1], m_eq, 1);
                     let eval_point_3 = eval_point_2 + m_eq;
                     (eval_point_0, eval_point_2, eval_point_3)
                 })
@@ -594,10 +594,10 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                                 let m_left = left.1 - left.0;
                                 let m_right = right.1 - right.0;
 
-                                let left_eval_2 = left.1 + m_left;
+                                let left_eval_2 = F::step_linear_eval(left.1, m_left, 1);
                                 let left_eval_3 = left_eval_2 + m_left;
 
-                                let right_eval_2 = right.1 + m_right;
+                                let right_eval_2 = F::step_linear_eval(right.1, m_right, 1);
                                 let right_eval_3 = right_eval_2 + m_right;
 
                                 let x1 = block_index & x1_bitmask;
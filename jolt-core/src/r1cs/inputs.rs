@@ -7,16 +7,16 @@
 use crate::impl_r1cs_input_lc_conversions;
 use crate::jolt::instruction::JoltInstructionSet;
 use crate::jolt::vm::rv32i_vm::RV32I;
-use crate::jolt::vm::{JoltCommitments, JoltStuff, JoltTraceStep};
+use crate::jolt::vm::{CompactEvals, JoltCommitments, JoltStuff, JoltTraceStep};
 use crate::lasso::memory_checking::{Initializable, StructuredPolynomialData};
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::opening_proof::VerifierOpeningAccumulator;
-use crate::utils::thread::unsafe_allocate_zero_vec;
+use crate::utils::errors::ProofVerifyError;
 use crate::utils::transcript::Transcript;
 
 use super::key::UniformSpartanKey;
-use super::spartan::{SpartanError, UniformSpartanProof};
+use super::spartan::UniformSpartanProof;
 
 use crate::field::JoltField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -166,48 +166,60 @@ impl<F: JoltField> R1CSPolynomials<F> {
         I: ConstraintInput,
     >(
         trace: &[JoltTraceStep<InstructionSet>],
-    ) -> Self {
+    ) -> (Self, R1CSStuff<CompactEvals>) {
         let log_M = log2(M) as usize;
+        assert!(log_M <= 16, "operand chunks must fit in a u16");
 
-        let mut chunks_x = vec![unsafe_allocate_zero_vec(trace.len()); C];
-        let mut chunks_y = vec![unsafe_allocate_zero_vec(trace.len()); C];
-        let mut circuit_flags = vec![unsafe_allocate_zero_vec(trace.len()); NUM_CIRCUIT_FLAGS];
+        let mut chunks_x = vec![vec![0u16; trace.len()]; C];
+        let mut chunks_y = vec![vec![0u16; trace.len()]; C];
+        let mut circuit_flags = vec![vec![false; trace.len()]; NUM_CIRCUIT_FLAGS];
 
         // TODO(moodlezoup): Can be parallelized
         for (step_index, step) in trace.iter().enumerate() {
             if let Some(instr) = &step.instruction_lookup {
                 let (x, y) = instr.operand_chunks(C, log_M);
                 for i in 0..C {
-                    chunks_x[i][step_index] = F::from_u64(x[i]).unwrap();
-                    chunks_y[i][step_index] = F::from_u64(y[i]).unwrap();
+                    chunks_x[i][step_index] = x[i] as u16;
+                    chunks_y[i][step_index] = y[i] as u16;
                 }
             }
 
             for j in 0..NUM_CIRCUIT_FLAGS {
-                if step.circuit_flags[j] {
-                    circuit_flags[j][step_index] = F::one();
-                }
+                circuit_flags[j][step_index] = step.circuit_flags[j];
             }
         }
 
-        Self {
+        let polynomials = Self {
             chunks_x: chunks_x
-                .into_iter()
-                .map(|vals| DensePolynomial::new(vals))
+                .iter()
+                .map(|vals| DensePolynomial::from_small_scalars(vals))
                 .collect(),
             chunks_y: chunks_y
-                .into_iter()
-                .map(|vals| DensePolynomial::new(vals))
+                .iter()
+                .map(|vals| DensePolynomial::from_small_scalars(vals))
                 .collect(),
             circuit_flags: circuit_flags
-                .into_iter()
-                .map(|vals| DensePolynomial::new(vals))
+                .iter()
+                .map(|vals| DensePolynomial::from_small_scalars(vals))
                 .collect::<Vec<_>>()
                 .try_into()
                 .unwrap(),
             // Actual aux variable polynomials will be computed afterwards
             aux: AuxVariableStuff::initialize(&C),
-        }
+        };
+        let compact = R1CSStuff {
+            chunks_x: chunks_x.into_iter().map(CompactEvals::U16).collect(),
+            chunks_y: chunks_y.into_iter().map(CompactEvals::U16).collect(),
+            circuit_flags: circuit_flags
+                .into_iter()
+                .map(CompactEvals::Bits)
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            aux: AuxVariableStuff::initialize(&C),
+        };
+
+        (polynomials, compact)
     }
 }
 
@@ -228,7 +240,7 @@ impl<const C: usize, I: ConstraintInput, F: JoltField, ProofTranscript: Transcri
         commitments: &JoltCommitments<PCS, ProofTranscript>,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
-    ) -> Result<(), SpartanError>
+    ) -> Result<(), ProofVerifyError>
     where
         PCS: CommitmentScheme<ProofTranscript, Field = F>,
         ProofTranscript: Transcript,
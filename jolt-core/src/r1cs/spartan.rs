@@ -10,6 +10,7 @@ use crate::poly::opening_proof::ProverOpeningAccumulator;
 use crate::poly::opening_proof::VerifierOpeningAccumulator;
 use crate::poly::split_eq_poly::SplitEqPolynomial;
 use crate::r1cs::key::UniformSpartanKey;
+use crate::utils::errors::{Mismatch, ProofVerifyError, SumcheckError};
 use crate::utils::math::Math;
 use crate::utils::thread::drop_in_background_thread;
 
@@ -216,7 +217,7 @@ where
         commitments: &JoltCommitments<PCS, ProofTranscript>,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>,
         transcript: &mut ProofTranscript,
-    ) -> Result<(), SpartanError>
+    ) -> Result<(), ProofVerifyError>
     where
         PCS: CommitmentScheme<ProofTranscript, Field = F>,
         ProofTranscript: Transcript,
@@ -232,7 +233,7 @@ where
         let (claim_outer_final, r_x) = self
             .outer_sumcheck_proof
             .verify(F::zero(), num_rounds_x, 3, transcript)
-            .map_err(|_| SpartanError::InvalidOuterSumcheckProof)?;
+            .map_err(ProofVerifyError::SpartanOuterSumcheck)?;
 
         // Outer sumcheck is bound from the top, reverse the fiat shamir randomness
         let r_x: Vec<F> = r_x.into_iter().rev().collect();
@@ -241,9 +242,9 @@ where
         let (claim_Az, claim_Bz, claim_Cz) = self.outer_sumcheck_claims;
         let taus_bound_rx = EqPolynomial::new(tau).evaluate(&r_x);
         let claim_outer_final_expected = taus_bound_rx * (claim_Az * claim_Bz - claim_Cz);
-        if claim_outer_final != claim_outer_final_expected {
-            return Err(SpartanError::InvalidOuterSumcheckClaim);
-        }
+        Mismatch::check(claim_outer_final, claim_outer_final_expected).map_err(|mismatch| {
+            ProofVerifyError::SpartanOuterSumcheck(SumcheckError::FinalClaim(mismatch))
+        })?;
 
         transcript.append_scalars(
             [
@@ -263,7 +264,7 @@ where
         let (claim_inner_final, inner_sumcheck_r) = self
             .inner_sumcheck_proof
             .verify(claim_inner_joint, num_rounds_y, 2, transcript)
-            .map_err(|_| SpartanError::InvalidInnerSumcheckProof)?;
+            .map_err(ProofVerifyError::SpartanInnerSumcheck)?;
 
         // n_prefix = n_segments + 1
         let n_prefix = key.uniform_r1cs.num_vars.next_power_of_two().log_2() + 1;
@@ -279,9 +280,9 @@ where
             + r_inner_sumcheck_RLC * r_inner_sumcheck_RLC * eval_c;
         let right_expected = eval_Z;
         let claim_inner_final_expected = left_expected * right_expected;
-        if claim_inner_final != claim_inner_final_expected {
-            return Err(SpartanError::InvalidInnerSumcheckClaim);
-        }
+        Mismatch::check(claim_inner_final, claim_inner_final_expected).map_err(|mismatch| {
+            ProofVerifyError::SpartanInnerSumcheck(SumcheckError::FinalClaim(mismatch))
+        })?;
 
         let flattened_commitments: Vec<_> = I::flatten::<C>()
             .iter()
@@ -1,4 +1,6 @@
-use super::grand_product_quarks::QuarkGrandProductProof;
+use super::grand_product_quarks::{
+    QuarkGrandProduct, QuarkGrandProductBase, QuarkGrandProductConfig, QuarkGrandProductProof,
+};
 use super::sumcheck::{BatchedCubicSumcheck, SumcheckInstanceProof};
 use crate::field::JoltField;
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
@@ -6,11 +8,12 @@ use crate::poly::dense_interleaved_poly::DenseInterleavedPolynomial;
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
 use crate::poly::split_eq_poly::SplitEqPolynomial;
+use crate::utils::errors::{GrandProductError, Mismatch, SumcheckError};
 use crate::utils::math::Math;
 use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::Transcript;
 use ark_serialize::*;
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use rayon::prelude::*;
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
@@ -27,10 +30,9 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedGrandProductLayerProof<F,
         num_rounds: usize,
         degree_bound: usize,
         transcript: &mut ProofTranscript,
-    ) -> (F, Vec<F>) {
+    ) -> Result<(F, Vec<F>), SumcheckError> {
         self.proof
             .verify(claim, num_rounds, degree_bound, transcript)
-            .unwrap()
     }
 }
 
@@ -114,10 +116,15 @@ where
         grand_product_claim: &mut F,
         r_grand_product: &mut Vec<F>,
         transcript: &mut ProofTranscript,
-    ) {
+    ) -> Result<(), GrandProductError> {
         let layer_proof = &layer_proofs[layer_index];
         let expected_sumcheck_claim: F = layer_proof.left_claim * layer_proof.right_claim * eq_eval;
-        assert_eq!(expected_sumcheck_claim, sumcheck_claim);
+        Mismatch::check(sumcheck_claim, expected_sumcheck_claim).map_err(|mismatch| {
+            GrandProductError::LayerClaims {
+                layer: layer_index,
+                mismatch,
+            }
+        })?;
 
         // produce a random challenge to condense two claims into a single claim
         let r_layer = transcript.challenge_scalar();
@@ -125,6 +132,7 @@ where
             layer_proof.left_claim + r_layer * (layer_proof.right_claim - layer_proof.left_claim);
 
         r_grand_product.push(r_layer);
+        Ok(())
     }
 
     /// Function used for layer sumchecks in the generic batch verifier as well as the quark layered sumcheck hybrid
@@ -133,7 +141,7 @@ where
         mut claim: F,
         transcript: &mut ProofTranscript,
         r_start: Vec<F>,
-    ) -> (F, Vec<F>) {
+    ) -> Result<(F, Vec<F>), GrandProductError> {
         // `r_start` is the random point at which the MLE of the first layer of the grand product is evaluated.
         // In the case of the Quarks hybrid grand product, this is obtained from the Quarks grand product sumcheck.
         // In the case of Thaler'13 GKR-based grand products, this is from Fiat-Shamir.
@@ -141,8 +149,12 @@ where
         let fixed_at_start = r_start.len();
 
         for (layer_index, layer_proof) in proof_layers.iter().enumerate() {
-            let (sumcheck_claim, r_sumcheck) =
-                layer_proof.verify(claim, layer_index + fixed_at_start, 3, transcript);
+            let (sumcheck_claim, r_sumcheck) = layer_proof
+                .verify(claim, layer_index + fixed_at_start, 3, transcript)
+                .map_err(|error| GrandProductError::LayerSumcheck {
+                    layer: layer_index,
+                    error,
+                })?;
 
             transcript.append_scalar(&layer_proof.left_claim);
             transcript.append_scalar(&layer_proof.right_claim);
@@ -163,10 +175,10 @@ where
                 &mut claim,
                 &mut r_grand_product,
                 transcript,
-            );
+            )?;
         }
 
-        (claim, r_grand_product)
+        Ok((claim, r_grand_product))
     }
 
     /// Verifies the given grand product proof.
@@ -175,8 +187,8 @@ where
         claimed_outputs: &[F],
         _opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
         transcript: &mut ProofTranscript,
-        _setup: Option<&PCS::Setup>,
-    ) -> (F, Vec<F>) {
+        _setup: Option<&PCS::VerifierSetup>,
+    ) -> Result<(F, Vec<F>), GrandProductError> {
         // Evaluate the MLE of the output layer at a random point to reduce the outputs to
         // a single claim.
         transcript.append_scalars(claimed_outputs);
@@ -304,16 +316,188 @@ where
     }
 }
 
+/// Selects how the memory-checking grand products are proven.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum GrandProductBackend {
+    /// Thaler'13 GKR-based grand products, i.e. `BatchedDenseGrandProduct`.
+    #[default]
+    Dense,
+    /// The Quarks/GKR hybrid, i.e. `QuarkGrandProduct` with the given config.
+    QuarkHybrid(QuarkGrandProductConfig),
+}
+
+/// A batched grand product whose backend is chosen at construction time by a
+/// [`GrandProductBackend`].
+///
+/// Both backends are verified by the Quarks verifier, which reduces to plain GKR
+/// verification when the proof contains no Quark proof, so the verifier does not
+/// need to know which backend the prover used.
+pub enum ConfigurableGrandProduct<F: JoltField, ProofTranscript: Transcript> {
+    Dense(BatchedDenseGrandProduct<F>),
+    QuarkHybrid(QuarkGrandProduct<F, ProofTranscript>),
+}
+
+impl<F, PCS, ProofTranscript> BatchedGrandProduct<F, PCS, ProofTranscript>
+    for ConfigurableGrandProduct<F, ProofTranscript>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<ProofTranscript, Field = F>,
+    ProofTranscript: Transcript,
+{
+    // (leaf values, batch size)
+    type Leaves = (Vec<F>, usize);
+    type Config = GrandProductBackend;
+
+    #[tracing::instrument(skip_all, name = "ConfigurableGrandProduct::construct_with_config")]
+    fn construct_with_config(leaves: Self::Leaves, config: Self::Config) -> Self {
+        match config {
+            GrandProductBackend::Dense => {
+                Self::Dense(<BatchedDenseGrandProduct<F> as BatchedGrandProduct<
+                    F,
+                    PCS,
+                    ProofTranscript,
+                >>::construct(leaves))
+            }
+            GrandProductBackend::QuarkHybrid(config) => {
+                Self::QuarkHybrid(
+                    <QuarkGrandProduct<F, ProofTranscript> as BatchedGrandProduct<
+                        F,
+                        PCS,
+                        ProofTranscript,
+                    >>::construct_with_config(leaves, config),
+                )
+            }
+        }
+    }
+
+    fn num_layers(&self) -> usize {
+        match self {
+            Self::Dense(grand_product) => <BatchedDenseGrandProduct<F> as BatchedGrandProduct<
+                F,
+                PCS,
+                ProofTranscript,
+            >>::num_layers(grand_product),
+            Self::QuarkHybrid(grand_product) => {
+                <QuarkGrandProduct<F, ProofTranscript> as BatchedGrandProduct<
+                    F,
+                    PCS,
+                    ProofTranscript,
+                >>::num_layers(grand_product)
+            }
+        }
+    }
+
+    fn claimed_outputs(&self) -> Vec<F> {
+        match self {
+            Self::Dense(grand_product) => <BatchedDenseGrandProduct<F> as BatchedGrandProduct<
+                F,
+                PCS,
+                ProofTranscript,
+            >>::claimed_outputs(grand_product),
+            Self::QuarkHybrid(grand_product) => {
+                <QuarkGrandProduct<F, ProofTranscript> as BatchedGrandProduct<
+                    F,
+                    PCS,
+                    ProofTranscript,
+                >>::claimed_outputs(grand_product)
+            }
+        }
+    }
+
+    fn layers(
+        &'_ mut self,
+    ) -> impl Iterator<Item = &'_ mut dyn BatchedGrandProductLayer<F, ProofTranscript>> {
+        match self {
+            Self::Dense(grand_product) => {
+                Either::Left(<BatchedDenseGrandProduct<F> as BatchedGrandProduct<
+                    F,
+                    PCS,
+                    ProofTranscript,
+                >>::layers(grand_product))
+            }
+            Self::QuarkHybrid(grand_product) => Either::Right(<QuarkGrandProduct<
+                F,
+                ProofTranscript,
+            > as BatchedGrandProduct<
+                F,
+                PCS,
+                ProofTranscript,
+            >>::layers(grand_product)),
+        }
+    }
+
+    fn quark_poly(&self) -> Option<&[F]> {
+        match self {
+            Self::Dense(_) => None,
+            Self::QuarkHybrid(grand_product) => {
+                <QuarkGrandProduct<F, ProofTranscript> as BatchedGrandProduct<
+                    F,
+                    PCS,
+                    ProofTranscript,
+                >>::quark_poly(grand_product)
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, name = "ConfigurableGrandProduct::prove_grand_product")]
+    fn prove_grand_product(
+        &mut self,
+        opening_accumulator: Option<&mut ProverOpeningAccumulator<F, ProofTranscript>>,
+        transcript: &mut ProofTranscript,
+        setup: Option<&PCS::Setup>,
+    ) -> (BatchedGrandProductProof<PCS, ProofTranscript>, Vec<F>) {
+        match self {
+            Self::Dense(grand_product) => <BatchedDenseGrandProduct<F> as BatchedGrandProduct<
+                F,
+                PCS,
+                ProofTranscript,
+            >>::prove_grand_product(
+                grand_product, opening_accumulator, transcript, setup
+            ),
+            Self::QuarkHybrid(grand_product) => {
+                <QuarkGrandProduct<F, ProofTranscript> as BatchedGrandProduct<
+                    F,
+                    PCS,
+                    ProofTranscript,
+                >>::prove_grand_product(
+                    grand_product, opening_accumulator, transcript, setup
+                )
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, name = "ConfigurableGrandProduct::verify_grand_product")]
+    fn verify_grand_product(
+        proof: &BatchedGrandProductProof<PCS, ProofTranscript>,
+        claimed_outputs: &[F],
+        opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
+        transcript: &mut ProofTranscript,
+        _setup: Option<&PCS::VerifierSetup>,
+    ) -> Result<(F, Vec<F>), GrandProductError> {
+        QuarkGrandProductBase::verify_quark_grand_product::<Self, PCS>(
+            proof,
+            claimed_outputs,
+            opening_accumulator,
+            transcript,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utils::transcript::{KeccakTranscript, Transcript};
     use crate::{
-        poly::{commitment::zeromorph::Zeromorph, dense_interleaved_poly::bind_left_and_right},
+        field::binius::BiniusField,
+        poly::{
+            commitment::{binius::Binius128Scheme, zeromorph::Zeromorph},
+            dense_interleaved_poly::bind_left_and_right,
+        },
         subprotocols::sumcheck::Bindable,
     };
     use ark_bn254::{Bn254, Fr};
     use ark_std::test_rng;
+    use binius_field::BinaryField128bPolyval;
 
     #[test]
     fn dense_construct() {
@@ -396,8 +580,11 @@ mod tests {
         }
     }
 
-    #[test]
-    fn dense_prove_verify() {
+    fn run_dense_prove_verify_test<F, PCS>()
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<KeccakTranscript, Field = F>,
+    {
         let mut rng = test_rng();
         const LAYER_SIZE: [usize; 7] = [1 << 2, 1 << 3, 1 << 4, 1 << 5, 1 << 6, 1 << 7, 1 << 8];
         const BATCH_SIZE: [usize; 5] = [2, 3, 4, 5, 6];
@@ -406,30 +593,30 @@ mod tests {
             .into_iter()
             .cartesian_product(BATCH_SIZE.into_iter())
         {
-            let leaves: Vec<Vec<Fr>> = std::iter::repeat_with(|| {
-                std::iter::repeat_with(|| Fr::random(&mut rng))
+            let leaves: Vec<Vec<F>> = std::iter::repeat_with(|| {
+                std::iter::repeat_with(|| F::random(&mut rng))
                     .take(layer_size)
                     .collect::<Vec<_>>()
             })
             .take(batch_size)
             .collect();
 
-            let mut batched_circuit = <BatchedDenseGrandProduct<Fr> as BatchedGrandProduct<
-                Fr,
-                Zeromorph<Bn254, KeccakTranscript>,
+            let mut batched_circuit = <BatchedDenseGrandProduct<F> as BatchedGrandProduct<
+                F,
+                PCS,
                 KeccakTranscript,
             >>::construct((leaves.concat(), batch_size));
             let mut prover_transcript: KeccakTranscript = KeccakTranscript::new(b"test_transcript");
 
             // I love the rust type system
-            let claims = <BatchedDenseGrandProduct<Fr> as BatchedGrandProduct<
-                Fr,
-                Zeromorph<Bn254, KeccakTranscript>,
+            let claims = <BatchedDenseGrandProduct<F> as BatchedGrandProduct<
+                F,
+                PCS,
                 KeccakTranscript,
             >>::claimed_outputs(&batched_circuit);
-            let (proof, r_prover) = <BatchedDenseGrandProduct<Fr> as BatchedGrandProduct<
-                Fr,
-                Zeromorph<Bn254, KeccakTranscript>,
+            let (proof, r_prover) = <BatchedDenseGrandProduct<F> as BatchedGrandProduct<
+                F,
+                PCS,
                 KeccakTranscript,
             >>::prove_grand_product(
                 &mut batched_circuit, None, &mut prover_transcript, None
@@ -444,8 +631,22 @@ mod tests {
                 None,
                 &mut verifier_transcript,
                 None,
-            );
+            )
+            .unwrap();
             assert_eq!(r_prover, r_verifier);
         }
     }
+
+    #[test]
+    fn dense_prove_verify() {
+        run_dense_prove_verify_test::<Fr, Zeromorph<Bn254, KeccakTranscript>>();
+    }
+
+    #[test]
+    fn dense_prove_verify_binius() {
+        run_dense_prove_verify_test::<
+            BiniusField<BinaryField128bPolyval>,
+            Binius128Scheme<KeccakTranscript>,
+        >();
+    }
 }
@@ -9,6 +9,7 @@ use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::eq_poly::EqPolynomial;
 use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
 use crate::subprotocols::QuarkHybridLayerDepth;
+use crate::utils::errors::GrandProductError;
 use crate::utils::math::Math;
 use crate::utils::transcript::{AppendToTranscript, Transcript};
 use ark_serialize::*;
@@ -168,8 +169,8 @@ where
         claimed_outputs: &[F],
         opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
         transcript: &mut ProofTranscript,
-        _setup: Option<&PCS::Setup>,
-    ) -> (F, Vec<F>) {
+        _setup: Option<&PCS::VerifierSetup>,
+    ) -> Result<(F, Vec<F>), GrandProductError> {
         QuarkGrandProductBase::verify_quark_grand_product::<Self, PCS>(
             proof,
             claimed_outputs,
@@ -243,7 +244,7 @@ where
         claimed_outputs: &[F],
         opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
         transcript: &mut ProofTranscript,
-    ) -> (F, Vec<F>)
+    ) -> Result<(F, Vec<F>), GrandProductError>
     where
         PCS: CommitmentScheme<ProofTranscript, Field = F>,
         G: BatchedGrandProduct<F, PCS, ProofTranscript>,
@@ -266,7 +267,7 @@ where
                         transcript,
                         v_len,
                     )
-                    .unwrap_or_else(|e| panic!("quark verify error: {:?}", e))
+                    .map_err(GrandProductError::Quark)?
             }
             None => {
                 // Otherwise we must check the actual claims and the preset random will be empty.
@@ -274,10 +275,7 @@ where
             }
         };
 
-        let (grand_product_claim, grand_product_r) =
-            G::verify_layers(&proof.gkr_layers, claim, transcript, rand);
-
-        (grand_product_claim, grand_product_r)
+        G::verify_layers(&proof.gkr_layers, claim, transcript, rand)
     }
 }
 
@@ -682,7 +680,7 @@ mod quark_grand_product_tests {
                 KeccakTranscript,
             >>::construct_with_config((v, 2), config);
         let mut prover_accumulator: ProverOpeningAccumulator<Fr, KeccakTranscript> =
-            ProverOpeningAccumulator::new();
+            ProverOpeningAccumulator::new::<Zeromorph<Bn254, KeccakTranscript>>();
         let proof: BatchedGrandProductProof<Zeromorph<Bn254, KeccakTranscript>, KeccakTranscript> =
             hybrid_grand_product
                 .prove_grand_product(
@@ -691,7 +689,8 @@ mod quark_grand_product_tests {
                     Some(&setup),
                 )
                 .0;
-        let batched_proof = prover_accumulator.reduce_and_prove(&setup, &mut prover_transcript);
+        let batched_proof =
+            prover_accumulator.reduce_and_prove(&setup, &[], &mut prover_transcript);
 
         // Note resetting the transcript is important
         let mut verifier_transcript = KeccakTranscript::new(b"test_transcript");
@@ -703,15 +702,16 @@ mod quark_grand_product_tests {
         > = VerifierOpeningAccumulator::new();
         verifier_accumulator.compare_to(prover_accumulator, &setup);
 
-        let _ = QuarkGrandProduct::verify_grand_product(
+        QuarkGrandProduct::verify_grand_product(
             &proof,
             &known_products,
             Some(&mut verifier_accumulator),
             &mut verifier_transcript,
             None,
-        );
+        )
+        .unwrap();
         assert!(verifier_accumulator
-            .reduce_and_verify(&setup, &batched_proof, &mut verifier_transcript)
+            .reduce_and_verify(&setup.1, &batched_proof, &mut verifier_transcript)
             .is_ok());
     }
 
@@ -1,6 +1,6 @@
 use super::grand_product::{
     BatchedGrandProduct, BatchedGrandProductLayer, BatchedGrandProductLayerProof,
-    BatchedGrandProductProof,
+    BatchedGrandProductProof, GrandProductBackend,
 };
 use super::sumcheck::{BatchedCubicSumcheck, Bindable};
 use crate::field::{JoltField, OptimizedMul};
@@ -13,6 +13,7 @@ use crate::poly::split_eq_poly::SplitEqPolynomial;
 use crate::poly::unipoly::UniPoly;
 use crate::subprotocols::grand_product_quarks::QuarkGrandProductBase;
 use crate::subprotocols::QuarkHybridLayerDepth;
+use crate::utils::errors::{GrandProductError, Mismatch};
 use crate::utils::math::Math;
 use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::Transcript;
@@ -412,17 +413,18 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                         let eq_evals = {
                             let eval_point_0 = eq_chunk[0];
                             let m_eq = eq_chunk[1] - eq_chunk[0];
-                            let eval_point_2 = eq_chunk[1] + m_eq;
+                            let eval_point_2 = F::step_linear_eval(eq_chunk[1], m_eq, 1);
                             let eval_point_3 = eval_point_2 + m_eq;
                             (eval_point_0, eval_point_2, eval_point_3)
                         };
                         let m_flag = flags[1] - flags[0];
                         let m_fingerprint = fingerprints[1] - fingerprints[0];
 
-                        let flag_eval_2 = flags[1] + m_flag;
+                        let flag_eval_2 = F::step_linear_eval(flags[1], m_flag, 1);
                         let flag_eval_3 = flag_eval_2 + m_flag;
 
-                        let fingerprint_eval_2 = fingerprints[1] + m_fingerprint;
+                        let fingerprint_eval_2 =
+                            F::step_linear_eval(fingerprints[1], m_fingerprint, 1);
                         let fingerprint_eval_3 = fingerprint_eval_2 + m_fingerprint;
 
                         (
@@ -445,7 +447,7 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                     .map(|E1_chunk| {
                         let eval_point_0 = E1_chunk[0];
                         let m_eq = E1_chunk[1] - E1_chunk[0];
-                        let eval_point_2 = E1_chunk[1] + m_eq;
+                        let eval_point_2 = F::step_linear_eval(E1_chunk[1], m_eq, 1);
                         let eval_point_3 = eval_point_2 + m_eq;
                         (eval_point_0, eval_point_2, eval_point_3)
                     })
@@ -469,10 +471,11 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                             let m_flag = flag_chunk[1] - flag_chunk[0];
                             let m_fingerprint = fingerprint_chunk[1] - fingerprint_chunk[0];
 
-                            let flag_eval_2 = flag_chunk[1] + m_flag;
+                            let flag_eval_2 = F::step_linear_eval(flag_chunk[1], m_flag, 1);
                             let flag_eval_3 = flag_eval_2 + m_flag;
 
-                            let fingerprint_eval_2 = fingerprint_chunk[1] + m_fingerprint;
+                            let fingerprint_eval_2 =
+                                F::step_linear_eval(fingerprint_chunk[1], m_fingerprint, 1);
                             let fingerprint_eval_3 = fingerprint_eval_2 + m_fingerprint;
 
                             inner_sum.0 += E1_evals.0
@@ -513,7 +516,7 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                 .map(|eq_chunk| {
                     let eval_point_0 = eq_chunk[0];
                     let m_eq = eq_chunk[1] - eq_chunk[0];
-                    let eval_point_2 = eq_chunk[1] + m_eq;
+                    let eval_point_2 = F::step_linear_eval(eq_chunk[1], m_eq, 1);
                     let eval_point_3 = eval_point_2 + m_eq;
                     (eval_point_0, eval_point_2, eval_point_3)
                 })
@@ -592,10 +595,11 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
 
                         // If flags are still unbound, flag evals will mostly be 0s and 1s
                         // Bound flags are still mostly 0s, so flag evals will mostly be 0s.
-                        let flag_eval_2 = flags.1 + m_flag;
+                        let flag_eval_2 = F::step_linear_eval(flags.1, m_flag, 1);
                         let flag_eval_3 = flag_eval_2 + m_flag;
 
-                        let fingerprint_eval_2 = fingerprints.1 + m_fingerprint;
+                        let fingerprint_eval_2 =
+                            F::step_linear_eval(fingerprints.1, m_fingerprint, 1);
                         let fingerprint_eval_3 = fingerprint_eval_2 + m_fingerprint;
 
                         let block_index = (self.layer_len * batch_index) / 4 + index / 2;
@@ -633,7 +637,7 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                 .map(|E1_chunk| {
                     let eval_point_0 = E1_chunk[0];
                     let m_eq = E1_chunk[1] - E1_chunk[0];
-                    let eval_point_2 = E1_chunk[1] + m_eq;
+                    let eval_point_2 = F::step_linear_eval(E1_chunk[1], m_eq, 1);
                     let eval_point_3 = eval_point_2 + m_eq;
                     (eval_point_0, eval_point_2, eval_point_3)
                 })
@@ -717,10 +721,11 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
 
                         // If flags are still unbound, flag evals will mostly be 0s and 1s
                         // Bound flags are still mostly 0s, so flag evals will mostly be 0s.
-                        let flag_eval_2 = flags.1 + m_flag;
+                        let flag_eval_2 = F::step_linear_eval(flags.1, m_flag, 1);
                         let flag_eval_3 = flag_eval_2 + m_flag;
 
-                        let fingerprint_eval_2 = fingerprints.1 + m_fingerprint;
+                        let fingerprint_eval_2 =
+                            F::step_linear_eval(fingerprints.1, m_fingerprint, 1);
                         let fingerprint_eval_3 = fingerprint_eval_2 + m_fingerprint;
 
                         let block_index = (self.layer_len * batch_index) / 4 + index / 2;
@@ -846,7 +851,7 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                     let eq_evals = {
                         let eval_point_0 = eq_chunk[0];
                         let m_eq = eq_chunk[1] - eq_chunk[0];
-                        let eval_point_2 = eq_chunk[1] + m_eq;
+                        let eval_point_2 = F::step_linear_eval(eq_chunk[1], m_eq, 1);
                         let eval_point_3 = eval_point_2 + m_eq;
                         (eval_point_0, eval_point_2, eval_point_3)
                     };
@@ -862,10 +867,10 @@ impl<F: JoltField, ProofTranscript: Transcript> BatchedCubicSumcheck<F, ProofTra
                     let m_flag = flags.1 - flags.0;
                     let m_fingerprint = fingerprints.1 - fingerprints.0;
 
-                    let flag_eval_2 = flags.1 + m_flag;
+                    let flag_eval_2 = F::step_linear_eval(flags.1, m_flag, 1);
                     let flag_eval_3 = flag_eval_2 + m_flag;
 
-                    let fingerprint_eval_2 = fingerprints.1 + m_fingerprint;
+                    let fingerprint_eval_2 = F::step_linear_eval(fingerprints.1, m_fingerprint, 1);
                     let fingerprint_eval_3 = fingerprint_eval_2 + m_fingerprint;
 
                     (
@@ -946,6 +951,17 @@ impl Default for SparseGrandProductConfig {
     }
 }
 
+impl From<GrandProductBackend> for SparseGrandProductConfig {
+    fn from(backend: GrandProductBackend) -> Self {
+        match backend {
+            GrandProductBackend::Dense => Self::default(),
+            GrandProductBackend::QuarkHybrid(config) => Self {
+                hybrid_layer_depth: config.hybrid_layer_depth,
+            },
+        }
+    }
+}
+
 pub struct ToggledBatchedGrandProduct<F: JoltField> {
     batch_size: usize,
     toggle_layer: BatchedGrandProductToggleLayer<F>,
@@ -1068,8 +1084,8 @@ where
         claimed_outputs: &[F],
         opening_accumulator: Option<&mut VerifierOpeningAccumulator<F, PCS, ProofTranscript>>,
         transcript: &mut ProofTranscript,
-        _setup: Option<&PCS::Setup>,
-    ) -> (F, Vec<F>) {
+        _setup: Option<&PCS::VerifierSetup>,
+    ) -> Result<(F, Vec<F>), GrandProductError> {
         QuarkGrandProductBase::verify_quark_grand_product::<Self, PCS>(
             proof,
             claimed_outputs,
@@ -1086,14 +1102,22 @@ where
         grand_product_claim: &mut F,
         r_grand_product: &mut Vec<F>,
         transcript: &mut ProofTranscript,
-    ) {
+    ) -> Result<(), GrandProductError> {
         let layer_proof = &layer_proofs[layer_index];
+        let check_claim = |expected_sumcheck_claim: F| {
+            Mismatch::check(sumcheck_claim, expected_sumcheck_claim).map_err(|mismatch| {
+                GrandProductError::LayerClaims {
+                    layer: layer_index,
+                    mismatch,
+                }
+            })
+        };
         if layer_index != layer_proofs.len() - 1 {
             // Normal grand product layer (multiplication gates)
             let expected_sumcheck_claim: F =
                 layer_proof.left_claim * layer_proof.right_claim * eq_eval;
 
-            assert_eq!(expected_sumcheck_claim, sumcheck_claim);
+            check_claim(expected_sumcheck_claim)?;
 
             // produce a random challenge to condense two claims into a single claim
             let r_layer = transcript.challenge_scalar();
@@ -1109,27 +1133,31 @@ where
                 * (layer_proof.left_claim * layer_proof.right_claim + F::one()
                     - layer_proof.left_claim);
 
-            assert_eq!(expected_sumcheck_claim, sumcheck_claim);
+            check_claim(expected_sumcheck_claim)?;
 
             // flag * fingerprint + 1 - flag
             *grand_product_claim = layer_proof.left_claim * layer_proof.right_claim + F::one()
                 - layer_proof.left_claim;
         }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::field::binius::BiniusField;
     use crate::poly::commitment::zeromorph::ZeromorphSRS;
     use crate::{
         poly::{
-            commitment::zeromorph::Zeromorph, dense_interleaved_poly::DenseInterleavedPolynomial,
+            commitment::{binius::Binius128Scheme, zeromorph::Zeromorph},
+            dense_interleaved_poly::DenseInterleavedPolynomial,
         },
         utils::transcript::KeccakTranscript,
     };
     use ark_bn254::{Bn254, Fr};
     use ark_std::{rand::Rng, test_rng, One};
+    use binius_field::BinaryField128bPolyval;
     use itertools::Itertools;
     use rand_core::SeedableRng;
 
@@ -1266,68 +1294,68 @@ mod tests {
         }
     }
 
-    fn run_sparse_prove_verify_test(
+    fn run_sparse_prove_verify_test<F, PCS>(
         num_vars: usize,
         density: f64,
         batch_size: usize,
         config: SparseGrandProductConfig,
-    ) {
+        setup: &PCS::Setup,
+    ) where
+        F: JoltField,
+        PCS: CommitmentScheme<KeccakTranscript, Field = F>,
+    {
         let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(1111_u64);
         let layer_size = 1 << num_vars;
 
-        let fingerprints: Vec<Vec<Fr>> = (0..batch_size)
-            .map(|_| (0..layer_size).map(|_| Fr::random(&mut rng)).collect())
+        let fingerprints: Vec<Vec<F>> = (0..batch_size)
+            .map(|_| (0..layer_size).map(|_| F::random(&mut rng)).collect())
             .collect();
 
         let flags: Vec<Vec<usize>> = (0..batch_size / 2)
             .map(|_| (0..layer_size).filter(|_| rng.gen_bool(density)).collect())
             .collect();
 
-        let srs = ZeromorphSRS::<Bn254>::setup(&mut rng, 1 << 10);
-        let setup = srs.trim(1 << 10);
-
         // Construct circuit with configuration
-        let mut circuit = <ToggledBatchedGrandProduct<Fr> as BatchedGrandProduct<
-            Fr,
-            Zeromorph<Bn254, KeccakTranscript>,
+        let mut circuit = <ToggledBatchedGrandProduct<F> as BatchedGrandProduct<
+            F,
+            PCS,
             KeccakTranscript,
         >>::construct_with_config((flags, fingerprints), config);
 
-        let claims = <ToggledBatchedGrandProduct<Fr> as BatchedGrandProduct<
-            Fr,
-            Zeromorph<Bn254, KeccakTranscript>,
+        let claims = <ToggledBatchedGrandProduct<F> as BatchedGrandProduct<
+            F,
+            PCS,
             KeccakTranscript,
         >>::claimed_outputs(&circuit);
 
         // Prover setup
         let mut prover_transcript = KeccakTranscript::new(b"test_transcript");
-        let mut prover_accumulator = ProverOpeningAccumulator::<Fr, KeccakTranscript>::new();
-        let (proof, r_prover) = <ToggledBatchedGrandProduct<Fr> as BatchedGrandProduct<
-            Fr,
-            Zeromorph<Bn254, KeccakTranscript>,
+        let mut prover_accumulator = ProverOpeningAccumulator::<F, KeccakTranscript>::new::<PCS>();
+        let (proof, r_prover) = <ToggledBatchedGrandProduct<F> as BatchedGrandProduct<
+            F,
+            PCS,
             KeccakTranscript,
         >>::prove_grand_product(
             &mut circuit,
             Some(&mut prover_accumulator),
             &mut prover_transcript,
-            Some(&setup),
+            Some(setup),
         );
 
         // Verifier setup
+        let verifier_setup = PCS::verifier_setup(setup);
         let mut verifier_transcript = KeccakTranscript::new(b"test_transcript");
-        let mut verifier_accumulator = VerifierOpeningAccumulator::<
-            Fr,
-            Zeromorph<Bn254, KeccakTranscript>,
-            KeccakTranscript,
-        >::new();
+        let mut verifier_accumulator =
+            VerifierOpeningAccumulator::<F, PCS, KeccakTranscript>::new();
         verifier_transcript.compare_to(prover_transcript);
         let (_, r_verifier) = ToggledBatchedGrandProduct::verify_grand_product(
             &proof,
             &claims,
             Some(&mut verifier_accumulator),
             &mut verifier_transcript,
-            Some(&setup),
-        );
+            Some(&verifier_setup),
+        )
+        .unwrap();
 
         assert_eq!(
             r_prover, r_verifier,
@@ -1335,8 +1363,11 @@ mod tests {
         );
     }
 
-    #[test]
-    fn sparse_prove_verify() {
+    fn run_sparse_prove_verify_tests<F, PCS>(setup: &PCS::Setup)
+    where
+        F: JoltField,
+        PCS: CommitmentScheme<KeccakTranscript, Field = F>,
+    {
         const NUM_VARS: [usize; 7] = [1, 2, 3, 4, 5, 6, 7];
         const DENSITY: [f64; 6] = [0.0, 0.2, 0.4, 0.6, 0.8, 1.0];
         const BATCH_SIZE: [usize; 4] = [2, 4, 6, 8];
@@ -1363,11 +1394,33 @@ mod tests {
                     "Running test with num_vars = {}, density = {}, batch_size = {}, config = {:?}",
                     num_vars, density, batch_size, config
                 );
-                run_sparse_prove_verify_test(num_vars, density, batch_size, config.clone());
+                run_sparse_prove_verify_test::<F, PCS>(
+                    num_vars,
+                    density,
+                    batch_size,
+                    config.clone(),
+                    setup,
+                );
             }
         }
     }
 
+    #[test]
+    fn sparse_prove_verify() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(1111_u64);
+        let srs = ZeromorphSRS::<Bn254>::setup(&mut rng, 1 << 10);
+        let setup = srs.trim(1 << 10);
+        run_sparse_prove_verify_tests::<Fr, Zeromorph<Bn254, KeccakTranscript>>(&setup);
+    }
+
+    #[test]
+    fn sparse_prove_verify_binius() {
+        run_sparse_prove_verify_tests::<
+            BiniusField<BinaryField128bPolyval>,
+            Binius128Scheme<KeccakTranscript>,
+        >(&());
+    }
+
     #[test]
     fn sparse_construct() {
         let mut rng = test_rng();
@@ -6,7 +6,7 @@ use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::split_eq_poly::SplitEqPolynomial;
 use crate::poly::unipoly::{CompressedUniPoly, UniPoly};
 use crate::r1cs::special_polys::{SparsePolynomial, SparseTripleIterator};
-use crate::utils::errors::ProofVerifyError;
+use crate::utils::errors::SumcheckError;
 use crate::utils::math::Math;
 use crate::utils::mul_0_optimized;
 use crate::utils::thread::drop_in_background_thread;
@@ -141,14 +141,17 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
                     // D_n(index, 2) = D_{n-1}[HIGH] + (D_{n-1}[HIGH] - D_{n-1}[LOW])
                     // D_n(index, 3) = D_{n-1}[HIGH] + (D_{n-1}[HIGH] - D_{n-1}[LOW]) + (D_{n-1}[HIGH] - D_{n-1}[LOW])
                     // ...
+                    // (see `JoltField::step_linear_eval` for fields where this doesn't hold)
                     let mut existing_term = params_one;
                     for eval_i in 2..(combined_degree + 1) {
                         let mut poly_evals = vec![F::zero(); polys.len()];
                         for poly_i in 0..polys.len() {
                             let poly = &polys[poly_i];
-                            poly_evals[poly_i] = existing_term[poly_i]
-                                + poly[mle_half + poly_term_i]
-                                - poly[poly_term_i];
+                            poly_evals[poly_i] = F::step_linear_eval(
+                                existing_term[poly_i],
+                                poly[mle_half + poly_term_i] - poly[poly_term_i],
+                                eval_i as u64 - 1,
+                            );
                         }
 
                         accum[eval_i] += comb_func(&poly_evals);
@@ -231,7 +234,7 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
                 .map(|eq_chunk| {
                     let eval_point_0 = eq_chunk[0];
                     let m_eq = eq_chunk[1] - eq_chunk[0];
-                    let eval_point_2 = eq_chunk[1] + m_eq;
+                    let eval_point_2 = F::step_linear_eval(eq_chunk[1], m_eq, 1);
                     let eval_point_3 = eval_point_2 + m_eq;
                     (eval_point_0, eval_point_2, eval_point_3)
                 })
@@ -259,9 +262,9 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
                         let m_C = c_high - c_low;
 
                         // eval 2
-                        let poly_A_bound_point = a_high + m_A;
-                        let poly_B_bound_point = b_high + m_B;
-                        let poly_C_bound_point = c_high + m_C;
+                        let poly_A_bound_point = F::step_linear_eval(a_high, m_A, 1);
+                        let poly_B_bound_point = F::step_linear_eval(b_high, m_B, 1);
+                        let poly_C_bound_point = F::step_linear_eval(c_high, m_C, 1);
                         eval_point_2 += comb_func(
                             &eq_evals.1,
                             &poly_A_bound_point,
@@ -294,7 +297,7 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
                 .map(|E1_chunk| {
                     let eval_point_0 = E1_chunk[0];
                     let m_eq = E1_chunk[1] - E1_chunk[0];
-                    let eval_point_2 = E1_chunk[1] + m_eq;
+                    let eval_point_2 = F::step_linear_eval(E1_chunk[1], m_eq, 1);
                     let eval_point_3 = eval_point_2 + m_eq;
                     (eval_point_0, eval_point_2, eval_point_3)
                 })
@@ -341,9 +344,9 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
                         let m_C = c_high - c_low;
 
                         // eval 2
-                        let poly_A_bound_point = a_high + m_A;
-                        let poly_B_bound_point = b_high + m_B;
-                        let poly_C_bound_point = c_high + m_C;
+                        let poly_A_bound_point = F::step_linear_eval(a_high, m_A, 1);
+                        let poly_B_bound_point = F::step_linear_eval(b_high, m_B, 1);
+                        let poly_C_bound_point = F::step_linear_eval(c_high, m_C, 1);
                         inner_sums.1 += comb_func(
                             &E1_evals.1,
                             &poly_A_bound_point,
@@ -648,19 +651,25 @@ impl<F: JoltField, ProofTranscript: Transcript> SumcheckInstanceProof<F, ProofTr
         num_rounds: usize,
         degree_bound: usize,
         transcript: &mut ProofTranscript,
-    ) -> Result<(F, Vec<F>), ProofVerifyError> {
+    ) -> Result<(F, Vec<F>), SumcheckError> {
         let mut e = claim;
         let mut r: Vec<F> = Vec::new();
 
         // verify that there is a univariate polynomial for each round
-        assert_eq!(self.compressed_polys.len(), num_rounds);
+        if self.compressed_polys.len() != num_rounds {
+            return Err(SumcheckError::NumRounds {
+                expected: num_rounds,
+                actual: self.compressed_polys.len(),
+            });
+        }
         for i in 0..self.compressed_polys.len() {
             // verify degree bound
             if self.compressed_polys[i].degree() != degree_bound {
-                return Err(ProofVerifyError::InvalidInputLength(
-                    degree_bound,
-                    self.compressed_polys[i].degree(),
-                ));
+                return Err(SumcheckError::Degree {
+                    round: i,
+                    expected: degree_bound,
+                    actual: self.compressed_polys[i].degree(),
+                });
             }
 
             // append the prover's message to the transcript
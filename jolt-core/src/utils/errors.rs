@@ -1,21 +1,178 @@
-use core::fmt::Debug;
+use core::fmt::{self, Debug, Display};
 use thiserror::Error;
 
+use crate::subprotocols::grand_product_quarks::QuarkError;
+
 #[derive(Error, Debug, Default)]
 pub enum ProofVerifyError {
     #[error("Invalid input length, expected length {0} but got {1}")]
     InvalidInputLength(usize, usize),
     #[error("Input too large")]
     InputTooLarge,
+    #[error("Output too large")]
+    OutputTooLarge,
+    #[error("Input commitment must be present if and only if the inputs are private")]
+    InputCommitmentShape,
     #[error("Proof verification failed")]
     #[default]
     InternalError,
     #[error("Compressed group element failed to decompress: {0:?}")]
     DecompressionError([u8; 32]),
-    #[error("R1CS proof verification failed: {0}")]
-    SpartanError(String),
     #[error("Length Error: SRS Length: {0}, Key Length: {0}")]
     KeyLengthError(usize, usize),
     #[error("Invalid key length: {0}, expected power of 2")]
     InvalidKeyLength(usize),
+    #[error("Bytecode memory checking failed: {0}")]
+    BytecodeMemoryChecking(MemoryCheckingError),
+    #[error("Read-write memory checking failed: {0}")]
+    ReadWriteMemoryChecking(MemoryCheckingError),
+    #[error("Program IO must be set before verifying the program outputs")]
+    MissingProgramIO,
+    #[error("Memory layout has no power-of-two sized IO region below RAM")]
+    InvalidMemoryLayout,
+    #[error("Program output check has {actual} rounds, expected between {min} and {max}")]
+    OutputCheckNumRounds {
+        min: usize,
+        max: usize,
+        actual: usize,
+    },
+    #[error("Program output check failed: {0}")]
+    OutputCheck(SumcheckError),
+    #[error("Timestamp range check failed: {0}")]
+    TimestampRangeCheck(MemoryCheckingError),
+    #[error("Instruction lookups primary sumcheck failed: {0}")]
+    InstructionLookupsSumcheck(SumcheckError),
+    #[error("Instruction lookups memory checking failed: {0}")]
+    InstructionLookupsMemoryChecking(MemoryCheckingError),
+    #[error("Spartan outer sumcheck failed: {0}")]
+    SpartanOuterSumcheck(SumcheckError),
+    #[error("Spartan inner sumcheck failed: {0}")]
+    SpartanInnerSumcheck(SumcheckError),
+    #[error("Batched opening reduction sumcheck failed: {0}")]
+    BatchedOpeningSumcheck(SumcheckError),
+    #[error("Batched opening proof failed: {0}")]
+    BatchedOpening(Box<ProofVerifyError>),
+    #[error("Expected {expected} opening proofs, got {actual}")]
+    NumOpeningProofs { expected: usize, actual: usize },
+}
+
+/// The claimed and expected values of a failed verifier check. The values themselves
+/// are only recorded in debug builds.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mismatch {
+    #[cfg(debug_assertions)]
+    pub claimed: String,
+    #[cfg(debug_assertions)]
+    pub expected: String,
+}
+
+impl Mismatch {
+    #[allow(unused_variables)]
+    pub fn new<T: Debug>(claimed: &T, expected: &T) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            claimed: format!("{claimed:?}"),
+            #[cfg(debug_assertions)]
+            expected: format!("{expected:?}"),
+        }
+    }
+
+    /// Returns `Ok(())` if `claimed == expected`, and the mismatch otherwise.
+    pub fn check<T: Debug + PartialEq>(claimed: T, expected: T) -> Result<(), Self> {
+        if claimed == expected {
+            Ok(())
+        } else {
+            Err(Self::new(&claimed, &expected))
+        }
+    }
+}
+
+impl Display for Mismatch {
+    #[cfg(debug_assertions)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "claimed {}, expected {}", self.claimed, self.expected)
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "claimed value does not match expected value")
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SumcheckError {
+    #[error("expected {expected} rounds, got {actual}")]
+    NumRounds { expected: usize, actual: usize },
+    #[error("round {round} polynomial has degree {actual}, expected {expected}")]
+    Degree {
+        round: usize,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("final claim does not match the claimed openings: {0}")]
+    FinalClaim(Mismatch),
+}
+
+#[derive(Error, Debug)]
+pub enum GrandProductError {
+    #[error("layer {layer} sumcheck is invalid: {error}")]
+    LayerSumcheck { layer: usize, error: SumcheckError },
+    #[error("layer {layer} claims are inconsistent with its sumcheck: {mismatch}")]
+    LayerClaims { layer: usize, mismatch: Mismatch },
+    #[error("Quarks proof is invalid: {0}")]
+    Quark(QuarkError),
+}
+
+#[derive(Error, Debug)]
+pub enum MemoryCheckingError {
+    #[error("expected {expected} multiset hashes of each kind, got {actual}")]
+    NumMultisetHashes { expected: usize, actual: usize },
+    #[error("multiset hashes of memory {memory} don't match: {mismatch}")]
+    MultisetHashes { memory: usize, mismatch: Mismatch },
+    #[error("read-write grand product is invalid: {0}")]
+    ReadWriteGrandProduct(GrandProductError),
+    #[error("init-final grand product is invalid: {0}")]
+    InitFinalGrandProduct(GrandProductError),
+    #[error("read-write fingerprints don't match the grand product claim: {0}")]
+    ReadWriteFingerprints(Mismatch),
+    #[error("init-final fingerprints don't match the grand product claim: {0}")]
+    InitFinalFingerprints(Mismatch),
+    /// For memory checking that batches all four multisets into a single grand product.
+    #[error("grand product is invalid: {0}")]
+    GrandProduct(GrandProductError),
+    /// For memory checking that batches all four multisets into a single grand product.
+    #[error("fingerprints don't match the grand product claim: {0}")]
+    Fingerprints(Mismatch),
+    #[error("missing commitment or opening for a preprocessed polynomial")]
+    MissingPreprocessedOpening,
+    #[error("preprocessed polynomial has {expected} variables, opening point has {actual}")]
+    PreprocessedOpeningPoint { expected: usize, actual: usize },
+}
+
+#[derive(Error, Debug)]
+pub enum ProverError {
+    #[error("Proving was cancelled")]
+    Cancelled,
+    #[error("Guest did not output an IO digest")]
+    MissingIoDigest,
+    #[error("Guest output digest does not match its private output")]
+    IoDigestMismatch,
+}
+
+#[derive(Error, Debug)]
+pub enum SRSError {
+    #[error("Failed to read SRS: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Malformed SRS file: {0}")]
+    Format(String),
+    #[error("SRS has {available} {group} powers, but {required} are required")]
+    TooFewPowers {
+        group: &'static str,
+        required: usize,
+        available: usize,
+    },
+    #[error("SRS {group} power {index} is not a nonzero point of the prime-order subgroup")]
+    InvalidPoint { group: &'static str, index: usize },
+    #[error("SRS G1 and G2 powers are not powers of the same tau")]
+    InconsistentPowers,
 }
@@ -4,11 +4,14 @@ use crate::field::JoltField;
 use ark_std::test_rng;
 use rayon::prelude::*;
 
+pub mod cancellation;
 pub mod errors;
 pub mod gaussian_elimination;
 pub mod instruction_utils;
 pub mod math;
+pub mod poseidon;
 pub mod profiling;
+pub mod recording_transcript;
 pub mod sol_types;
 pub mod thread;
 pub mod transcript;
@@ -0,0 +1,193 @@
+//! The Poseidon permutation (https://eprint.iacr.org/2019/458) over the BN254 scalar field,
+//! with the parameters of circomlib's `Poseidon(2)`: width 3, the `x^5` S-box, 8 full rounds
+//! and 57 partial rounds.
+
+use ark_bn254::Fr;
+use ark_ff::{BigInt, BigInteger, Field, PrimeField};
+use ark_std::Zero;
+
+/// Number of field elements in the permutation's state.
+pub const WIDTH: usize = 3;
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 57;
+
+lazy_static::lazy_static! {
+    static ref PARAMS: PoseidonParams = PoseidonParams::generate();
+}
+
+struct PoseidonParams {
+    round_constants: Vec<[Fr; WIDTH]>,
+    mds: [[Fr; WIDTH]; WIDTH],
+}
+
+impl PoseidonParams {
+    /// Derives the round constants and the (Cauchy) MDS matrix from the Grain LFSR, as the
+    /// reference `generate_parameters_grain.sage` script does.
+    fn generate() -> Self {
+        let mut grain = Grain::new(Fr::MODULUS_BIT_SIZE as usize, WIDTH);
+
+        let round_constants = (0..FULL_ROUNDS + PARTIAL_ROUNDS)
+            .map(|_| {
+                std::array::from_fn(|_| loop {
+                    // Rejection sampling
+                    if let Some(constant) = Fr::from_bigint(grain.next_bigint()) {
+                        break constant;
+                    }
+                })
+            })
+            .collect();
+
+        let mds = loop {
+            let points: Vec<Fr> = (0..2 * WIDTH)
+                .map(|_| Fr::from_be_bytes_mod_order(&grain.next_bigint().to_bytes_be()))
+                .collect();
+            let (xs, ys) = points.split_at(WIDTH);
+            let distinct = (0..points.len()).all(|i| !points[i + 1..].contains(&points[i]));
+            if distinct && xs.iter().all(|x| ys.iter().all(|y| !(*x + y).is_zero())) {
+                break std::array::from_fn(|i| {
+                    std::array::from_fn(|j| (xs[i] + ys[j]).inverse().unwrap())
+                });
+            }
+        };
+
+        Self {
+            round_constants,
+            mds,
+        }
+    }
+}
+
+/// The self-shrinking Grain LFSR the Poseidon parameters are sampled from.
+struct Grain {
+    field_bits: usize,
+    state: [bool; 80],
+}
+
+impl Grain {
+    fn new(field_bits: usize, width: usize) -> Self {
+        let mut state = [false; 80];
+        let fields = [
+            (1, 2), // prime field
+            (0, 4), // x^alpha S-box
+            (field_bits, 12),
+            (width, 12),
+            (FULL_ROUNDS, 10),
+            (PARTIAL_ROUNDS, 10),
+            ((1 << 30) - 1, 30),
+        ];
+        let mut i = 0;
+        for (value, num_bits) in fields {
+            for bit in (0..num_bits).rev() {
+                state[i] = (value >> bit) & 1 == 1;
+                i += 1;
+            }
+        }
+
+        let mut grain = Self { field_bits, state };
+        for _ in 0..160 {
+            grain.next_raw_bit();
+        }
+        grain
+    }
+
+    fn next_raw_bit(&mut self) -> bool {
+        let s = &self.state;
+        let bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+        self.state.rotate_left(1);
+        self.state[79] = bit;
+        bit
+    }
+
+    fn next_bit(&mut self) -> bool {
+        // Output the second bit of each pair whose first bit is set
+        while !self.next_raw_bit() {
+            self.next_raw_bit();
+        }
+        self.next_raw_bit()
+    }
+
+    /// Samples `field_bits` bits, most significant first.
+    fn next_bigint(&mut self) -> BigInt<4> {
+        let bits: Vec<bool> = (0..self.field_bits).map(|_| self.next_bit()).collect();
+        BigInt::from_bits_be(&bits)
+    }
+}
+
+/// Applies the Poseidon permutation to `state`.
+pub fn permute(state: &mut [Fr; WIDTH]) {
+    let params = &*PARAMS;
+    for (round, constants) in params.round_constants.iter().enumerate() {
+        for (s, c) in state.iter_mut().zip(constants.iter()) {
+            *s += c;
+        }
+        if round < FULL_ROUNDS / 2 || round >= FULL_ROUNDS / 2 + PARTIAL_ROUNDS {
+            state.iter_mut().for_each(sbox);
+        } else {
+            sbox(&mut state[0]);
+        }
+        *state = std::array::from_fn(|i| {
+            params.mds[i]
+                .iter()
+                .zip(state.iter())
+                .map(|(m, s)| *m * s)
+                .sum()
+        });
+    }
+}
+
+#[inline(always)]
+fn sbox(x: &mut Fr) {
+    let x2 = x.square();
+    *x *= x2.square();
+}
+
+/// The two-to-one Poseidon hash, equal to circomlib's `Poseidon(2)`.
+pub fn hash(left: Fr, right: Fr) -> Fr {
+    let mut state = [Fr::zero(), left, right];
+    permute(&mut state);
+    state[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn circomlib_test_vectors() {
+        assert_eq!(
+            hash(Fr::from(1u64), Fr::from(2u64)),
+            Fr::from_str(
+                "7853200120776062878684798364095072458815029376092732009249414926327459813530"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            hash(Fr::from(3u64), Fr::from(4u64)),
+            Fr::from_str(
+                "14763215145315200506921711489642608356394854266165572616578112107564877678998"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn grain_constants() {
+        // The first round constant and MDS entry of circomlib's `Poseidon(2)`
+        assert_eq!(
+            PARAMS.round_constants[0][0],
+            Fr::from_str(
+                "6745197990210204598374042828761989596302876299545964402857411729872131034734"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            PARAMS.mds[0][0],
+            Fr::from_str(
+                "7511745149465107256748700652201246547602992235352608707588321460060273774987"
+            )
+            .unwrap()
+        );
+        assert_eq!(PARAMS.round_constants.len(), FULL_ROUNDS + PARTIAL_ROUNDS);
+    }
+}
@@ -0,0 +1,329 @@
+//! A [`Transcript`] wrapper that logs every operation, for locating where a prover's and a
+//! verifier's Fiat-Shamir transcripts diverge outside of tests, where
+//! [`Transcript::compare_to`] is unavailable.
+//!
+//! Prove and verify with `RecordingTranscript<T>` in place of `T`, each inside
+//! [`TranscriptLog::record`], and compare the logs with [`TranscriptLog::first_divergence`].
+//! The wrapper does not change the transcript, so proofs are interchangeable with those made
+//! with `T`.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::Location;
+use std::sync::{Arc, Mutex};
+
+use ark_ec::CurveGroup;
+use ark_serialize::CanonicalSerialize;
+
+use crate::field::JoltField;
+use crate::utils::transcript::Transcript;
+
+thread_local! {
+    /// The log that `RecordingTranscript`s created on this thread record into.
+    static ACTIVE_LOG: RefCell<Option<TranscriptLog>> = const { RefCell::new(None) };
+}
+
+/// A single operation on a transcript.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranscriptEvent {
+    /// The protocol stage, as last set by [`Transcript::begin_stage`].
+    pub stage: &'static str,
+    /// The name of the `Transcript` method, e.g. `"append_scalar"` or `"challenge_vector"`.
+    pub operation: &'static str,
+    /// The serialized data appended to the transcript, or the challenges drawn from it.
+    pub data: Vec<u8>,
+    /// Where in the prover or verifier the transcript was called.
+    pub location: &'static Location<'static>,
+}
+
+impl TranscriptEvent {
+    /// Whether two events (e.g. of a prover and a verifier) agree, ignoring their locations.
+    pub fn matches(&self, other: &Self) -> bool {
+        self.stage == other.stage && self.operation == other.operation && self.data == other.data
+    }
+}
+
+impl fmt::Display for TranscriptEvent {
+    /// Formats the event as tab-separated `stage operation data location`, with the
+    /// location last so that logs can be diffed on the first three columns.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\t{}\t", self.stage, self.operation)?;
+        if matches!(self.operation, "new" | "append_message") {
+            write!(f, "{}", String::from_utf8_lossy(&self.data))?;
+        } else {
+            write!(f, "0x")?;
+            for byte in &self.data {
+                write!(f, "{byte:02x}")?;
+            }
+        }
+        write!(f, "\t{}", self.location)
+    }
+}
+
+#[derive(Default)]
+struct LogState {
+    stage: &'static str,
+    events: Vec<TranscriptEvent>,
+}
+
+/// Restores the log that was active before [`TranscriptLog::record`] when dropped, so that
+/// a panic in the recorded closure doesn't leave its log active on the thread.
+struct ActiveLogGuard(Option<TranscriptLog>);
+
+impl Drop for ActiveLogGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        ACTIVE_LOG.with(|active| active.replace(previous));
+    }
+}
+
+/// A log of transcript operations, shared by the `RecordingTranscript`s recording into it.
+#[derive(Clone, Default)]
+pub struct TranscriptLog(Arc<Mutex<LogState>>);
+
+impl TranscriptLog {
+    /// Runs `f`, recording the operations of every `RecordingTranscript` it creates on the
+    /// current thread (including operations those transcripts later perform on other
+    /// threads).
+    pub fn record<R>(f: impl FnOnce() -> R) -> (R, Self) {
+        let log = Self::default();
+        let _guard = ActiveLogGuard(ACTIVE_LOG.with(|active| active.replace(Some(log.clone()))));
+        (f(), log)
+    }
+
+    pub fn events(&self) -> Vec<TranscriptEvent> {
+        self.0.lock().unwrap().events.clone()
+    }
+
+    /// Returns the index of the first event at which `self` and `other` disagree, if any.
+    /// A log that is a strict prefix of the other disagrees at its end.
+    pub fn first_divergence(&self, other: &Self) -> Option<usize> {
+        let (events, other_events) = (self.events(), other.events());
+        events
+            .iter()
+            .zip(other_events.iter())
+            .position(|(event, other_event)| !event.matches(other_event))
+            .or_else(|| {
+                (events.len() != other_events.len()).then_some(events.len().min(other_events.len()))
+            })
+    }
+
+    fn set_stage(&self, stage: &'static str) {
+        self.0.lock().unwrap().stage = stage;
+    }
+
+    fn push(&self, operation: &'static str, data: Vec<u8>, location: &'static Location<'static>) {
+        let mut state = self.0.lock().unwrap();
+        let stage = state.stage;
+        state.events.push(TranscriptEvent {
+            stage,
+            operation,
+            data,
+            location,
+        });
+    }
+}
+
+impl fmt::Display for TranscriptLog {
+    /// Formats the log with one event per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, event) in self.events().iter().enumerate() {
+            writeln!(f, "{i}\t{event}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A transcript that behaves exactly like `T`, and additionally records its operations into
+/// the [`TranscriptLog`] active when it was created, if any.
+#[derive(Clone)]
+pub struct RecordingTranscript<T: Transcript> {
+    inner: T,
+    log: Option<TranscriptLog>,
+}
+
+impl<T: Transcript> RecordingTranscript<T> {
+    #[inline(always)]
+    #[track_caller]
+    fn record(&self, operation: &'static str, data: impl FnOnce() -> Vec<u8>) {
+        if let Some(log) = &self.log {
+            log.push(operation, data(), Location::caller());
+        }
+    }
+}
+
+fn serialize<S: CanonicalSerialize + ?Sized>(value: &S) -> Vec<u8> {
+    let mut buf = vec![];
+    value.serialize_compressed(&mut buf).unwrap();
+    buf
+}
+
+impl<T: Transcript> Transcript for RecordingTranscript<T> {
+    #[track_caller]
+    fn new(label: &'static [u8]) -> Self {
+        let transcript = Self {
+            inner: T::new(label),
+            log: ACTIVE_LOG.with(|active| active.borrow().clone()),
+        };
+        transcript.record("new", || label.to_vec());
+        transcript
+    }
+
+    #[cfg(test)]
+    fn compare_to(&mut self, other: Self) {
+        self.inner.compare_to(other.inner);
+    }
+
+    fn begin_stage(&mut self, stage: &'static str) {
+        if let Some(log) = &self.log {
+            log.set_stage(stage);
+        }
+        self.inner.begin_stage(stage);
+    }
+
+    #[track_caller]
+    fn append_message(&mut self, msg: &'static [u8]) {
+        self.record("append_message", || msg.to_vec());
+        self.inner.append_message(msg);
+    }
+
+    #[track_caller]
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        self.record("append_bytes", || bytes.to_vec());
+        self.inner.append_bytes(bytes);
+    }
+
+    #[track_caller]
+    fn append_u64(&mut self, x: u64) {
+        self.record("append_u64", || x.to_be_bytes().to_vec());
+        self.inner.append_u64(x);
+    }
+
+    #[track_caller]
+    fn append_scalar<F: JoltField>(&mut self, scalar: &F) {
+        self.record("append_scalar", || serialize(scalar));
+        self.inner.append_scalar(scalar);
+    }
+
+    #[track_caller]
+    fn append_scalars<F: JoltField>(&mut self, scalars: &[F]) {
+        self.record("append_scalars", || serialize(scalars));
+        self.inner.append_scalars(scalars);
+    }
+
+    #[track_caller]
+    fn append_point<G: CurveGroup>(&mut self, point: &G) {
+        self.record("append_point", || serialize(point));
+        self.inner.append_point(point);
+    }
+
+    #[track_caller]
+    fn append_points<G: CurveGroup>(&mut self, points: &[G]) {
+        self.record("append_points", || serialize(points));
+        self.inner.append_points(points);
+    }
+
+    #[track_caller]
+    fn challenge_scalar<F: JoltField>(&mut self) -> F {
+        let challenge: F = self.inner.challenge_scalar();
+        self.record("challenge_scalar", || serialize(&challenge));
+        challenge
+    }
+
+    #[track_caller]
+    fn challenge_vector<F: JoltField>(&mut self, len: usize) -> Vec<F> {
+        let challenges: Vec<F> = self.inner.challenge_vector(len);
+        self.record("challenge_vector", || serialize(&challenges));
+        challenges
+    }
+
+    #[track_caller]
+    fn challenge_scalar_powers<F: JoltField>(&mut self, len: usize) -> Vec<F> {
+        let powers: Vec<F> = self.inner.challenge_scalar_powers(len);
+        self.record("challenge_scalar_powers", || serialize(&powers));
+        powers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::transcript::KeccakTranscript;
+    use ark_bn254::Fr;
+
+    type Recording = RecordingTranscript<KeccakTranscript>;
+
+    /// A toy protocol: absorb `claim`, then draw a challenge.
+    fn run_protocol(claim: u64) -> Fr {
+        let mut transcript = Recording::new(b"test_transcript");
+        transcript.begin_stage("Claim");
+        transcript.append_message(b"claim");
+        transcript.append_scalar(&Fr::from(claim));
+        transcript.begin_stage("Challenge");
+        transcript.challenge_scalar()
+    }
+
+    #[test]
+    fn recording_does_not_change_transcript() {
+        let mut transcript = KeccakTranscript::new(b"test_transcript");
+        transcript.append_message(b"claim");
+        transcript.append_scalar(&Fr::from(5u64));
+        let expected: Fr = transcript.challenge_scalar();
+
+        let (challenge, log) = TranscriptLog::record(|| run_protocol(5));
+        assert_eq!(challenge, expected);
+        // Without an active log nothing is recorded, but the transcript is the same
+        assert_eq!(run_protocol(5), expected);
+
+        let events = log.events();
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| (event.stage, event.operation))
+                .collect::<Vec<_>>(),
+            [
+                ("", "new"),
+                ("Claim", "append_message"),
+                ("Claim", "append_scalar"),
+                ("Challenge", "challenge_scalar"),
+            ]
+        );
+        assert_eq!(events[2].location.file(), file!());
+        assert_eq!(log.to_string().lines().count(), 4);
+    }
+
+    #[test]
+    fn first_divergence() {
+        let (_, prover_log) = TranscriptLog::record(|| run_protocol(5));
+        let (_, verifier_log) = TranscriptLog::record(|| run_protocol(5));
+        assert_eq!(prover_log.first_divergence(&verifier_log), None);
+
+        let (_, bad_verifier_log) = TranscriptLog::record(|| run_protocol(6));
+        assert_eq!(prover_log.first_divergence(&bad_verifier_log), Some(2));
+
+        let (_, truncated_log) = TranscriptLog::record(|| {
+            let mut transcript = Recording::new(b"test_transcript");
+            transcript.begin_stage("Claim");
+            transcript.append_message(b"claim");
+        });
+        assert_eq!(prover_log.first_divergence(&truncated_log), Some(2));
+    }
+
+    #[test]
+    fn record_restores_log_on_panic() {
+        let (_, outer_log) = TranscriptLog::record(|| {
+            let result = std::panic::catch_unwind(|| {
+                TranscriptLog::record(|| {
+                    run_protocol(5);
+                    panic!("prover panicked");
+                })
+            });
+            assert!(result.is_err());
+            run_protocol(5);
+        });
+        assert_eq!(outer_log.events().len(), 4);
+
+        let _ = std::panic::catch_unwind(|| TranscriptLog::record(|| panic!("prover panicked")));
+        assert!(ACTIVE_LOG.with(|active| active.borrow().is_none()));
+    }
+}
@@ -1,7 +1,11 @@
 use crate::field::JoltField;
+use crate::utils::poseidon;
 use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
 use ark_serialize::CanonicalSerialize;
+use ark_std::Zero;
 use sha3::{Digest, Keccak256};
+use std::any::TypeId;
 
 /// Represents the current state of the protocol's Fiat-Shamir transcript.
 #[derive(Clone)]
@@ -207,10 +211,196 @@ impl Transcript for KeccakTranscript {
     }
 }
 
+/// A Fiat-Shamir transcript whose running state is a BN254 scalar, updated with the
+/// two-to-one Poseidon hash of [`poseidon::hash`] (circomlib's `Poseidon(2)`). Unlike
+/// [`KeccakTranscript`], it is cheap to replay inside a circuit over BN254 Fr, e.g. when the
+/// Jolt verifier is itself proven.
+#[derive(Clone)]
+pub struct PoseidonTranscript {
+    pub state: ark_bn254::Fr,
+    /// We append an ordinal to each invocation of the hash
+    n_rounds: u32,
+    #[cfg(test)]
+    /// A complete history of the transcript's `state`; used for testing.
+    state_history: Vec<ark_bn254::Fr>,
+    #[cfg(test)]
+    /// The prover's `state_history`, which the verifier's is checked against in testing.
+    expected_state_history: Option<Vec<ark_bn254::Fr>>,
+}
+
+impl PoseidonTranscript {
+    /// Absorbs `elements` into the state, together with the round ordinal:
+    /// `state = H(...H(H(state, n_rounds), elements[0])..., elements[k - 1])`.
+    fn absorb(&mut self, elements: &[ark_bn254::Fr]) {
+        let initial = poseidon::hash(self.state, ark_bn254::Fr::from(self.n_rounds as u64));
+        let new_state = elements
+            .iter()
+            .fold(initial, |acc, element| poseidon::hash(acc, *element));
+        self.update_state(new_state);
+    }
+
+    fn squeeze(&mut self) -> ark_bn254::Fr {
+        self.absorb(&[]);
+        self.state
+    }
+
+    fn update_state(&mut self, new_state: ark_bn254::Fr) {
+        self.state = new_state;
+        self.n_rounds += 1;
+        #[cfg(test)]
+        {
+            if let Some(expected_state_history) = &self.expected_state_history {
+                assert!(
+                    new_state == expected_state_history[self.n_rounds as usize],
+                    "Fiat-Shamir transcript mismatch"
+                );
+            }
+            self.state_history.push(new_state);
+        }
+    }
+
+    /// Packs `bytes` into little-endian 31-byte chunks, each of which is a canonical field
+    /// element.
+    fn bytes_to_elements(bytes: &[u8]) -> Vec<ark_bn254::Fr> {
+        bytes
+            .chunks(31)
+            .map(ark_bn254::Fr::from_le_bytes_mod_order)
+            .collect()
+    }
+
+    /// Encodes a length-prefixed byte string.
+    fn byte_string_elements(bytes: &[u8]) -> Vec<ark_bn254::Fr> {
+        let mut elements = vec![ark_bn254::Fr::from(bytes.len() as u64)];
+        elements.extend(Self::bytes_to_elements(bytes));
+        elements
+    }
+
+    /// BN254 scalars are absorbed as they are; scalars of other fields are packed from their
+    /// byte representation.
+    fn scalar_elements<F: JoltField>(scalar: &F) -> Vec<ark_bn254::Fr> {
+        let mut buf = vec![];
+        scalar.serialize_uncompressed(&mut buf).unwrap();
+        if TypeId::of::<F>() == TypeId::of::<ark_bn254::Fr>() {
+            vec![ark_bn254::Fr::from_le_bytes_mod_order(&buf)]
+        } else {
+            Self::bytes_to_elements(&buf)
+        }
+    }
+
+    /// Encodes the affine coordinates of `point`, packed from their byte representation. The
+    /// point at infinity is encoded as (0, 0), which is not on the curve.
+    fn point_elements<G: CurveGroup>(point: &G) -> Vec<ark_bn254::Fr> {
+        let aff = point.into_affine();
+        let zero = G::BaseField::zero();
+        let (x, y) = aff.xy().unwrap_or((&zero, &zero));
+        let mut x_bytes = vec![];
+        x.serialize_compressed(&mut x_bytes).unwrap();
+        let mut y_bytes = vec![];
+        y.serialize_compressed(&mut y_bytes).unwrap();
+
+        let mut elements = Self::bytes_to_elements(&x_bytes);
+        elements.extend(Self::bytes_to_elements(&y_bytes));
+        elements
+    }
+}
+
+impl Transcript for PoseidonTranscript {
+    fn new(label: &'static [u8]) -> Self {
+        assert!(label.len() < 33);
+        let state = Self::bytes_to_elements(label)
+            .into_iter()
+            .fold(ark_bn254::Fr::zero(), poseidon::hash);
+
+        Self {
+            state,
+            n_rounds: 0,
+            #[cfg(test)]
+            state_history: vec![state],
+            #[cfg(test)]
+            expected_state_history: None,
+        }
+    }
+
+    #[cfg(test)]
+    /// Compare this transcript to `other` and panic if/when they deviate.
+    /// Typically used to compare the verifier's transcript to the prover's.
+    fn compare_to(&mut self, other: Self) {
+        self.expected_state_history = Some(other.state_history);
+    }
+
+    fn append_message(&mut self, msg: &'static [u8]) {
+        assert!(msg.len() < 33);
+        self.absorb(&Self::byte_string_elements(msg));
+    }
+
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        self.absorb(&Self::byte_string_elements(bytes));
+    }
+
+    fn append_u64(&mut self, x: u64) {
+        self.absorb(&[ark_bn254::Fr::from(x)]);
+    }
+
+    fn append_scalar<F: JoltField>(&mut self, scalar: &F) {
+        self.absorb(&Self::scalar_elements(scalar));
+    }
+
+    fn append_scalars<F: JoltField>(&mut self, scalars: &[F]) {
+        let mut elements = vec![ark_bn254::Fr::from(scalars.len() as u64)];
+        elements.extend(scalars.iter().flat_map(Self::scalar_elements));
+        self.absorb(&elements);
+    }
+
+    fn append_point<G: CurveGroup>(&mut self, point: &G) {
+        self.absorb(&Self::point_elements(point));
+    }
+
+    fn append_points<G: CurveGroup>(&mut self, points: &[G]) {
+        let mut elements = vec![ark_bn254::Fr::from(points.len() as u64)];
+        elements.extend(points.iter().flat_map(Self::point_elements));
+        self.absorb(&elements);
+    }
+
+    fn challenge_scalar<F: JoltField>(&mut self) -> F {
+        let mut buf = vec![];
+        if TypeId::of::<F>() == TypeId::of::<ark_bn254::Fr>() {
+            self.squeeze().serialize_uncompressed(&mut buf).unwrap();
+        } else {
+            // Take the (almost uniform) low 31 bytes of as many squeezes as needed
+            while buf.len() < F::NUM_BYTES {
+                let mut bytes = vec![];
+                self.squeeze().serialize_uncompressed(&mut bytes).unwrap();
+                buf.extend_from_slice(&bytes[..31]);
+            }
+            buf.truncate(F::NUM_BYTES);
+        }
+        F::from_bytes(&buf)
+    }
+
+    fn challenge_vector<F: JoltField>(&mut self, len: usize) -> Vec<F> {
+        (0..len)
+            .map(|_i| self.challenge_scalar())
+            .collect::<Vec<F>>()
+    }
+
+    // Compute powers of scalar q : (1, q, q^2, ..., q^(len-1))
+    fn challenge_scalar_powers<F: JoltField>(&mut self, len: usize) -> Vec<F> {
+        let q: F = self.challenge_scalar();
+        let mut q_powers = vec![F::one(); len];
+        for i in 1..len {
+            q_powers[i] = q_powers[i - 1] * q;
+        }
+        q_powers
+    }
+}
+
 pub trait Transcript: Clone + Sync + Send + 'static {
     fn new(label: &'static [u8]) -> Self;
     #[cfg(test)]
     fn compare_to(&mut self, other: Self);
+    /// Marks the start of a protocol stage, e.g. `"Spartan"`, for debugging. Does not change
+    /// the transcript.
+    fn begin_stage(&mut self, _stage: &'static str) {}
     fn append_message(&mut self, msg: &'static [u8]);
     fn append_bytes(&mut self, bytes: &[u8]);
     fn append_u64(&mut self, x: u64);
@@ -227,3 +417,108 @@ pub trait Transcript: Clone + Sync + Send + 'static {
 pub trait AppendToTranscript {
     fn append_to_transcript<ProofTranscript: Transcript>(&self, transcript: &mut ProofTranscript);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::binius::Binius128Scheme;
+    use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
+    use crate::poly::commitment::hyperkzg::HyperKZG;
+    use crate::poly::commitment::hyrax::HyraxScheme;
+    use crate::poly::commitment::ligero::Ligero;
+    use crate::poly::commitment::zeromorph::Zeromorph;
+    use crate::poly::dense_mlpoly::DensePolynomial;
+    use ark_bn254::{Bn254, Fq, Fr, G1Affine, G1Projective};
+    use ark_std::{test_rng, One};
+    use std::str::FromStr;
+
+    #[test]
+    fn poseidon_transcript_test_vector() {
+        let mut transcript = PoseidonTranscript::new(b"test_transcript");
+        assert_eq!(
+            transcript.state,
+            Fr::from_str(
+                "20559458659023659745070212571252145134614854903871501900857965050847675263680"
+            )
+            .unwrap()
+        );
+
+        transcript.append_bytes(b"jolt");
+        transcript.append_u64(42);
+        transcript.append_scalar(&Fr::from(7u64));
+        transcript.append_scalars(&[Fr::from(1u64), Fr::from(2u64), Fr::from(3u64)]);
+        transcript.append_point(&G1Projective::from(G1Affine::new(
+            Fq::from(1u64),
+            Fq::from(2u64),
+        )));
+        transcript.append_point(&G1Projective::zero());
+
+        assert_eq!(
+            transcript.challenge_scalar::<Fr>(),
+            Fr::from_str(
+                "506690978930786183196971279906107790303620193615245151461797361824645654560"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            transcript.challenge_scalar::<Fr>(),
+            Fr::from_str(
+                "16408516768723033591597133654909176474524596794147461591018800835870921047976"
+            )
+            .unwrap()
+        );
+    }
+
+    fn commit_prove_verify<PCS: CommitmentScheme<PoseidonTranscript>>() {
+        let num_vars = 6;
+        let mut rng = test_rng();
+        let poly = DensePolynomial::new(
+            (0..1 << num_vars)
+                .map(|_| PCS::Field::random(&mut rng))
+                .collect::<Vec<_>>(),
+        );
+        let point: Vec<PCS::Field> = (0..num_vars)
+            .map(|_| PCS::Field::random(&mut rng))
+            .collect();
+        let eval = poly.evaluate(&point);
+
+        let setup = PCS::setup(&[CommitShape::new(1 << num_vars, BatchType::Big)]);
+        let verifier_setup = PCS::verifier_setup(&setup);
+        let commitment = PCS::commit(&poly, &setup);
+
+        let mut prover_transcript = PoseidonTranscript::new(b"test_transcript");
+        let proof = PCS::prove(&setup, &poly, &point, &mut prover_transcript);
+
+        let mut verifier_transcript = PoseidonTranscript::new(b"test_transcript");
+        verifier_transcript.compare_to(prover_transcript);
+        PCS::verify(
+            &proof,
+            &verifier_setup,
+            &mut verifier_transcript,
+            &point,
+            &eval,
+            &commitment,
+        )
+        .unwrap();
+
+        let mut verifier_transcript = PoseidonTranscript::new(b"test_transcript");
+        assert!(PCS::verify(
+            &proof,
+            &verifier_setup,
+            &mut verifier_transcript,
+            &point,
+            &(eval + PCS::Field::one()),
+            &commitment,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn poseidon_transcript_with_every_pcs() {
+        commit_prove_verify::<HyraxScheme<G1Projective, PoseidonTranscript>>();
+        commit_prove_verify::<Zeromorph<Bn254, PoseidonTranscript>>();
+        commit_prove_verify::<HyperKZG<Bn254, PoseidonTranscript>>();
+        commit_prove_verify::<Ligero<Fr, PoseidonTranscript>>();
+        commit_prove_verify::<Binius128Scheme<PoseidonTranscript>>();
+    }
+}
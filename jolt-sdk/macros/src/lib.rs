@@ -4,7 +4,14 @@ extern crate proc_macro;
 
 use core::panic;
 
-use common::{attributes::parse_attributes, rv_trace::MemoryLayout};
+use common::{
+    attributes::{
+        function_selector, parse_attributes, split_test_attributes, Allocator, IoCommitment,
+        TestAttributes,
+    },
+    constants::PRIVATE_OUTPUT_SYMBOL,
+    rv_trace::MemoryLayout,
+};
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
@@ -12,6 +19,8 @@ use std::sync::Once;
 use syn::{parse_macro_input, AttributeArgs, Ident, ItemFn, PatType, ReturnType, Type};
 
 static WASM_IMPORTS_INIT: Once = Once::new();
+static DISPATCH_RUNTIME_INIT: Once = Once::new();
+static DISPATCH_PREPROCESS_INIT: Once = Once::new();
 
 #[proc_macro_attribute]
 pub fn provable(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -35,6 +44,23 @@ pub fn provable(attr: TokenStream, item: TokenStream) -> TokenStream {
     token_stream
 }
 
+/// Marks a function as a guest test. The function takes no arguments and is run
+/// natively, in the emulator (whose output must match the native output), and, if
+/// `prove` is set, proven and verified using a mock commitment scheme. `max_cycles`
+/// bounds the emulated cycle count. Test functions are only compiled into the guest
+/// build made by their own test, so they never appear in the dispatch table or in the
+/// `main` of a regular guest build. Tests are only generated when the guest crate's
+/// `host` feature is enabled, which should forward to the SDK's `test-utils` feature.
+#[proc_macro_attribute]
+pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = parse_macro_input!(attr as AttributeArgs);
+    let func = parse_macro_input!(item as ItemFn);
+    let (test_attributes, attr) = split_test_attributes(attr);
+    let builder = MacroBuilder::new(attr, func);
+
+    builder.build_test(test_attributes)
+}
+
 struct MacroBuilder {
     attr: AttributeArgs,
     func: ItemFn,
@@ -64,16 +90,16 @@ impl MacroBuilder {
         let analyze_fn = self.make_analyze_function();
         let preprocess_fn = self.make_preprocess_func();
         let prove_fn = self.make_prove_func();
+        let io_digest_fn = self.make_io_digest_func();
+        let selector_const = self.make_selector_const();
 
-        let main_fn = if let Some(func) = self.get_func_selector() {
-            if *self.get_func_name() == func {
-                self.make_main_func()
-            } else {
-                quote! {}
-            }
-        } else {
-            self.make_main_func()
-        };
+        // The dispatch preprocessing covers every function, so it is only emitted once
+        let mut dispatch_preprocess_fn = quote! {};
+        DISPATCH_PREPROCESS_INIT.call_once(|| {
+            dispatch_preprocess_fn = self.make_dispatch_preprocess_func();
+        });
+
+        let main_fn = self.make_selected_main_func();
 
         quote! {
             #build_fn
@@ -81,11 +107,55 @@ impl MacroBuilder {
             #analyze_fn
             #preprocess_fn
             #prove_fn
+            #io_digest_fn
+            #selector_const
+            #dispatch_preprocess_fn
             #main_fn
         }
         .into()
     }
 
+    fn build_test(&self, test_attributes: TestAttributes) -> TokenStream {
+        if !self.func_args.is_empty() {
+            panic!("jolt::test functions cannot take arguments");
+        }
+
+        let test_fn = self.make_test_func(test_attributes);
+        let main_fn = self.make_test_main_func();
+
+        quote! {
+            #test_fn
+            #main_fn
+        }
+        .into()
+    }
+
+    fn make_selected_main_func(&self) -> TokenStream2 {
+        if self.is_dispatch() {
+            self.make_main_func()
+        } else if let Some(func) = self.get_func_selector() {
+            if *self.get_func_name() == func {
+                self.make_main_func()
+            } else {
+                quote! {}
+            }
+        } else {
+            self.make_main_func()
+        }
+    }
+
+    /// Test functions only get an entry point in the guest build made for them by their own
+    /// test (`JOLT_FUNC_NAME` set to the test's name). They are never registered in the
+    /// dispatch table, and never become the default `main` of a guest without a selector.
+    fn make_test_main_func(&self) -> TokenStream2 {
+        match self.get_func_selector() {
+            Some(func) if !self.is_dispatch() && *self.get_func_name() == func => {
+                self.make_main_func()
+            }
+            _ => quote! {},
+        }
+    }
+
     fn make_build_fn(&self) -> TokenStream2 {
         let fn_name = self.get_func_name();
         let build_fn_name = Ident::new(&format!("build_{}", fn_name), fn_name.span());
@@ -98,11 +168,38 @@ impl MacroBuilder {
         let prove_fn_name = Ident::new(&format!("prove_{}", fn_name), fn_name.span());
         let imports = self.make_imports();
 
+        // With an IO commitment, the public statement is the digest of the inputs
+        // and outputs, so the verifier must be told which digest to expect.
+        let (verify_ty, verify_closure) = if self.get_io_commitment().is_some() {
+            (
+                quote! { impl Fn(jolt::JoltHyperKZGProof, jolt::IoDigest) -> bool + Sync + Send },
+                quote! {
+                    let verify_closure = move |proof: jolt::JoltHyperKZGProof, io_digest: jolt::IoDigest| {
+                        let program = (*program_cp).clone();
+                        let preprocessing = (*verifier_preprocessing).clone();
+                        proof.proof.program_io.outputs == io_digest.to_bytes()
+                            && jolt::verify_with_panic_status(preprocessing, proof, false)
+                    };
+                },
+            )
+        } else {
+            (
+                quote! { impl Fn(jolt::JoltHyperKZGProof) -> bool + Sync + Send },
+                quote! {
+                    let verify_closure = move |proof: jolt::JoltHyperKZGProof| {
+                        let program = (*program_cp).clone();
+                        let preprocessing = (*verifier_preprocessing).clone();
+                        jolt::verify_with_panic_status(preprocessing, proof, false)
+                    };
+                },
+            )
+        };
+
         quote! {
             #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
             pub fn #build_fn_name() -> (
                 impl Fn(#(#input_types),*) -> #prove_output_ty + Sync + Send,
-                impl Fn(jolt::JoltHyperKZGProof) -> bool + Sync + Send
+                #verify_ty
             ) {
                 #imports
                 let (program, preprocessing) = #preprocess_fn_name();
@@ -110,7 +207,8 @@ impl MacroBuilder {
                 let preprocessing = std::sync::Arc::new(preprocessing);
 
                 let program_cp = program.clone();
-                let preprocessing_cp = preprocessing.clone();
+                let verifier_preprocessing =
+                    std::sync::Arc::new(jolt::JoltVerifierPreprocessing::from(&*preprocessing));
 
                 let prove_closure = move |#inputs| {
                     let program = (*program).clone();
@@ -118,12 +216,7 @@ impl MacroBuilder {
                     #prove_fn_name(program, preprocessing, #(#input_names),*)
                 };
 
-
-                let verify_closure = move |proof: jolt::JoltHyperKZGProof| {
-                    let program = (*program_cp).clone();
-                    let preprocessing = (*preprocessing_cp).clone();
-                    RV32IJoltVM::verify(preprocessing, proof.proof, proof.commitments, None).is_ok()
-                };
+                #verify_closure
 
                 (prove_closure, verify_closure)
             }
@@ -178,6 +271,19 @@ impl MacroBuilder {
     }
 
     fn make_preprocess_func(&self) -> TokenStream2 {
+        let fn_name = self.get_func_name();
+        let preprocess_fn_name = Ident::new(&format!("preprocess_{}", fn_name), fn_name.span());
+        self.make_preprocess_func_with(preprocess_fn_name, false)
+    }
+
+    /// Preprocesses a single ELF containing every provable function of the guest.
+    /// All functions must share this function's memory attributes.
+    fn make_dispatch_preprocess_func(&self) -> TokenStream2 {
+        let preprocess_fn_name = Ident::new("preprocess_dispatch", self.get_func_name().span());
+        self.make_preprocess_func_with(preprocess_fn_name, true)
+    }
+
+    fn make_preprocess_func_with(&self, preprocess_fn_name: Ident, dispatch: bool) -> TokenStream2 {
         let attributes = parse_attributes(&self.attr);
         let max_input_size = proc_macro2::Literal::u64_unsuffixed(attributes.max_input_size);
         let max_output_size = proc_macro2::Literal::u64_unsuffixed(attributes.max_output_size);
@@ -186,9 +292,17 @@ impl MacroBuilder {
         let imports = self.make_imports();
         let set_std = self.make_set_std();
 
-        let fn_name = self.get_func_name();
-        let fn_name_str = fn_name.to_string();
-        let preprocess_fn_name = Ident::new(&format!("preprocess_{}", fn_name), fn_name.span());
+        let set_func = if dispatch {
+            quote! {
+                program.set_dispatch(true);
+            }
+        } else {
+            let fn_name_str = self.get_func_name().to_string();
+            quote! {
+                program.set_func(#fn_name_str);
+            }
+        };
+        let private_inputs = self.get_io_commitment().is_some();
         quote! {
             #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
             pub fn #preprocess_fn_name() -> (
@@ -198,18 +312,18 @@ impl MacroBuilder {
                 #imports
 
                 let mut program = Program::new(#guest_name);
-                program.set_func(#fn_name_str);
+                #set_func
                 #set_std
                 #set_mem_size
-                let (bytecode, memory_init) = program.decode();
-                let memory_layout = MemoryLayout::new(#max_input_size, #max_output_size);
+                program.set_private_inputs(#private_inputs);
+                let mut memory_layout = MemoryLayout::new(#max_input_size, #max_output_size);
+                memory_layout.private_inputs = #private_inputs;
 
                 // TODO(moodlezoup): Feed in size parameters via macro
                 let preprocessing: JoltPreprocessing<4, jolt::F, jolt::PCS, jolt::ProofTranscript> =
-                    RV32IJoltVM::preprocess(
-                        bytecode,
+                    jolt::preprocess_cached(
+                        &mut program,
                         memory_layout,
-                        memory_init,
                         1 << 20,
                         1 << 20,
                         1 << 24
@@ -223,13 +337,36 @@ impl MacroBuilder {
     fn make_prove_func(&self) -> TokenStream2 {
         let prove_output_ty = self.get_prove_output_type();
 
-        let handle_return = match &self.func.sig.output {
-            ReturnType::Default => quote! {
-                let ret_val = ();
-            },
-            ReturnType::Type(_, ty) => quote! {
-                let ret_val = jolt::postcard::from_bytes::<#ty>(&output_bytes).unwrap();
-            },
+        let fn_name = self.get_func_name();
+        let handle_return = if let Some(io_commitment) = self.get_io_commitment() {
+            // The guest only outputs a digest of its return value, and hands the
+            // serialized return value back through its private outputs, so check
+            // the latter against the digest.
+            let hash_fn = Self::get_hash_fn(io_commitment);
+            let ret_ty = match &self.func.sig.output {
+                ReturnType::Default => quote! { () },
+                ReturnType::Type(_, ty) => quote! { #ty },
+            };
+            quote! {
+                let io_digest = jolt::IoDigest::from_bytes(&output_bytes)
+                    .ok_or(jolt::ProverError::MissingIoDigest)?;
+                let (ret_val, unused) =
+                    jolt::postcard::take_from_bytes::<#ret_ty>(&private_output_bytes)
+                        .map_err(|_| jolt::ProverError::IoDigestMismatch)?;
+                let ret_bytes = &private_output_bytes[..private_output_bytes.len() - unused.len()];
+                if #hash_fn(ret_bytes) != io_digest.output {
+                    return Err(jolt::ProverError::IoDigestMismatch.into());
+                }
+            }
+        } else {
+            match &self.func.sig.output {
+                ReturnType::Default => quote! {
+                    let ret_val = ();
+                },
+                ReturnType::Type(_, ty) => quote! {
+                    let ret_val = jolt::postcard::from_bytes::<#ty>(&output_bytes).unwrap();
+                },
+            }
         };
 
         let set_program_args = self.func_args.iter().map(|(name, _)| {
@@ -238,40 +375,230 @@ impl MacroBuilder {
             }
         });
 
-        let fn_name = self.get_func_name();
+        // Guests returning `Result` have their proven error surfaced as `Err`.
+        let wrap_proof = if self.get_result_types().is_some() {
+            quote! {
+                match ret_val {
+                    Ok(ret_val) => Ok((ret_val, proof)),
+                    Err(error) => Err(jolt::GuestError::Err { error, proof }),
+                }
+            }
+        } else {
+            quote! {
+                Ok((ret_val, proof))
+            }
+        };
+
+        let guest_panic = if parse_attributes(&self.attr).panic_message {
+            quote! { jolt::GuestPanic::from_outputs(&output_bytes) }
+        } else {
+            quote! { jolt::GuestPanic::default() }
+        };
+
+        let attributes = parse_attributes(&self.attr);
+        let max_input_size = attributes.max_input_size;
+        let max_output_size = attributes.max_output_size;
+        let selector = function_selector(&fn_name.to_string());
+
         let inputs = &self.func.sig.inputs;
         let imports = self.make_imports();
 
+        let input_names = self.func_args.iter().map(|(name, _)| name);
         let prove_fn_name = syn::Ident::new(&format!("prove_{}", fn_name), fn_name.span());
+        let prove_with_config_fn_name =
+            syn::Ident::new(&format!("prove_{}_with_config", fn_name), fn_name.span());
         quote! {
             #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
             pub fn #prove_fn_name(
+                program: jolt::host::Program,
+                preprocessing: jolt::JoltPreprocessing<4, jolt::F, jolt::PCS, jolt::ProofTranscript>,
+                #inputs
+            ) -> #prove_output_ty {
+                #prove_with_config_fn_name(
+                    program,
+                    preprocessing,
+                    &jolt::ProverConfig::default(),
+                    #(#input_names),*
+                )
+            }
+
+            /// Like the `prove_*` function, but with an explicit prover config, e.g. to
+            /// forward progress reports to a UI or job tracker, or to make the proof
+            /// cancellable.
+            #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
+            pub fn #prove_with_config_fn_name(
                 mut program: jolt::host::Program,
                 preprocessing: jolt::JoltPreprocessing<4, jolt::F, jolt::PCS, jolt::ProofTranscript>,
+                config: &jolt::ProverConfig,
                 #inputs
             ) -> #prove_output_ty {
                 #imports
 
+                if program.is_dispatch() {
+                    assert!(
+                        preprocessing.memory_layout.max_input_size == #max_input_size
+                            && preprocessing.memory_layout.max_output_size == #max_output_size,
+                        "all functions of a dispatch program must use the same max_input_size and max_output_size"
+                    );
+                    program.set_input(&#selector);
+                }
                 #(#set_program_args;)*
 
                 let (io_device, trace) = program.trace();
 
                 let output_bytes = io_device.outputs.clone();
+                let private_output_bytes = io_device.private_outputs.clone();
+
+                let panicked = io_device.panic;
 
                 let (jolt_proof, jolt_commitments, _) = RV32IJoltVM::prove(
                     io_device,
                     trace,
                     preprocessing,
-                );
-
-                #handle_return
+                    config,
+                )?;
 
                 let proof = jolt::JoltHyperKZGProof {
                     proof: jolt_proof,
                     commitments: jolt_commitments,
                 };
 
-                (ret_val, proof)
+                if panicked {
+                    return Err(jolt::GuestError::Panic {
+                        panic: #guest_panic,
+                        proof,
+                    });
+                }
+
+                #handle_return
+
+                #wrap_proof
+            }
+        }
+    }
+
+    fn make_test_func(&self, test_attributes: TestAttributes) -> TokenStream2 {
+        let set_mem_size = self.make_set_linker_parameters();
+        let guest_name = self.get_guest_name();
+        let set_std = self.make_set_std();
+
+        let fn_name = self.get_func_name();
+        let fn_name_str = fn_name.to_string();
+        let block = &self.func.block;
+        let output_ty = match &self.func.sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) => quote! { #ty },
+        };
+
+        let check_cycles = match test_attributes.max_cycles {
+            Some(max_cycles) => quote! {
+                let cycles = trace.len();
+                assert!(
+                    cycles <= #max_cycles as usize,
+                    "{} took {} cycles, more than the maximum of {}",
+                    #fn_name_str,
+                    cycles,
+                    #max_cycles
+                );
+            },
+            None => quote! {},
+        };
+
+        let prove = if test_attributes.prove {
+            quote! {
+                type MockPCS = jolt::MockCommitScheme<jolt::F, jolt::ProofTranscript>;
+                let (bytecode, memory_init) = program.decode();
+                let preprocessing: jolt::JoltPreprocessing<4, jolt::F, MockPCS, jolt::ProofTranscript> =
+                    jolt::RV32IJoltVM::insecure_preprocess(
+                        bytecode,
+                        io_device.memory_layout.clone(),
+                        memory_init,
+                        1 << 20,
+                        1 << 20,
+                        1 << 24
+                    );
+                let verifier_preprocessing = jolt::JoltVerifierPreprocessing::from(&preprocessing);
+                let (proof, commitments, _) = <jolt::RV32IJoltVM as jolt::Jolt<
+                    jolt::F,
+                    MockPCS,
+                    4,
+                    { 1 << 16 },
+                    jolt::ProofTranscript,
+                >>::prove(io_device, trace, preprocessing, &jolt::ProverConfig::default())
+                .unwrap();
+                let verification_result =
+                    jolt::RV32IJoltVM::verify(verifier_preprocessing, proof, commitments, None);
+                assert!(
+                    verification_result.is_ok(),
+                    "{}: verification failed: {:?}",
+                    #fn_name_str,
+                    verification_result.err()
+                );
+            }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #[cfg(all(test, feature = "host", not(target_arch = "wasm32"), not(feature = "guest")))]
+            #[test]
+            fn #fn_name() {
+                use jolt::Jolt;
+
+                let native_output: #output_ty = (|| -> #output_ty #block)();
+
+                let mut program = jolt::host::Program::new(#guest_name);
+                program.set_func(#fn_name_str);
+                #set_std
+                #set_mem_size
+                let (io_device, trace) = program.trace();
+
+                assert!(!io_device.panic, "{} panicked in the emulator", #fn_name_str);
+                let native_output = jolt::postcard::to_stdvec(&native_output).unwrap();
+                assert_eq!(
+                    io_device.outputs, native_output,
+                    "{}: native and emulated outputs differ",
+                    #fn_name_str
+                );
+
+                #check_cycles
+
+                #prove
+            }
+        }
+    }
+
+    fn make_io_digest_func(&self) -> TokenStream2 {
+        let Some(io_commitment) = self.get_io_commitment() else {
+            return quote! {};
+        };
+        let hash_fn = Self::get_hash_fn(io_commitment);
+
+        let fn_name = self.get_func_name();
+        let io_digest_fn_name = Ident::new(&format!("io_digest_{}", fn_name), fn_name.span());
+        let inputs = self.func.sig.inputs.iter();
+        let output_ty = match &self.func.sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) => quote! { #ty },
+        };
+        let serialize_inputs = self.func_args.iter().map(|(name, _)| {
+            quote! {
+                input_bytes.extend(jolt::postcard::to_stdvec(&#name).unwrap());
+            }
+        });
+
+        quote! {
+            /// Computes the digest a proof of this function is expected to output
+            /// for the given inputs and return value.
+            #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
+            pub fn #io_digest_fn_name(#(#inputs,)* output: &#output_ty) -> jolt::IoDigest {
+                let mut input_bytes: Vec<u8> = Vec::new();
+                #(#serialize_inputs;)*
+                let output_bytes = jolt::postcard::to_stdvec(output).unwrap();
+                jolt::IoDigest {
+                    input: #hash_fn(&input_bytes),
+                    output: #hash_fn(&output_bytes),
+                }
             }
         }
     }
@@ -307,21 +634,133 @@ impl MacroBuilder {
         let block = &self.func.block;
         let block = quote! {let to_return = (|| -> _ { #block })();};
 
-        let handle_return = match &self.func.sig.output {
-            ReturnType::Default => quote! {},
-            ReturnType::Type(_, ty) => quote! {
+        let handle_return = if let Some(io_commitment) = attributes.io_commitment {
+            // Instead of the inputs and outputs themselves, publish a digest of
+            // the consumed input bytes and of the serialized return value.
+            assert!(
+                max_output_len >= 64,
+                "io_commitment requires max_output_size of at least 64 bytes"
+            );
+            let hash_fn = Self::get_hash_fn(io_commitment);
+            let private_output_symbol =
+                Ident::new(PRIVATE_OUTPUT_SYMBOL, self.get_func_name().span());
+            let serialize_output = match &self.func.sig.output {
+                ReturnType::Default => quote! {
+                    let output_bytes: &[u8] = &[];
+                },
+                // The tracer hands the contents of this buffer back to the host,
+                // which checks them against the digest.
+                ReturnType::Type(_, ty) => quote! {
+                    #[no_mangle]
+                    static mut #private_output_symbol: [u8; #max_output_len] = [0; #max_output_len];
+                    let output_buffer =
+                        unsafe { &mut *core::ptr::addr_of_mut!(#private_output_symbol) };
+                    let output_bytes: &[u8] =
+                        jolt::postcard::to_slice::<#ty>(&to_return, output_buffer).unwrap();
+                },
+            };
+            quote! {
+                let consumed = #max_input_len - input_slice.len();
+                let input_bytes = unsafe {
+                    core::slice::from_raw_parts(input_ptr, consumed)
+                };
+                #serialize_output
+                let io_digest = jolt::IoDigest {
+                    input: #hash_fn(input_bytes),
+                    output: #hash_fn(output_bytes),
+                };
+
                 let output_ptr = #output_start as *mut u8;
                 let output_slice = unsafe {
-                    core::slice::from_raw_parts_mut(output_ptr, #max_output_len)
+                    core::slice::from_raw_parts_mut(output_ptr, jolt::IoDigest::SIZE)
                 };
-
-                jolt::postcard::to_slice::<#ty>(&to_return, output_slice).unwrap();
-            },
+                output_slice.copy_from_slice(&io_digest.to_bytes());
+            }
+        } else {
+            match &self.func.sig.output {
+                ReturnType::Default => quote! {},
+                ReturnType::Type(_, ty) => quote! {
+                    let output_ptr = #output_start as *mut u8;
+                    let output_slice = unsafe {
+                        core::slice::from_raw_parts_mut(output_ptr, #max_output_len)
+                    };
+
+                    jolt::postcard::to_slice::<#ty>(&to_return, output_slice).unwrap();
+                },
+            }
         };
 
-        let panic_fn = self.make_panic(memory_layout.panic);
+        let panic_fn = self.make_panic(&memory_layout);
         let declare_alloc = self.make_allocator();
 
+        if self.is_dispatch() {
+            assert!(
+                attributes.io_commitment.is_none(),
+                "io_commitment is not supported in dispatch builds"
+            );
+
+            let fn_name = self.get_func_name();
+            let entry_fn_name = Ident::new(&format!("jolt_entry_{}", fn_name), fn_name.span());
+            let entry_static_name = Ident::new(
+                &format!("JOLT_DISPATCH_{}", fn_name.to_string().to_uppercase()),
+                fn_name.span(),
+            );
+            let selector = function_selector(&fn_name.to_string());
+
+            // The runtime (entry point, allocator, panic handler) is shared by all functions
+            let mut runtime = quote! {};
+            DISPATCH_RUNTIME_INIT.call_once(|| {
+                runtime = quote! {
+                    #[cfg(feature = "guest")]
+                    use core::arch::global_asm;
+
+                    #[cfg(feature = "guest")]
+                    global_asm!("\
+                        .global _start\n\
+                        .extern _STACK_PTR\n\
+                        .section .text.boot\n\
+                        _start:	la sp, _STACK_PTR\n\
+                            jal main\n\
+                            j .\n\
+                    ");
+
+                    #declare_alloc
+
+                    #[cfg(feature = "guest")]
+                    #[no_mangle]
+                    pub extern "C" fn main() {
+                        #get_input_slice
+                        jolt::dispatch(input_slice);
+                        unsafe {
+                            core::ptr::write_volatile(#termination_bit as *mut u8, 1);
+                        }
+                    }
+
+                    #panic_fn
+                };
+            });
+
+            return quote! {
+                #runtime
+
+                #[cfg(feature = "guest")]
+                fn #entry_fn_name(input_slice: &'static [u8]) {
+                    #(#args_fetch;)*
+                    #check_input_len
+                    #block
+                    #handle_return
+                }
+
+                #[cfg(feature = "guest")]
+                #[used]
+                #[link_section = ".jolt_dispatch"]
+                static #entry_static_name: jolt::DispatchEntry = jolt::DispatchEntry {
+                    selector: #selector,
+                    entry: #entry_fn_name,
+                };
+            };
+        }
+
         quote! {
             #[cfg(feature = "guest")]
             use core::arch::global_asm;
@@ -356,7 +795,28 @@ impl MacroBuilder {
         }
     }
 
-    fn make_panic(&self, panic_address: u64) -> TokenStream2 {
+    fn make_panic(&self, memory_layout: &MemoryLayout) -> TokenStream2 {
+        let panic_address = memory_layout.panic;
+        let output_start = memory_layout.output_start;
+        let max_output_len = memory_layout.max_output_size as usize;
+
+        // The message must be written before the panic bit is set, since the
+        // tracer stops once the guest spins after setting it.
+        let write_message = if parse_attributes(&self.attr).panic_message {
+            quote! {
+                {
+                    use core::fmt::Write;
+                    let output_slice = unsafe {
+                        core::slice::from_raw_parts_mut(#output_start as *mut u8, #max_output_len)
+                    };
+                    let mut writer = jolt::PanicMessageWriter::new(output_slice);
+                    let _ = write!(writer, "{}", info.message());
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         if self.std {
             quote! {
                 #[cfg(feature = "guest")]
@@ -376,7 +836,9 @@ impl MacroBuilder {
 
                 #[cfg(feature = "guest")]
                 #[panic_handler]
-                fn panic(_info: &PanicInfo) -> ! {
+                #[allow(unused_variables)]
+                fn panic(info: &PanicInfo) -> ! {
+                    #write_message
                     unsafe {
                         core::ptr::write_volatile(#panic_address as *mut u8, 1);
                     }
@@ -388,14 +850,18 @@ impl MacroBuilder {
     }
 
     fn make_allocator(&self) -> TokenStream2 {
-        if self.std {
-            quote! {}
-        } else {
-            quote! {
+        match parse_attributes(&self.attr).allocator {
+            Allocator::FreeList => quote! {
+                #[cfg(feature = "guest")]
+                #[global_allocator]
+                static ALLOCATOR: jolt::FreeListAllocator = jolt::FreeListAllocator::new();
+            },
+            Allocator::Bump if self.std => quote! {},
+            Allocator::Bump => quote! {
                 #[cfg(feature = "guest")]
                 #[global_allocator]
                 static ALLOCATOR: jolt::BumpAllocator = jolt::BumpAllocator;
-            }
+            },
         }
     }
 
@@ -424,31 +890,37 @@ impl MacroBuilder {
 
     fn make_wasm_utilities(&self) -> TokenStream2 {
         quote! {
-            #[cfg(target_arch = "wasm32")]
+            #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
             use wasm_bindgen::prelude::*;
-            #[cfg(target_arch = "wasm32")]
-            use std::vec::Vec;
-            #[cfg(target_arch = "wasm32")]
-            use rmp_serde::Deserializer;
-            #[cfg(target_arch = "wasm32")]
-            use serde::{Deserialize, Serialize};
 
+            /// Why a proof was rejected by a `verify_*` wasm function.
             #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
-            use jolt::host::ELFInstruction;
+            #[wasm_bindgen]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            pub enum VerifyErrorKind {
+                InvalidVerifierKey,
+                InvalidProof,
+                GuestPanicked,
+                VerificationFailed,
+            }
 
+            /// Thrown by the `verify_*` wasm functions when a proof is rejected.
             #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
-            #[derive(Serialize, Deserialize)]
-            struct DecodedData {
-                bytecode: Vec<ELFInstruction>,
-                memory_init: Vec<(u64, u8)>,
+            #[wasm_bindgen(getter_with_clone)]
+            #[derive(Clone, Debug)]
+            pub struct VerifyError {
+                pub kind: VerifyErrorKind,
+                pub message: String,
             }
 
-            #[cfg(target_arch = "wasm32")]
-            fn deserialize_from_bin<'a, T: Deserialize<'a>>(
-                data: &'a [u8],
-            ) -> Result<T, rmp_serde::decode::Error> {
-                let mut de = Deserializer::new(data);
-                Deserialize::deserialize(&mut de)
+            #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
+            impl VerifyError {
+                fn new(kind: VerifyErrorKind, error: impl std::fmt::Display) -> Self {
+                    Self {
+                        kind,
+                        message: error.to_string(),
+                    }
+                }
             }
         }
     }
@@ -467,6 +939,12 @@ impl MacroBuilder {
             program.set_stack_size(#value);
         });
 
+        if let Some(value) = attributes.heap_size {
+            code.push(quote! {
+                program.set_heap_size(#value);
+            });
+        }
+
         let value = attributes.max_input_size;
         code.push(quote! {
             program.set_max_input_size(#value);
@@ -494,17 +972,49 @@ impl MacroBuilder {
         }
     }
 
+    /// A guest error or panic is a provable outcome too, so the prover returns the
+    /// proof of it inside the `GuestError`.
     fn get_prove_output_type(&self) -> TokenStream2 {
+        if let Some((ok_ty, err_ty)) = self.get_result_types() {
+            return quote! {
+                Result<(#ok_ty, jolt::JoltHyperKZGProof), jolt::GuestError<#err_ty>>
+            };
+        }
         match &self.func.sig.output {
             ReturnType::Default => quote! {
-                ((), jolt::JoltHyperKZGProof)
+                Result<((), jolt::JoltHyperKZGProof), jolt::GuestError>
             },
             ReturnType::Type(_, ty) => quote! {
-                (#ty, jolt::JoltHyperKZGProof)
+                Result<(#ty, jolt::JoltHyperKZGProof), jolt::GuestError>
             },
         }
     }
 
+    /// Returns the `T` and `E` of a function returning `Result<T, E>`.
+    fn get_result_types(&self) -> Option<(Type, Type)> {
+        let ReturnType::Type(_, ty) = &self.func.sig.output else {
+            return None;
+        };
+        let Type::Path(type_path) = ty.as_ref() else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+        if segment.ident != "Result" {
+            return None;
+        }
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        let mut types = args.args.iter().filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None,
+        });
+        match (types.next(), types.next(), types.next()) {
+            (Some(ok_ty), Some(err_ty), None) => Some((ok_ty, err_ty)),
+            _ => None,
+        }
+    }
+
     fn get_func_args(func: &ItemFn) -> Vec<(Ident, Box<Type>)> {
         let mut args = Vec::new();
         for arg in &func.sig.inputs {
@@ -522,6 +1032,35 @@ impl MacroBuilder {
         args
     }
 
+    fn get_io_commitment(&self) -> Option<IoCommitment> {
+        parse_attributes(&self.attr).io_commitment
+    }
+
+    fn get_hash_fn(io_commitment: IoCommitment) -> TokenStream2 {
+        match io_commitment {
+            IoCommitment::Keccak256 => quote! { jolt::io_commitment::keccak256 },
+            IoCommitment::Sha256 => quote! { jolt::io_commitment::sha256 },
+        }
+    }
+
+    fn make_selector_const(&self) -> TokenStream2 {
+        let fn_name = self.get_func_name();
+        let selector_name = Ident::new(
+            &format!("{}_SELECTOR", fn_name.to_string().to_uppercase()),
+            fn_name.span(),
+        );
+        let selector = function_selector(&fn_name.to_string());
+
+        quote! {
+            /// Selects this function in a guest built as a single dispatch ELF.
+            pub const #selector_name: u32 = #selector;
+        }
+    }
+
+    fn is_dispatch(&self) -> bool {
+        proc_macro::tracked_env::var("JOLT_DISPATCH").is_ok()
+    }
+
     fn get_func_name(&self) -> &Ident {
         &self.func.sig.ident
     }
@@ -543,24 +1082,32 @@ impl MacroBuilder {
         let verify_wasm_fn_name = Ident::new(&format!("verify_{}", fn_name), fn_name.span());
 
         quote! {
-            #[wasm_bindgen]
             #[cfg(all(target_arch = "wasm32", not(feature = "guest")))]
-            pub fn #verify_wasm_fn_name(preprocessing_data: &[u8], proof_bytes: &[u8]) -> bool {
-                use jolt::{Jolt, JoltHyperKZGProof, RV32IJoltVM, ProofTranscript};
-
-                let decoded_preprocessing_data: DecodedData = deserialize_from_bin(preprocessing_data).unwrap();
-                let proof = JoltHyperKZGProof::deserialize_from_bytes(proof_bytes).unwrap();
-
-                let preprocessing = RV32IJoltVM::preprocess(
-                    decoded_preprocessing_data.bytecode,
-                    decoded_preprocessing_data.memory_init,
-                    1 << 20,
-                    1 << 20,
-                    1 << 24,
-                );
+            #[wasm_bindgen]
+            pub fn #verify_wasm_fn_name(
+                verifier_key: &[u8],
+                proof_bytes: &[u8],
+            ) -> Result<(), VerifyError> {
+                use jolt::{
+                    Jolt, JoltHyperKZGProof, JoltHyperKZGVerifierPreprocessing, RV32IJoltVM,
+                    Serializable,
+                };
+
+                let preprocessing =
+                    JoltHyperKZGVerifierPreprocessing::deserialize_from_bytes(verifier_key)
+                    .map_err(|e| VerifyError::new(VerifyErrorKind::InvalidVerifierKey, e))?;
+                let proof = JoltHyperKZGProof::deserialize_from_bytes(proof_bytes)
+                    .map_err(|e| VerifyError::new(VerifyErrorKind::InvalidProof, e))?;
+
+                if proof.proof.program_io.panic {
+                    return Err(VerifyError::new(
+                        VerifyErrorKind::GuestPanicked,
+                        "the proof attests to a guest panic",
+                    ));
+                }
 
-                let result = RV32IJoltVM::verify(preprocessing, proof.proof, proof.commitments);
-                result.is_ok()
+                RV32IJoltVM::verify(preprocessing, proof.proof, proof.commitments, None)
+                    .map_err(|e| VerifyError::new(VerifyErrorKind::VerificationFailed, e))
             }
         }
     }
@@ -1,4 +1,8 @@
-use core::alloc::{GlobalAlloc, Layout};
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::UnsafeCell,
+    ptr::null_mut,
+};
 
 pub struct BumpAllocator;
 
@@ -37,3 +41,231 @@ pub unsafe extern "C" fn sys_alloc(size: usize, align: usize) -> *mut u8 {
 fn align_up(addr: usize, align: usize) -> usize {
     (addr + align - 1) & !(align - 1)
 }
+
+extern "C" {
+    static _HEAP_END: u8;
+}
+
+const MIN_BLOCK_SIZE: usize = 16;
+const NUM_SIZE_CLASSES: usize = (usize::BITS - MIN_BLOCK_SIZE.trailing_zeros()) as usize;
+
+/// An allocator that reuses freed memory. Allocations are rounded up to a power-of-two
+/// size class, and freed blocks are kept in a free list per size class. Fresh blocks
+/// are carved from the heap, which is bounded by the `heap_size` attribute (or the end
+/// of guest memory). Once the heap is exhausted, allocations return null, which the
+/// guest's allocation error handler turns into a panic.
+pub struct FreeListAllocator {
+    state: UnsafeCell<FreeListState>,
+}
+
+struct FreeListState {
+    /// Start of the unallocated portion of the heap; 0 until the first allocation.
+    next: usize,
+    /// Head of each size class's free list; each free block stores the address of the
+    /// next free block in its first word.
+    free_lists: [usize; NUM_SIZE_CLASSES],
+}
+
+// Guests are single-threaded.
+unsafe impl Sync for FreeListAllocator {}
+
+impl FreeListAllocator {
+    pub const fn new() -> Self {
+        Self {
+            state: UnsafeCell::new(FreeListState {
+                next: 0,
+                free_lists: [0; NUM_SIZE_CLASSES],
+            }),
+        }
+    }
+}
+
+impl FreeListState {
+    fn size_class(layout: Layout) -> usize {
+        let size = layout.size().max(layout.align()).max(MIN_BLOCK_SIZE);
+        (size.next_power_of_two().trailing_zeros() - MIN_BLOCK_SIZE.trailing_zeros()) as usize
+    }
+
+    /// Allocates a block for `layout`, reusing a freed block of the same size class if
+    /// possible and carving a fresh one from `heap_start..heap_end` otherwise. Returns
+    /// null if the heap is exhausted.
+    unsafe fn alloc(&mut self, layout: Layout, heap_start: usize, heap_end: usize) -> *mut u8 {
+        let class = Self::size_class(layout);
+
+        let head = self.free_lists[class];
+        if head != 0 && head % layout.align() == 0 {
+            self.free_lists[class] = *(head as *const usize);
+            return head as *mut u8;
+        }
+
+        if self.next == 0 {
+            self.next = heap_start;
+        }
+
+        let block_size = MIN_BLOCK_SIZE << class;
+        let start = align_up(self.next, layout.align().max(MIN_BLOCK_SIZE));
+        match start.checked_add(block_size) {
+            Some(end) if end <= heap_end => {
+                self.next = end;
+                start as *mut u8
+            }
+            _ => null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+        let class = Self::size_class(layout);
+        *(ptr as *mut usize) = self.free_lists[class];
+        self.free_lists[class] = ptr as usize;
+    }
+}
+
+impl Default for FreeListAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for FreeListAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let heap_start = (&_HEAP_PTR) as *const u8 as usize;
+        let heap_end = (&_HEAP_END) as *const u8 as usize;
+        (*self.state.get()).alloc(layout, heap_start, heap_end)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        (*self.state.get()).dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        if FreeListState::size_class(new_layout) == FreeListState::size_class(layout) {
+            return ptr;
+        }
+
+        let new_ptr = self.alloc(new_layout);
+        if new_ptr.is_null() {
+            return new_ptr;
+        }
+        core::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+        self.dealloc(ptr, layout);
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::{vec, vec::Vec};
+
+    const HEAP_SIZE: usize = 1 << 12;
+
+    /// Returns an empty allocator state and the bounds of a fresh heap backed by `heap`.
+    /// The heap starts 16 bytes past a 32-byte boundary, so that its first block is
+    /// aligned to `MIN_BLOCK_SIZE` but nothing larger.
+    fn new_heap(heap: &mut Vec<u64>) -> (FreeListState, usize, usize) {
+        *heap = vec![0; (HEAP_SIZE + 48) / 8];
+        let heap_start = align_up(heap.as_mut_ptr() as usize, 32) + MIN_BLOCK_SIZE;
+        let state = FreeListState {
+            next: 0,
+            free_lists: [0; NUM_SIZE_CLASSES],
+        };
+        (state, heap_start, heap_start + HEAP_SIZE)
+    }
+
+    #[test]
+    fn allocations_are_aligned_and_disjoint() {
+        let mut heap = Vec::new();
+        let (mut state, heap_start, heap_end) = new_heap(&mut heap);
+
+        let mut blocks: Vec<(usize, usize)> = Vec::new();
+        for (size, align) in [
+            (1, 1),
+            (3, 2),
+            (24, 8),
+            (16, 64),
+            (100, 4),
+            (8, 256),
+            (1, 1),
+        ] {
+            let layout = Layout::from_size_align(size, align).unwrap();
+            let ptr = unsafe { state.alloc(layout, heap_start, heap_end) } as usize;
+            assert_ne!(ptr, 0);
+            assert_eq!(
+                ptr % align,
+                0,
+                "{size}-byte allocation is not {align}-aligned"
+            );
+            assert!(ptr >= heap_start && ptr + size <= heap_end);
+            for &(start, end) in blocks.iter() {
+                assert!(ptr + size <= start || ptr >= end, "allocations overlap");
+            }
+            blocks.push((ptr, ptr + size));
+        }
+    }
+
+    #[test]
+    fn freed_blocks_are_reused() {
+        let mut heap = Vec::new();
+        let (mut state, heap_start, heap_end) = new_heap(&mut heap);
+
+        let layout = Layout::from_size_align(40, 8).unwrap();
+        let a = unsafe { state.alloc(layout, heap_start, heap_end) };
+        let b = unsafe { state.alloc(layout, heap_start, heap_end) };
+        unsafe {
+            state.dealloc(a, layout);
+            state.dealloc(b, layout);
+        }
+
+        // Blocks of the same size class are handed out again, most recently freed first
+        let smaller = Layout::from_size_align(33, 4).unwrap();
+        assert_eq!(unsafe { state.alloc(smaller, heap_start, heap_end) }, b);
+        assert_eq!(unsafe { state.alloc(layout, heap_start, heap_end) }, a);
+
+        // Blocks of other size classes are carved from the heap instead
+        let c =
+            unsafe { state.alloc(Layout::from_size_align(8, 8).unwrap(), heap_start, heap_end) };
+        assert!(c != a && c != b);
+    }
+
+    #[test]
+    fn freed_block_is_not_reused_if_misaligned() {
+        let mut heap = Vec::new();
+        let (mut state, heap_start, heap_end) = new_heap(&mut heap);
+
+        let layout = Layout::from_size_align(32, 16).unwrap();
+        let a = unsafe { state.alloc(layout, heap_start, heap_end) };
+        assert_eq!(a as usize, heap_start);
+        unsafe { state.dealloc(a, layout) };
+
+        // `a` is in the right size class but not 32-byte aligned
+        let aligned = Layout::from_size_align(32, 32).unwrap();
+        let b = unsafe { state.alloc(aligned, heap_start, heap_end) };
+        assert_ne!(a, b);
+        assert_eq!(b as usize % 32, 0);
+    }
+
+    #[test]
+    fn exhausted_heap_returns_null() {
+        let mut heap = Vec::new();
+        let (mut state, heap_start, heap_end) = new_heap(&mut heap);
+
+        let layout = Layout::from_size_align(HEAP_SIZE / 4, 8).unwrap();
+        let blocks: Vec<_> = (0..4)
+            .map(|_| unsafe { state.alloc(layout, heap_start, heap_end) })
+            .collect();
+        assert!(blocks.iter().all(|ptr| !ptr.is_null()));
+        assert!(unsafe { state.alloc(layout, heap_start, heap_end) }.is_null());
+        let too_large = Layout::from_size_align(HEAP_SIZE + 1, 8).unwrap();
+        assert!(unsafe { state.alloc(too_large, heap_start, heap_end) }.is_null());
+
+        // Freeing a block makes room again
+        unsafe { state.dealloc(blocks[2], layout) };
+        assert_eq!(
+            unsafe { state.alloc(layout, heap_start, heap_end) },
+            blocks[2]
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! Dispatch table for guests whose provable functions are built into a single ELF.
+//! Each `#[provable]` function registers an entry in the `.jolt_dispatch` linker
+//! section, and the guest's `main` runs the entry whose selector precedes the inputs.
+
+/// An entry point in the dispatch table.
+#[repr(C)]
+pub struct DispatchEntry {
+    pub selector: u32,
+    pub entry: fn(&'static [u8]),
+}
+
+extern "C" {
+    static __jolt_dispatch_start: u8;
+    static __jolt_dispatch_end: u8;
+}
+
+/// Reads the function selector from the start of `input` and runs the corresponding
+/// entry point on the remaining input. Panics if no entry point matches.
+pub fn dispatch(input: &'static [u8]) {
+    let (selector, input) = postcard::take_from_bytes::<u32>(input).unwrap();
+
+    let entries = unsafe {
+        let start = core::ptr::addr_of!(__jolt_dispatch_start);
+        let end = core::ptr::addr_of!(__jolt_dispatch_end);
+        let len = end.offset_from(start) as usize / core::mem::size_of::<DispatchEntry>();
+        core::slice::from_raw_parts(start as *const DispatchEntry, len)
+    };
+
+    match entries.iter().find(|entry| entry.selector == selector) {
+        Some(entry) => (entry.entry)(input),
+        None => panic!("unknown function selector"),
+    }
+}
@@ -1,4 +1,3 @@
-pub use ark_bn254::{Fr as F, G1Projective as G};
 pub use ark_ec::CurveGroup;
 pub use jolt_core::{field::JoltField, poly::commitment::hyperkzg::HyperKZG};
 
@@ -10,9 +9,71 @@ pub use jolt_core::host;
 pub use jolt_core::jolt::instruction;
 pub use jolt_core::jolt::vm::{
     bytecode::BytecodeRow,
-    rv32i_vm::{
-        JoltHyperKZGProof, ProofTranscript, RV32IJoltProof, RV32IJoltVM, Serializable, PCS, RV32I,
-    },
-    Jolt, JoltCommitments, JoltPreprocessing, JoltProof,
+    prover_config::{ProverConfig, ProverProgress, ProverStage},
+    rv32i_vm::{ProofTranscript, RV32IJoltProof, RV32IJoltVM, Serializable, RV32I},
+    Jolt, JoltCommitments, JoltPreprocessing, JoltProof, JoltVerifierPreprocessing,
 };
+#[cfg(feature = "test-utils")]
+pub use jolt_core::poly::commitment::mock::MockCommitScheme;
+pub use jolt_core::subprotocols::grand_product::GrandProductBackend;
+pub use jolt_core::utils::{cancellation::CancellationToken, errors::ProverError};
 pub use tracer;
+
+/// Proofs over BN254, which the `#[jolt::provable]` functions produce. The crate root
+/// re-exports these types.
+pub mod bn254 {
+    use ark_bn254::Bn254;
+    use jolt_core::jolt::vm::rv32i_vm::{self, ProofTranscript};
+    use jolt_core::poly::commitment::hyperkzg::HyperKZG;
+
+    pub use ark_bn254::{Fr as F, G1Projective as G};
+    pub type PCS = HyperKZG<Bn254, ProofTranscript>;
+    pub type JoltHyperKZGProof = rv32i_vm::JoltHyperKZGProof<Bn254>;
+    pub type JoltHyperKZGVerifierPreprocessing = rv32i_vm::JoltHyperKZGVerifierPreprocessing<Bn254>;
+}
+
+/// Proofs over BLS12-381, for deployments tied to BLS12-381 ceremonies or to chains that
+/// only have BLS12-381 precompiles. These are produced with `RV32IJoltVM` directly.
+pub mod bls12_381 {
+    use ark_bls12_381::Bls12_381;
+    use jolt_core::jolt::vm::rv32i_vm::{self, ProofTranscript};
+    use jolt_core::poly::commitment::hyperkzg::HyperKZG;
+
+    pub use ark_bls12_381::{Fr as F, G1Projective as G};
+    pub type PCS = HyperKZG<Bls12_381, ProofTranscript>;
+    pub type JoltHyperKZGProof = rv32i_vm::JoltHyperKZGProof<Bls12_381>;
+    pub type JoltHyperKZGVerifierPreprocessing =
+        rv32i_vm::JoltHyperKZGVerifierPreprocessing<Bls12_381>;
+}
+
+pub use bn254::{JoltHyperKZGProof, JoltHyperKZGVerifierPreprocessing, F, G, PCS};
+
+/// Verifies `proof`, additionally checking that the guest panicked if and only if
+/// `expect_panic` is set. Without this check, a proof that the guest panicked would
+/// be accepted as a proof of its normal execution.
+pub fn verify_with_panic_status(
+    preprocessing: JoltHyperKZGVerifierPreprocessing,
+    proof: JoltHyperKZGProof,
+    expect_panic: bool,
+) -> bool {
+    proof.proof.program_io.panic == expect_panic
+        && RV32IJoltVM::verify(preprocessing, proof.proof, proof.commitments, None).is_ok()
+}
+
+/// Returns the selector of the function a proof of a dispatch program attests to.
+pub fn proof_selector(proof: &JoltHyperKZGProof) -> Option<u32> {
+    postcard::take_from_bytes::<u32>(&proof.proof.program_io.inputs)
+        .ok()
+        .map(|(selector, _)| selector)
+}
+
+/// Verifies a proof of a dispatch program, checking that it attests to the normal
+/// execution of the function identified by `selector`.
+pub fn verify_dispatch(
+    preprocessing: JoltHyperKZGVerifierPreprocessing,
+    proof: JoltHyperKZGProof,
+    selector: u32,
+) -> bool {
+    proof_selector(&proof) == Some(selector)
+        && verify_with_panic_status(preprocessing, proof, false)
+}
@@ -0,0 +1,63 @@
+//! Digests used to commit to a guest's inputs and outputs when it is built with
+//! `#[provable(io_commitment = "keccak")]` (or `"sha256"`). In that mode the inputs
+//! are excluded from the public statement; instead the guest hashes its serialized
+//! inputs and outputs and writes an `IoDigest` to the output region.
+
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+
+/// The public statement of a program built with `io_commitment`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IoDigest {
+    /// Digest of the postcard-serialized inputs, in argument order.
+    pub input: [u8; 32],
+    /// Digest of the postcard-serialized return value.
+    pub output: [u8; 32],
+}
+
+impl IoDigest {
+    /// Number of bytes occupied by an `IoDigest` in the output region.
+    pub const SIZE: usize = 64;
+
+    pub fn keccak256(input_bytes: &[u8], output_bytes: &[u8]) -> Self {
+        Self {
+            input: keccak256(input_bytes),
+            output: keccak256(output_bytes),
+        }
+    }
+
+    pub fn sha256(input_bytes: &[u8], output_bytes: &[u8]) -> Self {
+        Self {
+            input: sha256(input_bytes),
+            output: sha256(output_bytes),
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[..32].copy_from_slice(&self.input);
+        bytes[32..].copy_from_slice(&self.output);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::SIZE {
+            return None;
+        }
+        let mut digest = Self {
+            input: [0u8; 32],
+            output: [0u8; 32],
+        };
+        digest.input.copy_from_slice(&bytes[..32]);
+        digest.output.copy_from_slice(&bytes[32..]);
+        Some(digest)
+    }
+}
+
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+pub fn sha256(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(bytes).into()
+}
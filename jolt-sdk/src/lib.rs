@@ -2,7 +2,7 @@
 
 extern crate jolt_sdk_macros;
 
-pub use jolt_sdk_macros::provable;
+pub use jolt_sdk_macros::{provable, test};
 pub use postcard;
 
 #[cfg(feature = "host")]
@@ -10,5 +10,19 @@ pub mod host_utils;
 #[cfg(feature = "host")]
 pub use host_utils::*;
 
+#[cfg(feature = "host")]
+pub mod preprocessing_cache;
+#[cfg(feature = "host")]
+pub use preprocessing_cache::preprocess_cached;
+
 pub mod alloc;
 pub use alloc::*;
+
+pub mod io_commitment;
+pub use io_commitment::IoDigest;
+
+pub mod panic;
+pub use panic::*;
+
+pub mod dispatch;
+pub use dispatch::*;
@@ -0,0 +1,202 @@
+//! Support for proving that a guest panicked. A panicking guest sets the panic bit
+//! in the IO region and, if built with `#[provable(panic_message)]`, first writes
+//! its panic message to the output region.
+
+use core::fmt;
+
+#[cfg(feature = "host")]
+use crate::{bn254::JoltHyperKZGProof, ProverError};
+
+/// Writes a panic message into the (otherwise unused) output region of a panicking
+/// guest, truncating it if it does not fit.
+pub struct PanicMessageWriter<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> PanicMessageWriter<'a> {
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self { buffer, len: 0 }
+    }
+}
+
+impl fmt::Write for PanicMessageWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let n = s.len().min(self.buffer.len() - self.len);
+        self.buffer[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        Ok(())
+    }
+}
+
+/// Returned (alongside a proof) by a generated `prove_*` function when the guest panicked.
+#[cfg(feature = "host")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GuestPanic {
+    /// The guest's panic message, if it was built with `#[provable(panic_message)]`.
+    pub message: Option<String>,
+}
+
+#[cfg(feature = "host")]
+impl GuestPanic {
+    /// Recovers the panic message written by the guest to its output region.
+    pub fn from_outputs(outputs: &[u8]) -> Self {
+        let len = outputs
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |i| i + 1);
+        Self {
+            message: Some(String::from_utf8_lossy(&outputs[..len]).into_owned()),
+        }
+    }
+}
+
+#[cfg(feature = "host")]
+impl fmt::Display for GuestPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "guest panicked: {message}"),
+            None => write!(f, "guest panicked"),
+        }
+    }
+}
+
+/// Returned by a generated `prove_*` function when it does not return the guest's
+/// output. If the guest returned an error or panicked, the proof of that outcome is
+/// returned alongside it.
+#[cfg(feature = "host")]
+pub enum GuestError<E = core::convert::Infallible, P = JoltHyperKZGProof> {
+    /// The guest returned `Err(error)`.
+    Err { error: E, proof: P },
+    /// The guest panicked.
+    Panic { panic: GuestPanic, proof: P },
+    /// Proving failed, so there is no proof.
+    Prover(ProverError),
+}
+
+#[cfg(feature = "host")]
+impl<E, P> GuestError<E, P> {
+    /// The proof that the guest returned an error or panicked, if there is one.
+    pub fn proof(&self) -> Option<&P> {
+        match self {
+            Self::Err { proof, .. } | Self::Panic { proof, .. } => Some(proof),
+            Self::Prover(_) => None,
+        }
+    }
+
+    pub fn into_proof(self) -> Option<P> {
+        match self {
+            Self::Err { proof, .. } | Self::Panic { proof, .. } => Some(proof),
+            Self::Prover(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "host")]
+impl<E, P> From<ProverError> for GuestError<E, P> {
+    fn from(error: ProverError) -> Self {
+        Self::Prover(error)
+    }
+}
+
+#[cfg(feature = "host")]
+impl<E: fmt::Debug, P> fmt::Debug for GuestError<E, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Err { error, .. } => f
+                .debug_struct("Err")
+                .field("error", error)
+                .finish_non_exhaustive(),
+            Self::Panic { panic, .. } => f
+                .debug_struct("Panic")
+                .field("panic", panic)
+                .finish_non_exhaustive(),
+            Self::Prover(error) => f.debug_tuple("Prover").field(error).finish(),
+        }
+    }
+}
+
+#[cfg(feature = "host")]
+impl<E: fmt::Debug, P> fmt::Display for GuestError<E, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Err { error, .. } => write!(f, "guest returned an error: {error:?}"),
+            Self::Panic { panic, .. } => write!(f, "{panic}"),
+            Self::Prover(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+#[cfg(feature = "host")]
+impl<E: fmt::Debug, P> std::error::Error for GuestError<E, P> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Prover(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Write;
+
+    #[test]
+    fn panic_message_writer_truncates() {
+        let mut buffer = [0u8; 8];
+        let mut writer = PanicMessageWriter::new(&mut buffer);
+        write!(writer, "index {} out of bounds", 17).unwrap();
+        assert_eq!(&buffer, b"index 17");
+
+        let mut buffer = [0u8; 8];
+        let mut writer = PanicMessageWriter::new(&mut buffer);
+        write!(writer, "oops").unwrap();
+        assert_eq!(&buffer, b"oops\0\0\0\0");
+    }
+
+    #[cfg(feature = "host")]
+    #[test]
+    fn guest_panic_from_outputs() {
+        let mut outputs = [0u8; 32];
+        let mut writer = PanicMessageWriter::new(&mut outputs);
+        write!(writer, "invalid header: {:#x}", 0xdead).unwrap();
+        let guest_panic = GuestPanic::from_outputs(&outputs);
+        assert_eq!(
+            guest_panic.message.as_deref(),
+            Some("invalid header: 0xdead")
+        );
+        assert_eq!(
+            guest_panic.to_string(),
+            "guest panicked: invalid header: 0xdead"
+        );
+
+        // A guest that panicked without a message leaves the output region zeroed
+        assert_eq!(
+            GuestPanic::from_outputs(&[0; 32]).message.as_deref(),
+            Some("")
+        );
+        assert_eq!(GuestPanic::default().to_string(), "guest panicked");
+    }
+
+    #[cfg(feature = "host")]
+    #[test]
+    fn guest_error_carries_proof() {
+        let error: GuestError<u8, &str> = GuestError::Err {
+            error: 3,
+            proof: "proof",
+        };
+        assert_eq!(error.proof(), Some(&"proof"));
+        assert_eq!(error.to_string(), "guest returned an error: 3");
+
+        let error: GuestError<u8, &str> = GuestError::Panic {
+            panic: GuestPanic::default(),
+            proof: "proof",
+        };
+        assert_eq!(error.into_proof(), Some("proof"));
+
+        let error: GuestError<u8, &str> = ProverError::Cancelled.into();
+        assert!(error.proof().is_none());
+        assert_eq!(error.to_string(), "Proving was cancelled");
+    }
+}
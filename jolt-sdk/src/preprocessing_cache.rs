@@ -0,0 +1,306 @@
+//! Preprocessing of guest programs, cached on disk so that it only runs the first time a
+//! guest is preprocessed with a given ELF, memory layout, size bounds and SRS. The PCS
+//! setup is derived from the SRS at `$JOLT_SRS_PATH` or, with the `insecure-setup`
+//! feature, from a publicly known SRS.
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read},
+    path::{Path, PathBuf},
+};
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use common::rv_trace::MemoryLayout;
+use jolt_core::{
+    host::Program,
+    poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme},
+};
+use sha3::{Digest, Sha3_256};
+
+use crate::bn254::{F, PCS};
+use crate::{Jolt, JoltPreprocessing, ProofTranscript, RV32IJoltVM};
+
+type Srs = <PCS as CommitmentScheme<ProofTranscript>>::SRS;
+type Setup = <PCS as CommitmentScheme<ProofTranscript>>::Setup;
+type VerifierSetup = <PCS as CommitmentScheme<ProofTranscript>>::VerifierSetup;
+type Preprocessing = JoltPreprocessing<4, F, PCS, ProofTranscript>;
+
+/// Path of the SRS to preprocess with: a snarkjs `.ptau` file, or an SRS file written by
+/// `jolt-core trim-srs`. SRS files are memory-mapped, so they must not be modified while
+/// the process runs.
+pub const SRS_PATH_ENV: &str = "JOLT_SRS_PATH";
+
+/// Bumped whenever the layout of the cached preprocessing changes, which invalidates
+/// previously cached entries.
+const CACHE_VERSION: u32 = 4;
+
+/// Overrides the directory the cache is stored in.
+pub const CACHE_DIR_ENV: &str = "JOLT_PREPROCESSING_CACHE_DIR";
+
+/// Preprocesses `program` for proving runs within the given bounds.
+///
+/// If `$JOLT_SRS_PATH` is set, the PCS setup is derived from the SRS at that path.
+/// Otherwise, with the `insecure-setup` feature, it is derived from a publicly known SRS.
+/// Either way, the preprocessing cached by an earlier call with the same ELF, memory
+/// layout, bounds and SRS file contents is reused if there is one. On a cache miss the
+/// program is preprocessed as usual and the result is cached.
+///
+/// Cached preprocessing is deserialized with full validation, and is only used if its
+/// verifier setup matches the one derived from the SRS independently of the cache, so a
+/// tampered cache entry cannot swap in a setup with a known trapdoor. The cache lives in
+/// `$JOLT_PREPROCESSING_CACHE_DIR`, or in `jolt/preprocessing` under the user's cache
+/// directory, which is created readable by the user only.
+///
+/// # Panics
+/// If the SRS cannot be loaded or is too small for the given bounds, or if
+/// `$JOLT_SRS_PATH` is not set and the `insecure-setup` feature is disabled.
+pub fn preprocess_cached(
+    program: &mut Program,
+    memory_layout: MemoryLayout,
+    max_bytecode_size: usize,
+    max_memory_address: usize,
+    max_trace_length: usize,
+) -> Preprocessing {
+    let source = SetupSource::from_env();
+    let bounds = [max_bytecode_size, max_memory_address, max_trace_length];
+    let elf_contents = program.elf_contents();
+    let path = cache_dir().map(|dir| {
+        entry_path(
+            &dir,
+            &cache_key(&elf_contents, &memory_layout, bounds, &source),
+        )
+    });
+
+    if let Some(preprocessing) = path.as_deref().and_then(|path| load(path, &source)) {
+        return preprocessing;
+    }
+
+    let generators = source.setup(&commitment_shapes(bounds));
+    let (bytecode, memory_init) = tracer::decode(&elf_contents);
+    let preprocessing =
+        RV32IJoltVM::preprocess_with_generators(bytecode, memory_layout, memory_init, generators);
+    // The cache is only an optimization, so failing to write it is not an error.
+    if let Some(path) = &path {
+        let _ = store(path, &preprocessing);
+    }
+    preprocessing
+}
+
+/// Where the PCS setup is derived from.
+enum SetupSource {
+    /// The SRS at `$JOLT_SRS_PATH`.
+    SrsFile(PathBuf),
+    /// The publicly known SRS of [`CommitmentScheme::insecure_srs`].
+    Insecure,
+}
+
+impl SetupSource {
+    fn from_env() -> Self {
+        match std::env::var_os(SRS_PATH_ENV) {
+            Some(srs_path) => Self::SrsFile(PathBuf::from(srs_path)),
+            None => Self::Insecure,
+        }
+    }
+
+    fn setup(&self, shapes: &[CommitShape]) -> Setup {
+        match self {
+            Self::SrsFile(path) => setup_from_srs_file(path, shapes),
+            Self::Insecure => insecure_setup(shapes),
+        }
+    }
+
+    /// The verifier setup, derived without the prover's powers: the HyperKZG verifier key
+    /// only depends on the first powers of the SRS.
+    fn verifier_setup(&self) -> VerifierSetup {
+        match self {
+            Self::SrsFile(path) => <PCS as CommitmentScheme<ProofTranscript>>::verifier_setup(
+                &setup_from_srs_file(path, &[CommitShape::new(1, BatchType::Small)]),
+            ),
+            Self::Insecure => insecure_verifier_setup(),
+        }
+    }
+}
+
+fn commitment_shapes(
+    [max_bytecode_size, max_memory_address, max_trace_length]: [usize; 3],
+) -> Vec<CommitShape> {
+    <RV32IJoltVM as Jolt<F, PCS, 4, { 1 << 16 }, ProofTranscript>>::commitment_shapes(
+        max_bytecode_size,
+        max_memory_address,
+        max_trace_length,
+    )
+}
+
+fn setup_from_srs_file(path: &Path, shapes: &[CommitShape]) -> Setup {
+    let (num_g1_powers, num_g2_powers) = PCS::srs_size(shapes);
+    // SAFETY: the SRS at `$JOLT_SRS_PATH` is documented to not be modified while the process
+    // runs
+    unsafe { Srs::from_file(path, num_g1_powers, num_g2_powers) }
+        .and_then(|srs| <PCS as CommitmentScheme<ProofTranscript>>::setup_from_srs(&srs, shapes))
+        .unwrap_or_else(|err| panic!("failed to load the SRS at {}: {err}", path.display()))
+}
+
+#[cfg(not(feature = "insecure-setup"))]
+fn insecure_setup(_shapes: &[CommitShape]) -> Setup {
+    missing_srs()
+}
+
+#[cfg(not(feature = "insecure-setup"))]
+fn insecure_verifier_setup() -> VerifierSetup {
+    missing_srs()
+}
+
+#[cfg(not(feature = "insecure-setup"))]
+fn missing_srs() -> ! {
+    panic!(
+        "${SRS_PATH_ENV} must point at an SRS, or the `insecure-setup` feature of jolt-sdk \
+         must be enabled to use a publicly known one"
+    )
+}
+
+#[cfg(feature = "insecure-setup")]
+fn insecure_setup(shapes: &[CommitShape]) -> Setup {
+    <PCS as CommitmentScheme<ProofTranscript>>::setup(shapes)
+}
+
+#[cfg(feature = "insecure-setup")]
+fn insecure_verifier_setup() -> VerifierSetup {
+    PCS::insecure_verifier_setup()
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    std::env::var_os(CACHE_DIR_ENV)
+        .map(PathBuf::from)
+        .or_else(|| Some(dirs::cache_dir()?.join("jolt").join("preprocessing")))
+}
+
+fn entry_path(dir: &Path, key: &str) -> PathBuf {
+    dir.join(format!("{key}.bin"))
+}
+
+fn cache_key(
+    elf_contents: &[u8],
+    memory_layout: &MemoryLayout,
+    bounds: [usize; 3],
+    source: &SetupSource,
+) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(CACHE_VERSION.to_le_bytes());
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    for type_name in [std::any::type_name::<F>(), std::any::type_name::<PCS>()] {
+        hasher.update((type_name.len() as u64).to_le_bytes());
+        hasher.update(type_name.as_bytes());
+    }
+    hasher.update((elf_contents.len() as u64).to_le_bytes());
+    hasher.update(elf_contents);
+    hasher.update(postcard::to_stdvec(memory_layout).unwrap());
+    for bound in bounds {
+        hasher.update((bound as u64).to_le_bytes());
+    }
+    match source {
+        SetupSource::SrsFile(path) => {
+            hasher.update([0]);
+            hasher.update(file_digest(path));
+        }
+        SetupSource::Insecure => hasher.update([1]),
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn file_digest(path: &Path) -> [u8; 32] {
+    let hash_file = || -> std::io::Result<[u8; 32]> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut hasher = Sha3_256::new();
+        let mut buffer = vec![0u8; 1 << 20];
+        loop {
+            match reader.read(&mut buffer)? {
+                0 => return Ok(hasher.finalize().into()),
+                len => hasher.update(&buffer[..len]),
+            }
+        }
+    };
+    hash_file().unwrap_or_else(|err| panic!("failed to read the SRS at {}: {err}", path.display()))
+}
+
+/// Loads the cached preprocessing at `path`, if it exists, is valid, and its verifier setup
+/// matches the one derived from `source`.
+fn load(path: &Path, source: &SetupSource) -> Option<Preprocessing> {
+    let file = File::open(path).ok()?;
+    let preprocessing = Preprocessing::deserialize_uncompressed(BufReader::new(file)).ok()?;
+    let verifier_setup =
+        <PCS as CommitmentScheme<ProofTranscript>>::verifier_setup(&preprocessing.generators);
+    (digest(&verifier_setup) == digest(&source.verifier_setup())).then_some(preprocessing)
+}
+
+fn digest(value: &impl CanonicalSerialize) -> [u8; 32] {
+    let mut bytes = vec![];
+    value
+        .serialize_compressed(&mut bytes)
+        .expect("serializing to a Vec cannot fail");
+    Sha3_256::digest(bytes).into()
+}
+
+fn store(path: &Path, preprocessing: &Preprocessing) -> Result<(), Box<dyn std::error::Error>> {
+    create_private_dir(path.parent().unwrap())?;
+    // Write to a temporary file first so that a concurrent reader never sees a
+    // partially written cache entry.
+    let tmp_path = path.with_extension(format!("tmp{}", std::process::id()));
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+    preprocessing.serialize_uncompressed(&mut writer)?;
+    writer.into_inner()?.sync_all()?;
+    fs::rename(tmp_path, path)?;
+    Ok(())
+}
+
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir)
+}
+
+#[cfg(all(test, feature = "insecure-setup"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_elf_invalidates_cache_entry() {
+        let dir = std::env::temp_dir().join(format!(
+            "jolt-preprocessing-cache-test-{}",
+            std::process::id()
+        ));
+        let memory_layout = MemoryLayout::new(64, 64);
+        let bounds = [1 << 10, 1 << 16, 1 << 10];
+        let source = SetupSource::Insecure;
+        let elf_contents = b"\x7fELF guest".to_vec();
+        let mut changed_elf_contents = elf_contents.clone();
+        *changed_elf_contents.last_mut().unwrap() ^= 1;
+
+        let preprocessing: Preprocessing = RV32IJoltVM::preprocess_with_generators(
+            vec![],
+            memory_layout.clone(),
+            vec![],
+            source.setup(&commitment_shapes(bounds)),
+        );
+        let path = entry_path(
+            &dir,
+            &cache_key(&elf_contents, &memory_layout, bounds, &source),
+        );
+        store(&path, &preprocessing).unwrap();
+        assert!(load(&path, &source).is_some());
+
+        let changed_path = entry_path(
+            &dir,
+            &cache_key(&changed_elf_contents, &memory_layout, bounds, &source),
+        );
+        assert_ne!(changed_path, path);
+        assert!(load(&changed_path, &source).is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -6,49 +6,88 @@ use std::{
     path::Path,
 };
 
+use common::rv_trace::MemoryLayout;
 use eyre::Result;
-use jolt_core::host::{ELFInstruction, Program};
-use rmp_serde::Serializer;
-use serde::{Deserialize, Serialize};
+use jolt_core::host::Program;
+use jolt_core::jolt::vm::{
+    rv32i_vm::{
+        JoltHyperKZGVerifierPreprocessing, ProofTranscript, RV32IJoltVM, Serializable, C, M, PCS,
+    },
+    Jolt,
+};
+use jolt_core::poly::commitment::kzg::SRS;
 use syn::{Attribute, ItemFn, Meta, PathSegment};
-use toml_edit::{value, Array, DocumentMut, InlineTable, Item, Table, Value};
+use toml_edit::{value, Array, DocumentMut, Item, Table};
 
-#[derive(Serialize, Deserialize)]
-struct DecodedData {
-    bytecode: Vec<ELFInstruction>,
-    memory_init: Vec<(u64, u8)>,
-}
+/// Same as the SDK's: the SRS to preprocess with, falling back to a publicly known one.
+const SRS_PATH_ENV: &str = "JOLT_SRS_PATH";
 
 struct FunctionAttributes {
     pub func_name: String,
     pub attributes: Attributes,
 }
 
+/// Preprocesses `func_name` the same way its generated `preprocess_*` function does and
+/// writes the resulting verifier key, which the wasm verifier loads instead of
+/// preprocessing the program itself.
 fn preprocess_and_save(func_name: &str, attributes: &Attributes, is_std: bool) -> Result<()> {
+    let private_inputs = attributes.io_commitment.is_some();
     let mut program = Program::new("guest");
 
     program.set_func(func_name);
     program.set_std(is_std);
     program.set_memory_size(attributes.memory_size);
     program.set_stack_size(attributes.stack_size);
+    if let Some(heap_size) = attributes.heap_size {
+        program.set_heap_size(heap_size);
+    }
     program.set_max_input_size(attributes.max_input_size);
     program.set_max_output_size(attributes.max_output_size);
+    program.set_private_inputs(private_inputs);
 
     let (bytecode, memory_init) = program.decode();
-    let decoded_data = DecodedData {
-        bytecode,
-        memory_init,
+    let mut memory_layout =
+        MemoryLayout::new(attributes.max_input_size, attributes.max_output_size);
+    memory_layout.private_inputs = private_inputs;
+
+    let (max_bytecode_size, max_memory_address, max_trace_length) = (1 << 20, 1 << 20, 1 << 24);
+    let preprocessing = match std::env::var_os(SRS_PATH_ENV) {
+        Some(srs_path) => {
+            let shapes = <RV32IJoltVM as Jolt<_, PCS, C, M, ProofTranscript>>::commitment_shapes(
+                max_bytecode_size,
+                max_memory_address,
+                max_trace_length,
+            );
+            let (num_g1_powers, num_g2_powers) = PCS::srs_size(&shapes);
+            // SAFETY: the SRS at `$JOLT_SRS_PATH` must not be modified while it is loaded
+            let srs = unsafe { SRS::from_file(srs_path, num_g1_powers, num_g2_powers) }?;
+            <RV32IJoltVM as Jolt<_, PCS, C, M, ProofTranscript>>::preprocess(
+                bytecode,
+                memory_layout,
+                memory_init,
+                &srs,
+                max_bytecode_size,
+                max_memory_address,
+                max_trace_length,
+            )?
+        }
+        None => <RV32IJoltVM as Jolt<_, PCS, C, M, ProofTranscript>>::insecure_preprocess(
+            bytecode,
+            memory_layout,
+            memory_init,
+            max_bytecode_size,
+            max_memory_address,
+            max_trace_length,
+        ),
     };
-
-    let mut buf = Vec::new();
-    decoded_data.serialize(&mut Serializer::new(&mut buf))?;
+    let verifier_key = JoltHyperKZGVerifierPreprocessing::from(&preprocessing);
 
     let target_dir = Path::new("target/wasm32-unknown-unknown/release");
     fs::create_dir_all(target_dir)?;
 
-    let output_path = target_dir.join(format!("preprocessed_{}.bin", func_name));
+    let output_path = target_dir.join(format!("verifier_key_{}.bin", func_name));
     let mut file = File::create(output_path)?;
-    file.write_all(&buf)?;
+    file.write_all(&verifier_key.serialize_to_bytes()?)?;
     Ok(())
 }
 
@@ -179,13 +218,16 @@ fn create_index_html(func_names: Vec<String>) -> Result<()> {
                     const proofArrayBuffer = event.target.result;
                     const proofData = new Uint8Array(proofArrayBuffer);
 
-                    // Fetch preprocessing data and prepare wasm binary to json conversion
-                    const response = await fetch('target/wasm32-unknown-unknown/release/preprocessed_{0}.bin')
-                    const wasmBinary = await response.arrayBuffer();
-                    const wasmData = new Uint8Array(wasmBinary);
+                    // Fetch the verifier key written by `jolt build-wasm`
+                    const response = await fetch('target/wasm32-unknown-unknown/release/verifier_key_{0}.bin');
+                    const verifierKey = new Uint8Array(await response.arrayBuffer());
 
-                    const result = verify_{0}(wasmData, proofData);
-                    alert(result ? "Proof is valid!" : "Proof is invalid.");
+                    try {{
+                        verify_{0}(verifierKey, proofData);
+                        alert("Proof is valid!");
+                    }} catch (error) {{
+                        alert(`Proof is invalid: ${{error.message}}`);
+                    }}
                 }};
 
                 reader.readAsArrayBuffer(file);
@@ -205,17 +247,6 @@ fn create_index_html(func_names: Vec<String>) -> Result<()> {
 pub fn modify_cargo_toml(name: &str) -> Result<()> {
     fn add_dependencies(dependencies: &mut Table) {
         dependencies.insert("wasm-bindgen", toml_edit::value("0.2.73"));
-        dependencies.insert("serde", {
-            let mut table = InlineTable::new();
-            table.insert("version", Value::from("1.0"));
-            let mut features_array = Array::new();
-            features_array.push("derive");
-            table.insert("features", Value::Array(features_array));
-            Item::Value(Value::InlineTable(table))
-        });
-        dependencies.insert("serde_json", toml_edit::value("1.0"));
-        dependencies.insert("serde-wasm-bindgen", toml_edit::value("=0.6.5"));
-        dependencies.insert("rmp-serde", toml_edit::value("1.3.0"));
     }
 
     {
@@ -186,7 +186,9 @@ codegen-units = 1
 lto = "fat"
 
 [dependencies]
-jolt = { package = "jolt-sdk", git = "https://github.com/a16z/jolt", features = ["host"] }
+# `insecure-setup` proves with a publicly known SRS. Remove it and set JOLT_SRS_PATH to
+# prove with the SRS of a trusted setup ceremony.
+jolt = { package = "jolt-sdk", git = "https://github.com/a16z/jolt", features = ["host", "insecure-setup"] }
 guest = { path = "./guest" }
 
 [patch.crates-io]
@@ -198,7 +200,7 @@ ark-serialize = { git = "https://github.com/a16z/arkworks-algebra", branch = "op
 const HOST_MAIN: &str = r#"pub fn main() {
     let (prove_fib, verify_fib) = guest::build_fib();
 
-    let (output, proof) = prove_fib(50);
+    let (output, proof) = prove_fib(50).unwrap();
     let is_valid = verify_fib(proof);
 
     println!("output: {}", output);
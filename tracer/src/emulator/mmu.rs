@@ -519,6 +519,15 @@ impl Mmu {
         self.store_bytes(v_address, value, 8)
     }
 
+    /// Reads a byte from main memory without tracing the access, e.g. to inspect
+    /// the guest's memory once it has terminated.
+    ///
+    /// # Arguments
+    /// * `p_address` Physical address, which must be in main memory
+    pub fn peek_byte(&mut self, p_address: u64) -> u8 {
+        self.memory.read_byte(p_address)
+    }
+
     /// Loads a byte from main memory or peripheral devices depending on
     /// physical address.
     ///
@@ -3,14 +3,17 @@
 
 use std::{fs::File, io::Read, path::PathBuf};
 
-use common::{self, constants::RAM_START_ADDRESS};
+use common::{
+    self,
+    constants::{PRIVATE_OUTPUT_SYMBOL, RAM_START_ADDRESS},
+};
 use emulator::{
     cpu::{self, Xlen},
     default_terminal::DefaultTerminal,
     Emulator,
 };
 
-use object::{Object, ObjectSection, SectionKind};
+use object::{Object, ObjectSection, ObjectSymbol, SectionKind};
 
 mod decode;
 mod emulator;
@@ -41,6 +44,7 @@ pub fn trace(
 
     let mut elf_contents = Vec::new();
     elf_file.read_to_end(&mut elf_contents).unwrap();
+    let private_output = private_output_buffer(&elf_contents);
 
     emulator.setup_program(elf_contents);
 
@@ -64,11 +68,25 @@ pub fn trace(
     output.append(&mut rows);
     drop(rows);
 
-    let device = emulator.get_mut_cpu().get_mut_mmu().jolt_device.clone();
+    let mmu = emulator.get_mut_cpu().get_mut_mmu();
+    let mut device = mmu.jolt_device.clone();
+    if let Some((address, size)) = private_output {
+        device.private_outputs = (address..address + size)
+            .map(|address| mmu.peek_byte(address))
+            .collect();
+    }
 
     (output, device)
 }
 
+/// Address and size of the guest's private output buffer, if it declares one.
+fn private_output_buffer(elf: &[u8]) -> Option<(u64, u64)> {
+    let obj = object::File::parse(elf).unwrap();
+    obj.symbols()
+        .find(|symbol| symbol.name() == Ok(PRIVATE_OUTPUT_SYMBOL))
+        .map(|symbol| (symbol.address(), symbol.size()))
+}
+
 #[tracing::instrument(skip_all)]
 pub fn decode(elf: &[u8]) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
     let obj = object::File::parse(elf).unwrap();